@@ -0,0 +1,174 @@
+// src/adopt.rs
+// RAM Eating Pet Simulator - Adopted Pets (Process Mirror Mode)
+
+use anyhow::{bail, Result};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::system::monitor::{ProcessInfo, RamTracker};
+use crate::system::sampler::{FreshSnapshots, SystemSnapshot};
+use crate::system::units::MiB;
+
+/// Samples of the adopted process's RSS its hunger is judged over
+const GROWTH_SAMPLES: usize = 30;
+
+/// Growth, as a share of its size over the sample window, that makes an
+/// adopted pet ravenous
+const RAVENOUS_GROWTH: f32 = 0.2;
+
+/// Which process to adopt (`--adopt <pid|name>`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdoptTarget {
+    /// An exact process ID
+    Pid(u32),
+    /// A process name; the biggest match wins
+    Name(String),
+}
+
+impl AdoptTarget {
+    /// Find the process to adopt in a table sorted biggest first
+    ///
+    /// Names match exactly first, then case-insensitively anywhere in the
+    /// process name. We never adopt ourselves.
+    pub fn find<'a>(&self, table: &'a [ProcessInfo]) -> Option<&'a ProcessInfo> {
+        let ours = std::process::id();
+        let others = || table.iter().filter(move |process| process.pid != ours);
+        match self {
+            AdoptTarget::Pid(pid) => others().find(|process| process.pid == *pid),
+            AdoptTarget::Name(name) => {
+                let lower = name.to_lowercase();
+                others()
+                    .find(|process| process.name == *name)
+                    .or_else(|| others().find(|process| process.name.to_lowercase().contains(&lower)))
+            }
+        }
+    }
+}
+
+impl fmt::Display for AdoptTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AdoptTarget::Pid(pid) => write!(f, "pid {}", pid),
+            AdoptTarget::Name(name) => write!(f, "'{}'", name),
+        }
+    }
+}
+
+impl FromStr for AdoptTarget {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s.is_empty() {
+            bail!("--adopt needs a process ID or name");
+        }
+        Ok(s.parse().map_or_else(|_| AdoptTarget::Name(s.to_string()), AdoptTarget::Pid))
+    }
+}
+
+/// What an adopted process was doing at the last sample
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Sighting {
+    /// Still running with this RSS; hunger (0 - 100) follows how fast it grows
+    Alive { size: MiB, hunger: f32 },
+    /// The process has exited
+    Gone,
+}
+
+/// A process the pet mirrors instead of eating RAM itself
+///
+/// The pet locks onto the PID it was adopted with, so a restarted service
+/// with the same name is a new pet, not the old one coming back.
+pub struct Adoption {
+    pid: u32,
+    name: String,
+    usage: RamTracker,
+    fresh: FreshSnapshots,
+}
+
+impl Adoption {
+    /// Adopt the process found in the process table
+    pub fn new(process: &ProcessInfo) -> Self {
+        Adoption {
+            pid: process.pid,
+            name: process.name.clone(),
+            usage: RamTracker::new(GROWTH_SAMPLES),
+            fresh: FreshSnapshots::default(),
+        }
+    }
+
+    /// PID of the adopted process
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    /// Name of the adopted process
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Look for the process in a new snapshot
+    ///
+    /// Returns `None` when the snapshot was already seen or doesn't have
+    /// the process table yet, so a missing table never looks like an exit.
+    pub fn observe(&mut self, snapshot: &SystemSnapshot) -> Option<Sighting> {
+        let table = self.fresh.processes(snapshot)?;
+
+        let size = match table.iter().find(|process| process.pid == self.pid) {
+            Some(process) => process.memory_mb,
+            None => return Some(Sighting::Gone),
+        };
+        self.usage.record_usage(snapshot.taken_at, size);
+
+        let growth = self.usage.get_trend().max(0) as f32 / size.0.max(1) as f32;
+        let hunger = (growth / RAVENOUS_GROWTH * 100.0).min(100.0);
+        Some(Sighting::Alive { size, hunger })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    fn process(pid: u32, name: &str, mb: usize) -> ProcessInfo {
+        ProcessInfo { pid, name: name.to_string(), memory_mb: MiB(mb), uid: None, user: None }
+    }
+
+    fn snapshot(secs: u64, table: Vec<ProcessInfo>) -> SystemSnapshot {
+        SystemSnapshot::fake(Instant::now() + Duration::from_secs(secs), table)
+    }
+
+    #[test]
+    fn test_find_target() {
+        let table = vec![process(10, "postgres: writer", 900), process(11, "postgres", 400), process(12, "nginx", 50)];
+
+        assert_eq!("12".parse::<AdoptTarget>().unwrap(), AdoptTarget::Pid(12));
+        assert_eq!(AdoptTarget::Pid(12).find(&table).unwrap().name, "nginx");
+        assert_eq!(AdoptTarget::Name("postgres".into()).find(&table).unwrap().pid, 11);
+        assert_eq!(AdoptTarget::Name("WRITER".into()).find(&table).unwrap().pid, 10);
+        assert!(AdoptTarget::Name("redis".into()).find(&table).is_none());
+        assert!("".parse::<AdoptTarget>().is_err());
+    }
+
+    #[test]
+    fn test_observe_growth_and_exit() {
+        let mut adoption = Adoption::new(&process(10, "leaky", 100));
+
+        let first = snapshot(1, vec![process(10, "leaky", 100)]);
+        assert_eq!(adoption.observe(&first), Some(Sighting::Alive { size: MiB(100), hunger: 0.0 }));
+        assert_eq!(adoption.observe(&first), None);
+        assert_eq!(adoption.observe(&SystemSnapshot { processes: None, ..snapshot(2, vec![]) }), None);
+
+        // Growing 10 MB on 110 over the window is a bit under halfway to ravenous
+        match adoption.observe(&snapshot(3, vec![process(10, "leaky", 110)])) {
+            Some(Sighting::Alive { size, hunger }) => {
+                assert_eq!(size, MiB(110));
+                assert!((hunger - 45.45).abs() < 0.1);
+            }
+            other => panic!("unexpected sighting {:?}", other),
+        }
+
+        assert_eq!(adoption.observe(&snapshot(4, vec![process(11, "leaky", 110)])), Some(Sighting::Gone));
+    }
+}
@@ -0,0 +1,232 @@
+// src/cli.rs
+// RAM Eating Pet Simulator - Command Line Arguments
+
+use anyhow::{anyhow, bail, Result};
+use std::path::PathBuf;
+
+use crate::adopt::AdoptTarget;
+
+/// What the program was asked to do
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Play the interactive game (default)
+    Play,
+    /// Print usage and exit
+    Help,
+    /// Print the version and exit
+    Version,
+    /// Follow a diet plan non-interactively (`ram_pet diet <plan.toml>`)
+    Diet(PathBuf),
+    /// Mirror another process instead of eating RAM (`--adopt <pid|name>`)
+    Adopt(AdoptTarget),
+    /// Show a pet per user, sized by their processes (`ram_pet household`)
+    Household,
+    /// Manage save slots from the shell (`ram_pet saves ...`)
+    Saves(SavesCommand),
+}
+
+/// What to do with the save slots
+#[derive(Debug, Clone, PartialEq)]
+pub enum SavesCommand {
+    /// `saves list`
+    List,
+    /// `saves rm <slot>`
+    Remove(String),
+    /// `saves mv <from> <to>`
+    Rename { from: String, to: String },
+    /// `saves cp <from> <to>`
+    Copy { from: String, to: String },
+}
+
+/// Parsed command line arguments
+#[derive(Debug, Clone, PartialEq)]
+pub struct CliArgs {
+    /// What to run
+    pub command: Command,
+    /// Explicit config file (`--config`)
+    pub config_path: Option<PathBuf>,
+    /// Config overrides as `(key, value)` pairs, applied after the environment
+    pub overrides: Vec<(String, String)>,
+}
+
+impl CliArgs {
+    /// Parse arguments from the process command line
+    pub fn from_env() -> Result<Self> {
+        Self::parse(std::env::args().skip(1))
+    }
+
+    /// Parse arguments (without the program name)
+    pub fn parse<I, S>(args: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut parsed = CliArgs {
+            command: Command::Play,
+            config_path: None,
+            overrides: Vec::new(),
+        };
+
+        let mut args = args.into_iter().map(Into::into);
+        while let Some(arg) = args.next() {
+            // Support both `--flag value` and `--flag=value`
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+                _ => (arg.clone(), None),
+            };
+            let mut value = |name: &str| -> Result<String> {
+                inline.clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| anyhow!("{} needs a value", name))
+            };
+
+            match flag.as_str() {
+                "-h" | "--help" => parsed.command = Command::Help,
+                "-V" | "--version" => parsed.command = Command::Version,
+                "-c" | "--config" => parsed.config_path = Some(PathBuf::from(value("--config")?)),
+                "-d" | "--difficulty" => {
+                    parsed.overrides.push(("game.difficulty".to_string(), value("--difficulty")?));
+                }
+                "--save" => {
+                    parsed.overrides.push(("game.save_path".to_string(), value("--save")?));
+                }
+                "--no-color" => {
+                    parsed.overrides.push(("graphics.use_colors".to_string(), "false".to_string()));
+                }
+                "--no-sound" => {
+                    parsed.overrides.push(("game.sound_enabled".to_string(), "false".to_string()));
+                }
+                "household" if parsed.command == Command::Play => parsed.command = Command::Household,
+                "diet" if parsed.command == Command::Play => {
+                    parsed.command = Command::Diet(PathBuf::from(value("diet")?));
+                }
+                "saves" if parsed.command == Command::Play => {
+                    parsed.command = Command::Saves(match value("saves")?.as_str() {
+                        "list" | "ls" => SavesCommand::List,
+                        "rm" => SavesCommand::Remove(value("saves rm")?),
+                        "mv" => SavesCommand::Rename { from: value("saves mv")?, to: value("saves mv")? },
+                        "cp" => SavesCommand::Copy { from: value("saves cp")?, to: value("saves cp")? },
+                        other => bail!("unknown saves command '{}' (list, rm, mv or cp)", other),
+                    });
+                }
+                "--adopt" if parsed.command == Command::Play => {
+                    parsed.command = Command::Adopt(value("--adopt")?.parse()?);
+                }
+                "household" | "diet" | "saves" | "--adopt" => {
+                    bail!("'{}' can't be combined with another command", flag);
+                }
+                "--set" => {
+                    let setting = value("--set")?;
+                    let (key, val) = setting.split_once('=')
+                        .ok_or_else(|| anyhow!("--set expects key=value, got '{}'", setting))?;
+                    parsed.overrides.push((key.trim().to_string(), val.to_string()));
+                }
+                other => bail!("unknown argument '{}' (try --help)", other),
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    /// Usage text for `--help`
+    pub fn usage() -> String {
+        format!(
+            "{name} {version}\n\
+             A virtual pet that literally eats your computer's RAM to survive\n\
+             \n\
+             USAGE:\n    ram_pet [OPTIONS]\n\
+             \x20   ram_pet [OPTIONS] diet <PLAN.toml>\n\
+             \x20   ram_pet [OPTIONS] household\n\
+             \x20   ram_pet [OPTIONS] saves <list|rm|mv|cp>\n\
+             \n\
+             COMMANDS:\n\
+             \x20   diet <PLAN.toml>           Follow a diet plan without the game UI, then exit\n\
+             \x20                              (0 = followed, 3 = corruption, 4 = held back by the reserve)\n\
+             \x20   household                  Rank users by the RAM their processes use, one pet each\n\
+             \x20   saves list                 List the save slots next to the save file\n\
+             \x20   saves rm <SLOT>            Delete a save slot and its backups\n\
+             \x20   saves mv <SLOT> <NEW>      Rename a save slot\n\
+             \x20   saves cp <SLOT> <NEW>      Copy a save slot\n\
+             \n\
+             OPTIONS:\n\
+             \x20   -c, --config <PATH>        Config file (default: $XDG_CONFIG_HOME/ram_pet/config.toml or ./config.toml)\n\
+             \x20   -d, --difficulty <LEVEL>   easy, normal, hard or nightmare\n\
+             \x20       --save <PATH>          Save file location; other slots live in the same directory\n\
+             \x20       --adopt <PID|NAME>     Mirror another process's memory instead of eating RAM\n\
+             \x20       --set <KEY=VALUE>      Override any config value (e.g. pet.max_size_mb=1024)\n\
+             \x20       --no-color             Disable colored output\n\
+             \x20       --no-sound             Disable the terminal bell\n\
+             \x20   -h, --help                 Print this help\n\
+             \x20   -V, --version              Print version\n\
+             \n\
+             Settings are layered: defaults, config file, RAM_PET_<SECTION>_<KEY>\n\
+             environment variables (e.g. RAM_PET_SYSTEM_MIN_FREE_RAM_MB=2048), then options.",
+            name = crate::NAME,
+            version = crate::VERSION,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_defaults() {
+        let args = CliArgs::parse(Vec::<String>::new()).unwrap();
+        assert_eq!(args.command, Command::Play);
+        assert!(args.config_path.is_none());
+        assert!(args.overrides.is_empty());
+    }
+
+    #[test]
+    fn test_parse_overrides() {
+        let args = CliArgs::parse([
+            "--config", "my.toml", "--difficulty=hard", "--set", "pet.max_size_mb=512", "--no-color",
+        ]).unwrap();
+        assert_eq!(args.config_path, Some(PathBuf::from("my.toml")));
+        assert_eq!(args.overrides, vec![
+            ("game.difficulty".to_string(), "hard".to_string()),
+            ("pet.max_size_mb".to_string(), "512".to_string()),
+            ("graphics.use_colors".to_string(), "false".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_parse_diet() {
+        let args = CliArgs::parse(["--no-sound", "diet", "plans/ramp.toml"]).unwrap();
+        assert_eq!(args.command, Command::Diet(PathBuf::from("plans/ramp.toml")));
+        assert!(CliArgs::parse(["diet"]).is_err());
+        assert_eq!(CliArgs::parse(["household"]).unwrap().command, Command::Household);
+    }
+
+    #[test]
+    fn test_parse_saves() {
+        assert_eq!(CliArgs::parse(["saves", "list"]).unwrap().command, Command::Saves(SavesCommand::List));
+        assert_eq!(
+            CliArgs::parse(["saves", "mv", "old", "new"]).unwrap().command,
+            Command::Saves(SavesCommand::Rename { from: "old".to_string(), to: "new".to_string() })
+        );
+        assert!(CliArgs::parse(["saves", "rm"]).is_err());
+        assert!(CliArgs::parse(["saves", "shred", "x"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_adopt() {
+        let args = CliArgs::parse(["--adopt", "1234"]).unwrap();
+        assert_eq!(args.command, Command::Adopt(AdoptTarget::Pid(1234)));
+        let args = CliArgs::parse(["--adopt=postgres"]).unwrap();
+        assert_eq!(args.command, Command::Adopt(AdoptTarget::Name("postgres".to_string())));
+
+        // Adopting is its own way to play, not an option to another command
+        assert!(CliArgs::parse(["diet", "plan.toml", "--adopt", "1"]).is_err());
+        assert!(CliArgs::parse(["--adopt", "1", "saves", "list"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(CliArgs::parse(["--bogus"]).is_err());
+        assert!(CliArgs::parse(["--config"]).is_err());
+        assert!(CliArgs::parse(["--set", "novalue"]).is_err());
+    }
+}
@@ -1,11 +1,28 @@
 // src/config.rs
 // RAM Eating Pet Simulator - Configuration
 
+use anyhow::{anyhow, bail, Context};
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::time::Duration;
 
+use crate::system::food::FoodKind;
+use crate::system::memory::ResidencyPolicy;
+use crate::system::units::MiB;
+
+/// Prefix for environment variable overrides (e.g. `RAM_PET_PET_MAX_SIZE_MB`)
+pub const ENV_PREFIX: &str = "RAM_PET_";
+
+/// Environment variable naming an explicit config file
+pub const ENV_CONFIG_PATH: &str = "RAM_PET_CONFIG";
+
 /// Main configuration for the RAM Pet Simulator
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Every section falls back to its defaults, so config files only need
+/// to list the values they change.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
     pub pet: PetConfig,
     pub graphics: GraphicsConfig,
@@ -15,11 +32,12 @@ pub struct Config {
 
 /// Pet-related configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PetConfig {
     /// Starting size in MB
-    pub starting_size_mb: usize,
+    pub starting_size_mb: MiB,
     /// Maximum size in MB before "full"
-    pub max_size_mb: usize,
+    pub max_size_mb: MiB,
     /// Rate at which pet digests RAM (MB per second)
     pub metabolism_rate: f32,
     /// How fast the pet gets hungry (hunger per second)
@@ -28,10 +46,13 @@ pub struct PetConfig {
     pub critical_hunger: f32,
     /// Happiness decay rate
     pub happiness_decay: f32,
+    /// How many days the pet lives before dying of old age
+    pub lifespan_days: f32,
 }
 
 /// Graphics configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct GraphicsConfig {
     /// Enable colored output
     pub use_colors: bool,
@@ -45,19 +66,42 @@ pub struct GraphicsConfig {
 
 /// System configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SystemConfig {
     /// Minimum free RAM to maintain (MB)
-    pub min_free_ram_mb: usize,
+    pub min_free_ram_mb: MiB,
     /// Warning threshold for low RAM (MB)
-    pub warning_threshold_mb: usize,
+    pub warning_threshold_mb: MiB,
     /// Enable system monitoring
     pub monitoring: bool,
     /// Update interval for system stats
     pub update_interval: Duration,
+    /// Where the pet's food comes from (heap, mmap, memfd, file, hugepage)
+    pub food_source: FoodKind,
+    /// Directory for file-backed food (ideally a tmpfs)
+    pub food_dir: PathBuf,
+    /// How the pet's memory is kept in RAM (lazy, touch, retouch, mlock)
+    pub residency: ResidencyPolicy,
+    /// Run the safety governor that frees the pet's RAM when the host runs low
+    pub governor: bool,
+    /// How much of the pet's memory the integrity scrubber checks per
+    /// second (MB); 0 turns filling and scrubbing off
+    pub scrub_rate_mb: MiB,
+    /// Start in balloon mode, sizing the pet to the spare RAM
+    pub balloon: bool,
+    /// Share of the spare RAM above the reserve a balloon pet takes (0.0 - 1.0)
+    pub balloon_fraction: f32,
+    /// How many of the biggest processes to watch for leaks; 0 turns it off
+    pub leak_watch: usize,
+    /// How long a process has to keep growing to count as leaking (seconds)
+    pub leak_window_secs: u64,
+    /// Growth over the window that counts as leaking (MB per minute)
+    pub leak_slope_mb_per_min: f32,
 }
 
 /// Game configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct GameConfig {
     /// Auto-save interval (seconds)
     pub autosave_interval: u64,
@@ -65,48 +109,128 @@ pub struct GameConfig {
     pub sound_enabled: bool,
     /// Difficulty level (affects hunger rate)
     pub difficulty: Difficulty,
-    /// Save file path
+    /// Save file path; other save slots are the `.ramsave` files in the
+    /// same directory
     pub save_path: String,
+    /// Older saves kept next to the save file as `<save_path>.1`, `.2`, ...
+    pub backups: usize,
+    /// Most minutes of time away simulated when a save is loaded (0 = no limit)
+    pub offline_cap_mins: u64,
+    /// A pet sitter feeds the pet while the game is closed, so it can't
+    /// starve (on by default)
+    pub pet_sitter: bool,
+    /// What happens to a meal cancelled part-way through
+    pub cancelled_meal: CancelledMeal,
+}
+
+/// What to do with a partly allocated meal when the player cancels it
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CancelledMeal {
+    /// The pet keeps (and grows by) whatever was eaten so far
+    #[default]
+    Credit,
+    /// Everything allocated for the meal is released
+    Rollback,
 }
 
 /// Game difficulty levels
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
 pub enum Difficulty {
     Easy,
+    #[default]
     Normal,
     Hard,
     Nightmare,
 }
 
-impl Default for Config {
+impl Default for PetConfig {
+    fn default() -> Self {
+        PetConfig {
+            starting_size_mb: MiB(50),
+            max_size_mb: MiB(2048), // 2GB max
+            metabolism_rate: 1.0, // 1 MB per second
+            hunger_rate: 2.0,
+            critical_hunger: 80.0,
+            happiness_decay: 1.0,
+            lifespan_days: 1095.0, // Three years, so it sees two birthdays
+        }
+    }
+}
+
+impl Default for GraphicsConfig {
+    fn default() -> Self {
+        GraphicsConfig {
+            use_colors: true,
+            animations: true,
+            fps: 10,
+            debug_mode: false,
+        }
+    }
+}
+
+impl Default for SystemConfig {
+    fn default() -> Self {
+        SystemConfig {
+            min_free_ram_mb: MiB(1024), // Keep at least 1GB free
+            warning_threshold_mb: MiB(2048),
+            monitoring: true,
+            update_interval: Duration::from_secs(1),
+            food_source: FoodKind::Heap,
+            food_dir: PathBuf::from("/dev/shm"),
+            residency: ResidencyPolicy::Touch,
+            governor: true,
+            scrub_rate_mb: MiB(64),
+            balloon: false,
+            balloon_fraction: 0.5,
+            leak_watch: 5,
+            leak_window_secs: 600,
+            leak_slope_mb_per_min: 10.0,
+        }
+    }
+}
+
+impl Default for GameConfig {
     fn default() -> Self {
-        Config {
-            pet: PetConfig {
-                starting_size_mb: 50,
-                max_size_mb: 2048, // 2GB max
-                metabolism_rate: 1.0, // 1 MB per second
-                hunger_rate: 2.0,
-                critical_hunger: 80.0,
-                happiness_decay: 1.0,
-            },
-            graphics: GraphicsConfig {
-                use_colors: true,
-                animations: true,
-                fps: 10,
-                debug_mode: false,
-            },
-            system: SystemConfig {
-                min_free_ram_mb: 1024, // Keep at least 1GB free
-                warning_threshold_mb: 2048,
-                monitoring: true,
-                update_interval: Duration::from_secs(1),
-            },
-            game: GameConfig {
-                autosave_interval: 60, // Auto-save every minute
-                sound_enabled: true,
-                difficulty: Difficulty::Normal,
-                save_path: "saves/pet_save.json".to_string(),
-            },
+        GameConfig {
+            autosave_interval: 60, // Auto-save every minute
+            sound_enabled: true,
+            difficulty: Difficulty::Normal,
+            save_path: "saves/pet_save.ramsave".to_string(),
+            backups: 3,
+            offline_cap_mins: 0,
+            pet_sitter: true, // A pet left alone starves within minutes
+            cancelled_meal: CancelledMeal::Credit,
+        }
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = anyhow::Error;
+    
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "easy" => Ok(Difficulty::Easy),
+            "normal" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            "nightmare" => Ok(Difficulty::Nightmare),
+            other => Err(anyhow!(
+                "unknown difficulty '{}' (expected easy, normal, hard or nightmare)", other
+            )),
+        }
+    }
+}
+
+impl FromStr for CancelledMeal {
+    type Err = anyhow::Error;
+    
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "credit" => Ok(CancelledMeal::Credit),
+            "rollback" => Ok(CancelledMeal::Rollback),
+            other => Err(anyhow!(
+                "unknown cancelled meal policy '{}' (expected credit or rollback)", other
+            )),
         }
     }
 }
@@ -126,12 +250,296 @@ impl Config {
         Ok(())
     }
     
+    /// Resolve the startup configuration
+    ///
+    /// Layers are applied lowest to highest priority: built-in defaults,
+    /// the config file, `RAM_PET_*` environment variables, then CLI
+    /// overrides. Difficulty scaling is applied last and the result is
+    /// validated against the machine's physical RAM.
+    pub fn resolve(
+        config_path: Option<&Path>,
+        cli_overrides: &[(String, String)],
+        total_ram_mb: MiB,
+    ) -> anyhow::Result<Self> {
+        let mut config = match Self::find_config_file(config_path)? {
+            Some(path) => {
+                log::info!("Loading config from {}", path.display());
+                Self::from_file(&path.to_string_lossy())
+                    .with_context(|| format!("failed to load config file {}", path.display()))?
+            }
+            None => Config::default(),
+        };
+        
+        config.apply_env(std::env::vars())?;
+        
+        for (key, value) in cli_overrides {
+            config.set(key, value)
+                .with_context(|| format!("invalid command line option for '{}'", key))?;
+        }
+        
+        let difficulty = config.game.difficulty;
+        let config = config.with_difficulty(difficulty);
+        config.validate(total_ram_mb)?;
+        Ok(config)
+    }
+    
+    /// Locate the config file to load
+    ///
+    /// An explicit path (from `--config` or `RAM_PET_CONFIG`) must exist.
+    /// Otherwise the XDG config directory is searched, then the working
+    /// directory. Returns `None` if no file is found.
+    pub fn find_config_file(explicit: Option<&Path>) -> anyhow::Result<Option<PathBuf>> {
+        let explicit = explicit
+            .map(Path::to_path_buf)
+            .or_else(|| std::env::var_os(ENV_CONFIG_PATH).map(PathBuf::from));
+        
+        if let Some(path) = explicit {
+            if !path.is_file() {
+                bail!("config file {} does not exist", path.display());
+            }
+            return Ok(Some(path));
+        }
+        
+        Ok(Self::candidate_paths().into_iter().find(|path| path.is_file()))
+    }
+    
+    /// Config file locations searched when none is given explicitly
+    fn candidate_paths() -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+        
+        let xdg_home = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|p| p.is_absolute())
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")));
+        if let Some(dir) = xdg_home {
+            paths.push(dir.join("ram_pet").join("config.toml"));
+        }
+        
+        if let Some(dirs) = std::env::var_os("XDG_CONFIG_DIRS") {
+            for dir in std::env::split_paths(&dirs).filter(|p| p.is_absolute()) {
+                paths.push(dir.join("ram_pet").join("config.toml"));
+            }
+        }
+        
+        paths.push(PathBuf::from("config.toml"));
+        paths
+    }
+    
+    /// Apply `RAM_PET_<SECTION>_<KEY>` environment overrides
+    pub fn apply_env<I>(&mut self, vars: I) -> anyhow::Result<()>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        for (name, value) in vars {
+            let Some(rest) = name.strip_prefix(ENV_PREFIX) else { continue };
+            if name == ENV_CONFIG_PATH {
+                continue;
+            }
+            
+            let rest = rest.to_ascii_lowercase();
+            let Some((section, key)) = rest.split_once('_') else {
+                log::warn!("Ignoring unrecognized environment variable {}", name);
+                continue;
+            };
+            
+            let key = format!("{}.{}", section, key);
+            if !Self::KEYS.contains(&key.as_str()) {
+                log::warn!("Ignoring unrecognized environment variable {}", name);
+                continue;
+            }
+            
+            self.set(&key, &value)
+                .with_context(|| format!("invalid value in environment variable {}", name))?;
+        }
+        Ok(())
+    }
+    
+    /// All keys accepted by [`Config::set`]
+    pub const KEYS: &'static [&'static str] = &[
+        "pet.starting_size_mb",
+        "pet.max_size_mb",
+        "pet.metabolism_rate",
+        "pet.hunger_rate",
+        "pet.critical_hunger",
+        "pet.happiness_decay",
+        "pet.lifespan_days",
+        "graphics.use_colors",
+        "graphics.animations",
+        "graphics.fps",
+        "graphics.debug_mode",
+        "system.min_free_ram_mb",
+        "system.warning_threshold_mb",
+        "system.monitoring",
+        "system.update_interval",
+        "system.food_source",
+        "system.food_dir",
+        "system.residency",
+        "system.governor",
+        "system.scrub_rate_mb",
+        "system.balloon",
+        "system.balloon_fraction",
+        "system.leak_watch",
+        "system.leak_window_secs",
+        "system.leak_slope_mb_per_min",
+        "game.autosave_interval",
+        "game.sound_enabled",
+        "game.difficulty",
+        "game.save_path",
+        "game.backups",
+        "game.offline_cap_mins",
+        "game.pet_sitter",
+        "game.cancelled_meal",
+    ];
+    
+    /// Set a single value by its dotted key (e.g. `pet.max_size_mb`)
+    pub fn set(&mut self, key: &str, value: &str) -> anyhow::Result<()> {
+        fn parse<T: FromStr>(key: &str, value: &str) -> anyhow::Result<T>
+        where
+            T::Err: std::fmt::Display,
+        {
+            value.trim().parse::<T>()
+                .map_err(|e| anyhow!("'{}' is not a valid value for {}: {}", value, key, e))
+        }
+        
+        match key {
+            "pet.starting_size_mb" => self.pet.starting_size_mb = parse(key, value)?,
+            "pet.max_size_mb" => self.pet.max_size_mb = parse(key, value)?,
+            "pet.metabolism_rate" => self.pet.metabolism_rate = parse(key, value)?,
+            "pet.hunger_rate" => self.pet.hunger_rate = parse(key, value)?,
+            "pet.critical_hunger" => self.pet.critical_hunger = parse(key, value)?,
+            "pet.happiness_decay" => self.pet.happiness_decay = parse(key, value)?,
+            "pet.lifespan_days" => self.pet.lifespan_days = parse(key, value)?,
+            "graphics.use_colors" => self.graphics.use_colors = parse(key, value)?,
+            "graphics.animations" => self.graphics.animations = parse(key, value)?,
+            "graphics.fps" => self.graphics.fps = parse(key, value)?,
+            "graphics.debug_mode" => self.graphics.debug_mode = parse(key, value)?,
+            "system.min_free_ram_mb" => self.system.min_free_ram_mb = parse(key, value)?,
+            "system.warning_threshold_mb" => self.system.warning_threshold_mb = parse(key, value)?,
+            "system.monitoring" => self.system.monitoring = parse(key, value)?,
+            "system.update_interval" => {
+                // Given in (fractional) seconds
+                let secs: f64 = parse(key, value)?;
+                self.system.update_interval = Duration::try_from_secs_f64(secs)
+                    .map_err(|e| anyhow!("'{}' is not a valid value for {}: {}", value, key, e))?;
+            }
+            "system.food_source" => self.system.food_source = parse(key, value)?,
+            "system.food_dir" => self.system.food_dir = PathBuf::from(value),
+            "system.residency" => self.system.residency = parse(key, value)?,
+            "system.governor" => self.system.governor = parse(key, value)?,
+            "system.scrub_rate_mb" => self.system.scrub_rate_mb = parse(key, value)?,
+            "system.balloon" => self.system.balloon = parse(key, value)?,
+            "system.balloon_fraction" => self.system.balloon_fraction = parse(key, value)?,
+            "system.leak_watch" => self.system.leak_watch = parse(key, value)?,
+            "system.leak_window_secs" => self.system.leak_window_secs = parse(key, value)?,
+            "system.leak_slope_mb_per_min" => self.system.leak_slope_mb_per_min = parse(key, value)?,
+            "game.autosave_interval" => self.game.autosave_interval = parse(key, value)?,
+            "game.sound_enabled" => self.game.sound_enabled = parse(key, value)?,
+            "game.difficulty" => self.game.difficulty = parse(key, value)?,
+            "game.save_path" => self.game.save_path = value.to_string(),
+            "game.backups" => self.game.backups = parse(key, value)?,
+            "game.offline_cap_mins" => self.game.offline_cap_mins = parse(key, value)?,
+            "game.pet_sitter" => self.game.pet_sitter = parse(key, value)?,
+            "game.cancelled_meal" => self.game.cancelled_meal = parse(key, value)?,
+            _ => bail!("unknown config key '{}'", key),
+        }
+        Ok(())
+    }
+    
+    /// Check the configuration for values that make no sense together
+    ///
+    /// All problems are reported at once rather than one at a time.
+    pub fn validate(&self, total_ram_mb: MiB) -> anyhow::Result<()> {
+        let mut problems = Vec::new();
+        
+        if self.system.warning_threshold_mb < self.system.min_free_ram_mb {
+            problems.push(format!(
+                "system.warning_threshold_mb ({}) is below system.min_free_ram_mb ({})",
+                self.system.warning_threshold_mb, self.system.min_free_ram_mb
+            ));
+        }
+        if !total_ram_mb.is_zero() && self.pet.max_size_mb > total_ram_mb {
+            problems.push(format!(
+                "pet.max_size_mb ({}) is larger than this machine's physical RAM ({})",
+                self.pet.max_size_mb, total_ram_mb
+            ));
+        }
+        if !total_ram_mb.is_zero() && self.system.min_free_ram_mb >= total_ram_mb {
+            problems.push(format!(
+                "system.min_free_ram_mb ({}) leaves no RAM to eat ({} installed)",
+                self.system.min_free_ram_mb, total_ram_mb
+            ));
+        }
+        if self.pet.starting_size_mb > self.pet.max_size_mb {
+            problems.push(format!(
+                "pet.starting_size_mb ({}) is larger than pet.max_size_mb ({})",
+                self.pet.starting_size_mb, self.pet.max_size_mb
+            ));
+        }
+        for (key, rate) in [
+            ("pet.metabolism_rate", self.pet.metabolism_rate),
+            ("pet.hunger_rate", self.pet.hunger_rate),
+            ("pet.happiness_decay", self.pet.happiness_decay),
+        ] {
+            if !rate.is_finite() || rate < 0.0 {
+                problems.push(format!("{} must be a non-negative number (got {})", key, rate));
+            }
+        }
+        if !self.pet.lifespan_days.is_finite() || self.pet.lifespan_days <= 0.0 {
+            problems.push(format!(
+                "pet.lifespan_days must be a positive number of days (got {})",
+                self.pet.lifespan_days
+            ));
+        }
+        if !(self.pet.critical_hunger > 0.0 && self.pet.critical_hunger <= 100.0) {
+            problems.push(format!(
+                "pet.critical_hunger must be between 0 and 100 (got {})",
+                self.pet.critical_hunger
+            ));
+        }
+        if !(self.system.balloon_fraction > 0.0 && self.system.balloon_fraction <= 1.0) {
+            problems.push(format!(
+                "system.balloon_fraction must be above 0 and at most 1 (got {})",
+                self.system.balloon_fraction
+            ));
+        }
+        if self.system.leak_watch > 0 {
+            if self.system.leak_window_secs == 0 {
+                problems.push("system.leak_window_secs must be greater than zero".to_string());
+            }
+            if self.system.leak_slope_mb_per_min.is_nan() || self.system.leak_slope_mb_per_min <= 0.0 {
+                problems.push(format!(
+                    "system.leak_slope_mb_per_min must be above 0 (got {})",
+                    self.system.leak_slope_mb_per_min
+                ));
+            }
+        }
+        if self.graphics.fps == 0 {
+            problems.push("graphics.fps must be at least 1".to_string());
+        }
+        if self.system.update_interval.is_zero() {
+            problems.push("system.update_interval must be greater than zero".to_string());
+        }
+        if self.system.food_source == FoodKind::File && !self.system.food_dir.is_dir() {
+            problems.push(format!(
+                "system.food_dir ({}) must be an existing directory for file-backed food",
+                self.system.food_dir.display()
+            ));
+        }
+        
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!("invalid configuration:\n  - {}", problems.join("\n  - ")))
+        }
+    }
+    
     /// Get config with difficulty adjustments
     pub fn with_difficulty(mut self, difficulty: Difficulty) -> Self {
         match difficulty {
             Difficulty::Easy => {
                 self.pet.hunger_rate *= 0.5;
                 self.pet.metabolism_rate *= 0.5;
+                self.pet.happiness_decay *= 0.75;
             }
             Difficulty::Normal => {
                 // Default values
@@ -139,10 +547,12 @@ impl Config {
             Difficulty::Hard => {
                 self.pet.hunger_rate *= 1.5;
                 self.pet.metabolism_rate *= 1.5;
+                self.pet.happiness_decay *= 1.25;
             }
             Difficulty::Nightmare => {
                 self.pet.hunger_rate *= 2.0;
                 self.pet.metabolism_rate *= 2.0;
+                self.pet.happiness_decay *= 1.5;
                 self.pet.critical_hunger = 60.0;
             }
         }
@@ -153,14 +563,39 @@ impl Config {
 
 /// Feeding amounts in MB
 pub mod feeding {
-    pub const SNACK: usize = 10;
-    pub const MEAL: usize = 50;
-    pub const FEAST: usize = 100;
-    pub const GORGE: usize = 500;
+    use crate::system::food::FoodContent;
+    use crate::system::units::MiB;
+    
+    pub const SNACK: MiB = MiB(10);
+    pub const MEAL: MiB = MiB(50);
+    pub const FEAST: MiB = MiB(100);
+    pub const GORGE: MiB = MiB(500);
+    
+    /// Something on the menu: how much RAM it is and what it's filled with
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct FoodType {
+        pub name: &'static str,
+        pub size: MiB,
+        pub content: FoodContent,
+    }
+    
+    impl FoodType {
+        /// Text-like crumbs; compress about in half
+        pub const SNACK: FoodType = FoodType { name: "Snack", size: SNACK, content: FoodContent::Text };
+        /// A repeating pattern; compresses to almost nothing
+        pub const MEAL: FoodType = FoodType { name: "Meal", size: MEAL, content: FoodContent::Pattern };
+        /// Random data; doesn't compress at all
+        pub const FEAST: FoodType = FoodType { name: "Feast", size: FEAST, content: FoodContent::Random };
+        /// Empty calories: all zeros, which zram and KSM make vanish
+        pub const GORGE: FoodType = FoodType { name: "Gorge", size: GORGE, content: FoodContent::Zeros };
+        
+        /// Everything on the menu, as numbered on the keyboard
+        pub const MENU: [FoodType; 4] = [Self::SNACK, Self::MEAL, Self::FEAST, Self::GORGE];
+    }
     
     /// Get feeding amount name
-    pub fn get_feeding_name(amount: usize) -> &'static str {
-        match amount {
+    pub fn get_feeding_name(amount: MiB) -> &'static str {
+        match amount.0 {
             0..=15 => "Tiny Snack",
             16..=30 => "Snack",
             31..=75 => "Meal",
@@ -179,7 +614,7 @@ mod tests {
     #[test]
     fn test_default_config() {
         let config = Config::default();
-        assert_eq!(config.pet.starting_size_mb, 50);
+        assert_eq!(config.pet.starting_size_mb, MiB(50));
         assert_eq!(config.game.difficulty, Difficulty::Normal);
     }
     
@@ -188,4 +623,47 @@ mod tests {
         let config = Config::default().with_difficulty(Difficulty::Hard);
         assert!(config.pet.hunger_rate > Config::default().pet.hunger_rate);
     }
+    
+    #[test]
+    fn test_partial_config_file() {
+        let config: Config = toml::de::from_str("[pet]\nmax_size_mb = 512\n").unwrap();
+        assert_eq!(config.pet.max_size_mb, MiB(512));
+        assert_eq!(config.pet.starting_size_mb, MiB(50));
+        assert_eq!(config.game.difficulty, Difficulty::Normal);
+    }
+    
+    #[test]
+    fn test_shipped_config_parses() {
+        let config: Config = toml::de::from_str(include_str!("../config.toml")).unwrap();
+        assert!(config.validate(MiB(0)).is_ok());
+    }
+    
+    #[test]
+    fn test_env_overrides() {
+        let mut config = Config::default();
+        config.apply_env(vec![
+            ("RAM_PET_PET_MAX_SIZE_MB".to_string(), "1024".to_string()),
+            ("RAM_PET_GAME_DIFFICULTY".to_string(), "hard".to_string()),
+            ("UNRELATED".to_string(), "ignored".to_string()),
+        ]).unwrap();
+        assert_eq!(config.pet.max_size_mb, MiB(1024));
+        assert_eq!(config.game.difficulty, Difficulty::Hard);
+        
+        let bad = config.apply_env(vec![
+            ("RAM_PET_SYSTEM_MIN_FREE_RAM_MB".to_string(), "lots".to_string()),
+        ]);
+        assert!(bad.is_err());
+    }
+    
+    #[test]
+    fn test_validation() {
+        let mut config = Config::default();
+        assert!(config.validate(MiB(16384)).is_ok());
+        
+        config.system.warning_threshold_mb = MiB(512);
+        config.pet.max_size_mb = MiB(32768);
+        let err = config.validate(MiB(16384)).unwrap_err().to_string();
+        assert!(err.contains("warning_threshold_mb"));
+        assert!(err.contains("physical RAM"));
+    }
 }
\ No newline at end of file
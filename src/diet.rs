@@ -0,0 +1,686 @@
+// src/diet.rs
+// RAM Eating Pet Simulator - Diet Plans (Load Generator Mode)
+
+use anyhow::{anyhow, bail, Context, Result};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::config::Config;
+use crate::pet::Pet;
+use crate::system::food::{FoodContent, FoodKind};
+use crate::system::governor::{GovernorEvent, SafetyGovernor};
+use crate::system::memory::MemoryManager;
+use crate::system::monitor::SystemMonitor;
+use crate::system::scrubber::Scrubber;
+use crate::system::units::MiB;
+
+/// How often the pet says something while dieting
+const COMMENT_INTERVAL: Duration = Duration::from_secs(30);
+/// Longest the diet sleeps without checking whether it was told to stop
+const STOP_POLL: Duration = Duration::from_millis(100);
+
+/// Exit status when the plan was followed all the way
+pub const EXIT_FOLLOWED: i32 = 0;
+/// Exit status when the integrity scrubber found corrupted memory
+pub const EXIT_CORRUPTED: i32 = 3;
+/// Exit status when the reserve (or the allocator) kept the pet below target
+///
+/// 1 is left for errors and 2 for invalid plans.
+pub const EXIT_HELD_BACK: i32 = 4;
+/// Exit status when SIGINT or SIGTERM stopped the diet before its end
+pub const EXIT_STOPPED: i32 = 5;
+
+/// A diet plan as written in its TOML file
+///
+/// ```toml
+/// name = "morning ramp"
+/// tick = 0.5
+/// content = "random"
+///
+/// [[phase]]
+/// shape = "ramp"
+/// from_mb = 100
+/// to_mb = 2000
+/// secs = 60
+///
+/// [[phase]]
+/// shape = "hold"
+/// mb = 2000
+/// secs = 300
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct DietPlan {
+    /// Name shown in the log
+    #[serde(default)]
+    pub name: String,
+    /// Seconds between adjustments
+    #[serde(default = "default_tick")]
+    pub tick: f64,
+    /// What the pet's memory is filled with
+    #[serde(default)]
+    pub content: FoodContent,
+    /// Food source, if not the configured one
+    #[serde(default)]
+    pub food_source: Option<FoodKind>,
+    /// The phases, run one after another
+    #[serde(rename = "phase")]
+    pub phases: Vec<Phase>,
+}
+
+fn default_tick() -> f64 {
+    1.0
+}
+
+/// One stretch of a diet plan
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "shape", rename_all = "snake_case")]
+pub enum Phase {
+    /// Straight line from one size to another
+    Ramp { from_mb: MiB, to_mb: MiB, secs: f64 },
+    /// Staircase from one size to another in `steps` equal jumps
+    Step { from_mb: MiB, to_mb: MiB, steps: u32, secs: f64 },
+    /// Repeated ramps from low to high, dropping straight back
+    Sawtooth { low_mb: MiB, high_mb: MiB, period_secs: f64, secs: f64 },
+    /// Jump to a peak for a moment, then back down to the base
+    Spike { base_mb: MiB, peak_mb: MiB, spike_secs: f64, secs: f64 },
+    /// Stay at one size
+    Hold { mb: MiB, secs: f64 },
+    /// Wander up and down by up to `step_mb` each tick, within bounds
+    RandomWalk {
+        start_mb: MiB,
+        min_mb: MiB,
+        max_mb: MiB,
+        step_mb: MiB,
+        secs: f64,
+        #[serde(default)]
+        seed: Option<u64>,
+    },
+    /// `t,mb` points from a CSV file, relative to the plan
+    Csv { path: PathBuf },
+}
+
+impl Phase {
+    /// Name of the phase's shape, for the log
+    pub fn shape(&self) -> &'static str {
+        match self {
+            Phase::Ramp { .. } => "ramp",
+            Phase::Step { .. } => "step",
+            Phase::Sawtooth { .. } => "sawtooth",
+            Phase::Spike { .. } => "spike",
+            Phase::Hold { .. } => "hold",
+            Phase::RandomWalk { .. } => "random_walk",
+            Phase::Csv { .. } => "csv",
+        }
+    }
+}
+
+impl DietPlan {
+    /// Read a plan from a TOML file
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("could not read diet plan {}", path.display()))?;
+        let mut plan: DietPlan = toml::from_str(&text)
+            .with_context(|| format!("invalid diet plan {}", path.display()))?;
+
+        // CSV paths are relative to the plan, not wherever we were started
+        let base = path.parent().unwrap_or(Path::new("."));
+        for phase in &mut plan.phases {
+            if let Phase::Csv { path } = phase {
+                if path.is_relative() {
+                    *path = base.join(&*path);
+                }
+            }
+        }
+        Ok(plan)
+    }
+
+    /// Turn the plan into a schedule, reading any CSV files
+    pub fn compile(&self) -> Result<Diet> {
+        let mut problems = Vec::new();
+        if !positive(self.tick) {
+            problems.push(format!("tick must be positive, got {}", self.tick));
+        }
+        if self.phases.is_empty() {
+            problems.push("the plan has no [[phase]] entries".to_string());
+        }
+
+        let mut segments = Vec::new();
+        for (index, phase) in self.phases.iter().enumerate() {
+            match self.segment(phase) {
+                Ok(segment) => segments.push(segment),
+                Err(e) => problems.push(format!("phase {} ({}): {:#}", index + 1, phase.shape(), e)),
+            }
+        }
+
+        if !problems.is_empty() {
+            bail!("invalid diet plan:\n  - {}", problems.join("\n  - "));
+        }
+        Ok(Diet { segments })
+    }
+
+    fn segment(&self, phase: &Phase) -> Result<Segment> {
+        let (secs, curve) = match *phase {
+            Phase::Ramp { from_mb, to_mb, secs } => (secs, Curve::Linear { from: from_mb, to: to_mb }),
+            Phase::Step { from_mb, to_mb, steps, secs } => {
+                if steps == 0 {
+                    bail!("steps must be at least 1");
+                }
+                (secs, Curve::Stairs { from: from_mb, to: to_mb, steps })
+            }
+            Phase::Sawtooth { low_mb, high_mb, period_secs, secs } => {
+                if !positive(period_secs) {
+                    bail!("period_secs must be positive");
+                }
+                (secs, Curve::Sawtooth { low: low_mb, high: high_mb, period: period_secs })
+            }
+            Phase::Spike { base_mb, peak_mb, spike_secs, secs } => {
+                if !(0.0..=secs).contains(&spike_secs) {
+                    bail!("spike_secs must be between 0 and secs");
+                }
+                (secs, Curve::Spike { base: base_mb, peak: peak_mb, spike: spike_secs })
+            }
+            Phase::Hold { mb, secs } => (secs, Curve::Linear { from: mb, to: mb }),
+            Phase::RandomWalk { start_mb, min_mb, max_mb, step_mb, secs, seed } => {
+                if min_mb > max_mb {
+                    bail!("min_mb is above max_mb");
+                }
+                if !positive(secs) {
+                    bail!("secs must be positive"); // Before walking forever
+                }
+                let points = random_walk(start_mb, min_mb, max_mb, step_mb, secs, self.tick, seed);
+                (secs, Curve::Points(points))
+            }
+            Phase::Csv { ref path } => {
+                let points = read_points(path)?;
+                let secs = points.last().map_or(0.0, |(t, _)| *t);
+                (secs, Curve::Points(points))
+            }
+        };
+
+        if !positive(secs) {
+            bail!("secs must be positive");
+        }
+        Ok(Segment { shape: phase.shape(), secs, curve })
+    }
+}
+
+/// A compiled plan: target size at any moment
+#[derive(Debug)]
+pub struct Diet {
+    segments: Vec<Segment>,
+}
+
+/// Where a diet is at a given moment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DietTarget {
+    /// Index of the current phase
+    pub phase: usize,
+    /// Shape of the current phase
+    pub shape: &'static str,
+    /// Size the pet should be
+    pub size: MiB,
+}
+
+#[derive(Debug)]
+struct Segment {
+    shape: &'static str,
+    secs: f64,
+    curve: Curve,
+}
+
+#[derive(Debug)]
+enum Curve {
+    Linear { from: MiB, to: MiB },
+    Stairs { from: MiB, to: MiB, steps: u32 },
+    Sawtooth { low: MiB, high: MiB, period: f64 },
+    Spike { base: MiB, peak: MiB, spike: f64 },
+    /// Linear between `(t, size)` points, sorted by time
+    Points(Vec<(f64, MiB)>),
+}
+
+impl Curve {
+    /// Size at `t` seconds into the segment lasting `secs`
+    fn at(&self, t: f64, secs: f64) -> MiB {
+        match *self {
+            Curve::Linear { from, to } => lerp(from, to, t / secs),
+            Curve::Stairs { from, to, steps } => {
+                // steps + 1 levels of equal length, starting at `from`
+                let level = ((t / secs) * (steps + 1) as f64).floor();
+                lerp(from, to, level / steps as f64)
+            }
+            Curve::Sawtooth { low, high, period } => lerp(low, high, (t % period) / period),
+            Curve::Spike { base, peak, spike } => if t < spike { peak } else { base },
+            Curve::Points(ref points) => {
+                let after = points.partition_point(|(time, _)| *time <= t);
+                match (after.checked_sub(1).map(|i| points[i]), points.get(after)) {
+                    (Some((t0, a)), Some(&(t1, b))) => lerp(a, b, (t - t0) / (t1 - t0)),
+                    (Some((_, a)), None) => a,
+                    (None, Some(&(_, b))) => b,
+                    (None, None) => MiB(0),
+                }
+            }
+        }
+    }
+}
+
+impl Diet {
+    /// Total length of the plan
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs_f64(self.segments.iter().map(|s| s.secs).sum())
+    }
+
+    /// Number of phases
+    pub fn phases(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Target at `elapsed` into the plan, or `None` once it's over
+    pub fn target_at(&self, elapsed: Duration) -> Option<DietTarget> {
+        let mut t = elapsed.as_secs_f64();
+        for (phase, segment) in self.segments.iter().enumerate() {
+            if t < segment.secs {
+                return Some(DietTarget {
+                    phase,
+                    shape: segment.shape,
+                    size: segment.curve.at(t, segment.secs),
+                });
+            }
+            t -= segment.secs;
+        }
+        None
+    }
+}
+
+/// Whether a time is a usable, positive number of seconds
+fn positive(secs: f64) -> bool {
+    secs.is_finite() && secs > 0.0
+}
+
+/// Interpolate between two sizes, `fraction` of the way from `a` to `b`
+fn lerp(a: MiB, b: MiB, fraction: f64) -> MiB {
+    let fraction = fraction.clamp(0.0, 1.0);
+    MiB((a.0 as f64 + (b.0 as f64 - a.0 as f64) * fraction).round() as usize)
+}
+
+/// Points of a random walk, one per tick
+fn random_walk(start: MiB, min: MiB, max: MiB, step: MiB, secs: f64, tick: f64, seed: Option<u64>) -> Vec<(f64, MiB)> {
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    let mut size = start.0.clamp(min.0, max.0) as i64;
+    let mut points = vec![(0.0, MiB(size as usize))];
+    let mut t = 0.0;
+    while t < secs && tick > 0.0 {
+        t += tick;
+        size = (size + rng.gen_range(-(step.0 as i64)..=step.0 as i64)).clamp(min.0 as i64, max.0 as i64);
+        points.push((t, MiB(size as usize)));
+    }
+    points
+}
+
+/// Read `t,mb` points from a CSV file
+///
+/// Blank lines, `#` comments and a header line are skipped. Times must not
+/// go backwards.
+fn read_points(path: &Path) -> Result<Vec<(f64, MiB)>> {
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("could not read {}", path.display()))?;
+
+    let mut points: Vec<(f64, MiB)> = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let parsed = line.split_once(',').and_then(|(t, mb)| {
+            Some((t.trim().parse::<f64>().ok()?, mb.trim().parse::<MiB>().ok()?))
+        });
+        let (t, mb) = match parsed {
+            Some(point) => point,
+            None if points.is_empty() && number == 0 => continue, // Header
+            None => bail!("{}:{}: expected 't,mb', got '{}'", path.display(), number + 1, line),
+        };
+        if points.last().is_some_and(|(last, _)| t < *last) || t < 0.0 {
+            bail!("{}:{}: time {} goes backwards", path.display(), number + 1, t);
+        }
+        points.push((t, mb));
+    }
+
+    if points.is_empty() {
+        return Err(anyhow!("{} has no points", path.display()));
+    }
+    Ok(points)
+}
+
+/// How closely a diet was followed
+#[derive(Debug, Clone, Default)]
+pub struct DietReport {
+    /// Adjustments made
+    pub ticks: u64,
+    /// Adjustments that left the pet below its target
+    pub short_ticks: u64,
+    /// Biggest gap between target and achieved size
+    pub worst_shortfall: MiB,
+    /// Biggest size reached
+    pub peak: MiB,
+    /// Times the safety governor made the pet throw up
+    pub vomits: u64,
+    /// Corrupted blocks the integrity scrubber found
+    pub mismatches: usize,
+    /// Whether a signal stopped the diet before the plan was over
+    pub stopped: bool,
+}
+
+impl DietReport {
+    /// Process exit status for the run
+    pub fn exit_code(&self) -> i32 {
+        if self.mismatches > 0 {
+            EXIT_CORRUPTED
+        } else if self.stopped {
+            EXIT_STOPPED
+        } else if self.short_ticks > 0 {
+            EXIT_HELD_BACK
+        } else {
+            EXIT_FOLLOWED
+        }
+    }
+}
+
+/// Make the pet follow a diet, blocking until the plan is over
+///
+/// Target versus achieved size is logged every tick; the pet's commentary
+/// goes to stderr. The configured RAM reserve is never eaten into, and the
+/// safety governor and scrubber run as they do in the game. SIGINT or
+/// SIGTERM ends the diet early, still freeing its memory and reporting.
+pub fn run(plan: &DietPlan, diet: &Diet, config: &Config) -> Result<DietReport> {
+    let stop = stop_on_signals();
+    let monitor = SystemMonitor::with_interval(config.system.update_interval);
+    let mut manager = MemoryManager::with_snapshots(config.system.min_free_ram_mb, monitor.snapshots());
+    manager.set_food(plan.food_source.unwrap_or(config.system.food_source));
+    manager.set_food_dir(&config.system.food_dir);
+    manager.set_content(plan.content);
+    manager.set_integrity(!config.system.scrub_rate_mb.is_zero());
+    manager.set_residency(config.system.residency);
+
+    let governor = config.system.governor.then(|| SafetyGovernor::spawn(
+        manager.shared_blocks(),
+        manager.shared_releases(),
+        monitor.snapshots(),
+        config.system.min_free_ram_mb,
+        config.system.update_interval,
+    ));
+    let scrubber = (!config.system.scrub_rate_mb.is_zero())
+        .then(|| Scrubber::spawn(manager.shared_blocks(), config.system.scrub_rate_mb));
+
+    let mut pet = Pet::new(config)?;
+    pet.shrink_to(MiB(0));
+    let name = if plan.name.is_empty() { "unnamed" } else { plan.name.as_str() };
+    eprintln!("🐾 {} starts the {} diet ({} phases, {:.0?})", pet.name, name, diet.phases(), diet.duration());
+    log::info!(
+        "Diet '{}': {} of {} food, reserve {}",
+        name, plan.content, manager.food(), config.system.min_free_ram_mb
+    );
+
+    let tick = Duration::from_secs_f64(plan.tick);
+    let start = Instant::now();
+    let mut report = DietReport::default();
+    let mut phase = None;
+    let mut held_back = false;
+    let mut last_comment = start;
+
+    while let Some(target) = diet.target_at(start.elapsed()) {
+        if stop.load(Ordering::Relaxed) {
+            report.stopped = true;
+            break;
+        }
+        if phase != Some(target.phase) {
+            phase = Some(target.phase);
+            held_back = false;
+            eprintln!("{}: {}", pet.name, pet.get_reaction());
+        }
+
+        let shortfall = follow(&mut manager, target.size, config.system.min_free_ram_mb);
+        let achieved = manager.get_allocated_mb();
+
+        // Keep the pet the size of what it really holds
+        if achieved > pet.get_size_mb() {
+            pet.eat(achieved - pet.get_size_mb())?;
+        } else {
+            pet.shrink_to(achieved);
+        }
+
+        report.ticks += 1;
+        report.peak = report.peak.max(achieved);
+        if !shortfall.is_zero() {
+            report.short_ticks += 1;
+            report.worst_shortfall = report.worst_shortfall.max(shortfall);
+            if !held_back {
+                held_back = true;
+                eprintln!("{}: I could eat {} more, but the reserve says no", pet.name, shortfall);
+            }
+        }
+        log::info!(
+            "t={:>8.1}s  phase {}/{} {:<11}  target {:>9}  achieved {:>9}",
+            start.elapsed().as_secs_f64(), target.phase + 1, diet.phases(), target.shape,
+            target.size.to_string(), achieved.to_string(),
+        );
+
+        if let Some(governor) = &governor {
+            for event in governor.events() {
+                let GovernorEvent::Vomited { released, available } = event;
+                report.vomits += 1;
+                pet.vomit(manager.get_allocated_mb());
+                eprintln!("{}: 🤮 threw up {} - only {} left free!", pet.name, released, available);
+            }
+        }
+        if let Some(scrubber) = &scrubber {
+            let found = scrubber.events().count();
+            if found > 0 {
+                report.mismatches += found;
+                eprintln!("{}: ☠ that memory tasted wrong ({} corrupted block(s))", pet.name, found);
+            }
+        }
+        if last_comment.elapsed() >= COMMENT_INTERVAL {
+            last_comment = Instant::now();
+            let comment = pet.get_personality().get_random_comment();
+            if comment != "..." {
+                eprintln!("{}: {}", pet.name, comment);
+            }
+        }
+
+        // Stay on the tick grid so slow allocations don't stretch the plan
+        let next = start + tick.mul_f64((start.elapsed().as_secs_f64() / plan.tick).floor() + 1.0);
+        while !stop.load(Ordering::Relaxed) && Instant::now() < next {
+            thread::sleep(next.saturating_duration_since(Instant::now()).min(STOP_POLL));
+        }
+    }
+    if stop.load(Ordering::Relaxed) {
+        report.stopped = true;
+    }
+
+    manager.clear();
+    if let Some(scrubber) = &scrubber {
+        report.mismatches = scrubber.report().mismatches.len();
+    }
+    if report.stopped {
+        eprintln!("{}: Stopped early? Fine, I'll give it all back. {}", pet.name, pet.get_reaction());
+    } else {
+        eprintln!("{}: Diet over. {}", pet.name, pet.get_reaction());
+    }
+    log::info!(
+        "Diet '{}' {}: {} ticks, {} short (worst {}), peak {}, {} vomits, {} corrupted blocks",
+        name, if report.stopped { "stopped" } else { "finished" }, report.ticks, report.short_ticks,
+        report.worst_shortfall, report.peak, report.vomits, report.mismatches,
+    );
+    Ok(report)
+}
+
+/// A flag raised by SIGINT or SIGTERM so the diet can clean up and report
+///
+/// A second signal while the first is being handled exits straight away.
+#[cfg(unix)]
+fn stop_on_signals() -> Arc<AtomicBool> {
+    use signal_hook::consts::{SIGINT, SIGTERM};
+    use signal_hook::flag;
+
+    let stop = Arc::new(AtomicBool::new(false));
+    for signal in [SIGINT, SIGTERM] {
+        // The shutdown check runs first, so it only fires once the flag is up
+        let registered = flag::register_conditional_shutdown(signal, EXIT_STOPPED, Arc::clone(&stop))
+            .and_then(|_| flag::register(signal, Arc::clone(&stop)));
+        if let Err(e) = registered {
+            log::warn!("Couldn't catch signal {}; the diet can't stop cleanly: {}", signal, e);
+        }
+    }
+    stop
+}
+
+#[cfg(not(unix))]
+fn stop_on_signals() -> Arc<AtomicBool> {
+    Arc::new(AtomicBool::new(false))
+}
+
+/// Grow or shrink towards `target` without eating into the reserve,
+/// returning how far short of it the pet is left
+fn follow(manager: &mut MemoryManager, target: MiB, reserve: MiB) -> MiB {
+    let held = manager.get_allocated_mb();
+    if held > target {
+        let _ = manager.release(held - target);
+    } else if held < target {
+        let room = manager.estimated_free_mb().saturating_sub(reserve);
+        let want = (target - held).min(room);
+        if !want.is_zero() {
+            if let Err(e) = manager.allocate(want) {
+                log::debug!("Diet allocation failed: {:#}", e);
+            }
+        }
+    }
+    target.saturating_sub(manager.get_allocated_mb())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diet(toml: &str) -> Diet {
+        toml::from_str::<DietPlan>(toml).unwrap().compile().unwrap()
+    }
+
+    fn at(diet: &Diet, secs: f64) -> Option<MiB> {
+        diet.target_at(Duration::from_secs_f64(secs)).map(|target| target.size)
+    }
+
+    #[test]
+    fn test_shapes() {
+        let diet = diet(r#"
+            [[phase]]
+            shape = "ramp"
+            from_mb = 0
+            to_mb = 100
+            secs = 10
+
+            [[phase]]
+            shape = "step"
+            from_mb = 100
+            to_mb = 400
+            steps = 3
+            secs = 30
+
+            [[phase]]
+            shape = "sawtooth"
+            low_mb = 0
+            high_mb = 50
+            period_secs = 10
+            secs = 20
+
+            [[phase]]
+            shape = "spike"
+            base_mb = 10
+            peak_mb = 900
+            spike_secs = 1
+            secs = 5
+        "#);
+
+        assert_eq!(diet.phases(), 4);
+        assert_eq!(diet.duration(), Duration::from_secs(65));
+        assert_eq!(at(&diet, 5.0), Some(MiB(50)));
+        assert_eq!(at(&diet, 15.0), Some(MiB(100)));
+        assert_eq!(at(&diet, 20.0), Some(MiB(200)));
+        assert_eq!(at(&diet, 39.0), Some(MiB(400)));
+        assert_eq!(at(&diet, 45.0), Some(MiB(25)));
+        assert_eq!(at(&diet, 55.0), Some(MiB(25)));
+        assert_eq!(at(&diet, 60.5), Some(MiB(900)));
+        assert_eq!(at(&diet, 62.0), Some(MiB(10)));
+        assert_eq!(at(&diet, 65.0), None);
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        let mut report = DietReport::default();
+        assert_eq!(report.exit_code(), EXIT_FOLLOWED);
+        report.short_ticks = 1;
+        assert_eq!(report.exit_code(), EXIT_HELD_BACK);
+        report.stopped = true;
+        assert_eq!(report.exit_code(), EXIT_STOPPED);
+        report.mismatches = 1;
+        assert_eq!(report.exit_code(), EXIT_CORRUPTED);
+    }
+
+    #[test]
+    fn test_random_walk_and_csv() {
+        let walk = diet(r#"
+            tick = 1
+            [[phase]]
+            shape = "random_walk"
+            start_mb = 100
+            min_mb = 90
+            max_mb = 110
+            step_mb = 5
+            secs = 50
+            seed = 7
+        "#);
+        for t in 0..50 {
+            let size = at(&walk, t as f64).unwrap();
+            assert!(MiB(90) <= size && size <= MiB(110));
+        }
+
+        let dir = std::env::temp_dir().join(format!("ram_pet_diet_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("trace.csv"), "t,mb\n0,100\n10,300\n# done\n20,300\n").unwrap();
+        fs::write(dir.join("plan.toml"), "[[phase]]\nshape = \"csv\"\npath = \"trace.csv\"\n").unwrap();
+        let csv = DietPlan::load(&dir.join("plan.toml")).unwrap().compile().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(at(&csv, 5.0), Some(MiB(200)));
+        assert_eq!(at(&csv, 15.0), Some(MiB(300)));
+        assert_eq!(csv.duration(), Duration::from_secs(20));
+    }
+
+    #[test]
+    fn test_bad_plans_are_rejected() {
+        let plan: DietPlan = toml::from_str(r#"
+            tick = 0
+            [[phase]]
+            shape = "step"
+            from_mb = 0
+            to_mb = 10
+            steps = 0
+            secs = 10
+        "#).unwrap();
+        let error = plan.compile().unwrap_err().to_string();
+        assert!(error.contains("tick"));
+        assert!(error.contains("steps"));
+
+        assert!(toml::from_str::<DietPlan>("[[phase]]\nshape = \"zigzag\"\nsecs = 1\n").is_err());
+    }
+}
@@ -1,21 +1,36 @@
 // src/game.rs
 // RAM Eating Pet Simulator - Main Game Logic
 
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Utc};
 use colored::*;
-use crossterm::{
-    cursor,
-    execute,
-    terminal::{self, ClearType},
-};
+use crossterm::event::KeyCode;
 use std::io::{stdout, Write};
 use std::time::{Duration, Instant};
 
-use crate::config::{Config, feeding};
-use crate::graphics::renderer::Renderer;
-use crate::pet::Pet;
-use crate::system::memory::MemoryManager;
+use crate::adopt::{AdoptTarget, Adoption, Sighting};
+use crate::config::feeding::{self, FoodType};
+use crate::config::{CancelledMeal, Config};
+use crate::graphics::renderer::{BalloonStatus, MealProgress, Panel, Renderer, SessionInfo};
+use crate::pet::age::ordinal;
+use crate::pet::state::PetState;
+use crate::pet::{AwayReport, Mood, OfflineRules, Pet, Symptoms};
+use crate::save::{BrowserAction, SaveBrowser, SaveData, SaveFile, SaveSlots, SaveStore, SlotInfo};
+use crate::system::balloon::Balloon;
+use crate::system::food::FoodContent;
+use crate::system::governor::{GovernorEvent, SafetyGovernor};
+use crate::system::leaks::LeakDetector;
+use crate::system::memory::{AllocationJob, MemoryManager, Residency, ResidencyPolicy};
 use crate::system::monitor::SystemMonitor;
+use crate::system::rivals::{Competition, RankChange};
+use crate::system::sampler::{BudgetScope, SystemSnapshot};
+use crate::system::scrubber::{ScrubReport, Scrubber};
+use crate::system::units::MiB;
+
+/// Shortest gap between the pet's remarks about its rivals
+const TAUNT_COOLDOWN: Duration = Duration::from_secs(15);
+/// Time away after which loading a save shows what happened meanwhile
+const AWAY_SCREEN_AFTER: Duration = Duration::from_secs(60);
 
 /// Main game state
 pub struct Game {
@@ -39,13 +54,62 @@ pub struct Game {
     messages: Vec<(String, Instant, ColoredString)>,
     /// Game score/stats
     stats: GameStats,
+    /// Keeps allocated RAM in line with the pet's size
+    reconciler: MemoryReconciler,
+    /// Last residency check of the pet's memory, and when it was taken
+    residency: (Residency, Instant),
+    /// Meal currently being allocated
+    meal: Option<Meal>,
+    /// Watchdog that frees RAM if the host runs low
+    governor: Option<SafetyGovernor>,
+    /// Background integrity checker, unless turned off
+    scrubber: Option<Scrubber>,
+    /// Corrupted bytes the scrubber has found this session
+    mismatches: usize,
+    /// Sizes the pet to spare RAM while balloon mode is on
+    balloon: Option<Balloon>,
+    /// Balloon growth still being allocated
+    growth: Option<AllocationJob>,
+    /// Process the pet mirrors instead of eating, when adopted
+    adoption: Option<Adoption>,
+    /// Watches the biggest processes for leaks, unless turned off
+    leaks: Option<LeakDetector>,
+    /// The pet's ranking against the biggest processes
+    competition: Competition,
+    /// Last time the pet talked about its rivals
+    last_taunt: Option<Instant>,
+    /// The saves directory
+    slots: SaveSlots,
+    /// Save slot this game saves to
+    slot: String,
+    /// Where the pet is saved, with its backups
+    store: SaveStore,
+    /// The save/load screen, while it's open
+    browser: Option<SaveBrowser>,
+    /// When this game was started, kept across saves
+    created_at: DateTime<Utc>,
+    /// What happened while the game was closed, until the player has seen it
+    away: Option<AwayReport>,
+    /// Birthdays the pet has celebrated
+    birthdays: u32,
+    /// Decides when the pet is saved without being asked
+    autosave: Autosave,
+}
+
+/// A feeding whose RAM is still being allocated
+struct Meal {
+    job: AllocationJob,
+    /// Amount the player asked to feed
+    amount_mb: MiB,
+    /// Whether this is the pet's favorite food
+    favorite: bool,
 }
 
 /// Game statistics
 struct GameStats {
-    total_mb_eaten: usize,
+    total_mb_eaten: MiB,
     feeding_count: usize,
-    max_size_reached: usize,
+    max_size_reached: MiB,
     play_time: Duration,
     session_start: Instant,
 }
@@ -53,25 +117,158 @@ struct GameStats {
 impl Default for GameStats {
     fn default() -> Self {
         Self {
-            total_mb_eaten: 0,
+            total_mb_eaten: MiB(0),
             feeding_count: 0,
-            max_size_reached: 0,
+            max_size_reached: MiB(0),
             play_time: Duration::from_secs(0),
             session_start: Instant::now(),
         }
     }
 }
 
+/// Tracks how far the memory manager has drifted from the pet's size
+///
+/// The pet's size is the source of truth: the memory manager is made to
+/// follow it, except when the system refuses to hand over more RAM, in
+/// which case the pet shrinks to what is actually held.
+#[derive(Debug, Default)]
+struct MemoryReconciler {
+    /// Pet size minus RAM held after the last pass (positive = pet is ahead)
+    gap_mb: i64,
+    /// Last time an allocation shortfall was reported to the player
+    last_shortfall: Option<Instant>,
+}
+
+impl MemoryReconciler {
+    /// Whether enough time has passed to report another shortfall
+    fn should_report(&mut self, now: Instant) -> bool {
+        let due = self.last_shortfall
+            .is_none_or(|last| now.duration_since(last) >= Duration::from_secs(10));
+        if due {
+            self.last_shortfall = Some(now);
+        }
+        due
+    }
+}
+
+/// Tracks what the pet looked like at the last save, to know when to save again
+#[derive(Debug)]
+struct Autosave {
+    last: Instant,
+    stage: PetState,
+    starving: bool,
+}
+
+impl Autosave {
+    fn new(pet: &Pet) -> Self {
+        Autosave {
+            last: Instant::now(),
+            stage: *pet.get_state(),
+            starving: false,
+        }
+    }
+    
+    /// Why the pet should be saved now, if it should
+    ///
+    /// Saves on the interval (unless it's zero), on reaching a new life
+    /// stage and on starting to starve, so a crash never costs much.
+    fn due(&mut self, pet: &Pet, interval: Duration, now: Instant) -> Option<&'static str> {
+        let starving = *pet.get_mood() == Mood::Starving;
+        let reason = if *pet.get_state() != self.stage {
+            Some("new life stage")
+        } else if starving && !self.starving {
+            Some("close to starving")
+        } else if !interval.is_zero() && now.duration_since(self.last) >= interval {
+            Some("interval")
+        } else {
+            None
+        };
+        self.stage = *pet.get_state();
+        self.starving = starving;
+        if reason.is_some() {
+            self.last = now;
+        }
+        reason
+    }
+}
+
 impl Game {
-    /// Create a new game instance
+    /// Create a new game instance with the default configuration
     pub fn new() -> Result<Self> {
-        let config = Config::default();
+        Self::with_config(Config::default())
+    }
+    
+    /// Create a new game instance with a resolved configuration
+    pub fn with_config(config: Config) -> Result<Self> {
+        Self::build(config, None)
+    }
+    
+    /// Create a game whose pet mirrors another process instead of eating
+    ///
+    /// The pet allocates nothing: its size is the process's RSS, it gets
+    /// hungry while the process grows, and it dies when the process exits.
+    pub fn adopt(config: Config, target: &AdoptTarget) -> Result<Self> {
+        let snapshot = SystemSnapshot::capture_with_processes();
+        let table = snapshot.processes.as_deref().map_or(&[][..], Vec::as_slice);
+        let process = target.find(table)
+            .ok_or_else(|| anyhow!("no running process matches {}", target))?;
+        log::info!("Adopting {} (pid {}, {})", process.name, process.pid, process.memory_mb);
+        
+        let adoption = Adoption::new(process);
+        let mut game = Self::build(config, Some(adoption))?;
+        game.pet.mirror(process.memory_mb, 0.0);
+        Ok(game)
+    }
+    
+    fn build(config: Config, adoption: Option<Adoption>) -> Result<Self> {
+        // An adopted pet holds no memory, so there's nothing to guard or check
+        let own_memory = adoption.is_none();
         let pet = Pet::new(&config)?;
-        let memory_manager = MemoryManager::new(config.system.min_free_ram_mb);
-        let system_monitor = SystemMonitor::new();
+        let system_monitor = SystemMonitor::with_interval(config.system.update_interval);
+        let mut memory_manager = MemoryManager::with_snapshots(
+            config.system.min_free_ram_mb,
+            system_monitor.snapshots(),
+        );
+        memory_manager.set_food(config.system.food_source);
+        memory_manager.set_food_dir(&config.system.food_dir);
+        let integrity = own_memory && !config.system.scrub_rate_mb.is_zero();
+        memory_manager.set_integrity(integrity);
+        memory_manager.set_residency(config.system.residency);
+        // A checked body gets a pattern, as all zeros would hide stuck bits;
+        // a lazy one is left unwritten
+        if integrity && config.system.residency != ResidencyPolicy::Lazy {
+            memory_manager.set_content(FoodContent::Pattern);
+        }
         let renderer = Renderer::new(config.graphics.use_colors);
+        let governor = (own_memory && config.system.governor).then(|| SafetyGovernor::spawn(
+            memory_manager.shared_blocks(),
+            memory_manager.shared_releases(),
+            system_monitor.snapshots(),
+            config.system.min_free_ram_mb,
+            config.system.update_interval,
+        ));
+        let scrubber = (own_memory && !config.system.scrub_rate_mb.is_zero()).then(|| Scrubber::spawn(
+            memory_manager.shared_blocks(),
+            config.system.scrub_rate_mb,
+        ));
+        
+        let balloon = (own_memory && config.system.balloon).then(|| Balloon::new(
+            config.system.balloon_fraction,
+            config.system.min_free_ram_mb,
+        ));
+        let leaks = (config.system.leak_watch > 0).then(|| LeakDetector::new(
+            config.system.leak_watch,
+            Duration::from_secs(config.system.leak_window_secs),
+            config.system.leak_slope_mb_per_min,
+            config.system.update_interval,
+        ));
         
-        Ok(Game {
+        let competition = Competition::new(adoption.as_ref().map(Adoption::pid));
+        let (slots, slot) = SaveSlots::around(config.game.save_path.as_ref(), config.game.backups);
+        let store = slots.store(&slot);
+        let autosave = Autosave::new(&pet);
+        
+        let mut game = Game {
             pet,
             memory_manager,
             system_monitor,
@@ -85,7 +282,32 @@ impl Game {
                 session_start: Instant::now(),
                 ..Default::default()
             },
-        })
+            reconciler: MemoryReconciler::default(),
+            residency: (Residency::default(), Instant::now()),
+            meal: None,
+            governor,
+            scrubber,
+            mismatches: 0,
+            balloon,
+            growth: None,
+            adoption,
+            leaks,
+            competition,
+            last_taunt: None,
+            slots,
+            slot,
+            store,
+            browser: None,
+            created_at: Utc::now(),
+            away: None,
+            birthdays: 0,
+            autosave,
+        };
+        
+        // Allocate the newborn pet's starting body
+        game.reconcile_memory()?;
+        
+        Ok(game)
     }
     
     /// Update game state
@@ -97,14 +319,54 @@ impl Game {
         // Update play time
         self.stats.play_time = now.duration_since(self.stats.session_start);
         
-        // Update pet metabolism
-        self.pet.metabolize(delta)?;
+        if self.adoption.is_some() {
+            // Adopted pets live off their process instead of eating
+            self.follow_adoption();
+        } else {
+            // Let the pet eat any meal that's finished allocating
+            self.poll_meal()?;
+            
+            // Update pet metabolism
+            self.pet.metabolize(delta)?;
+        }
         
         // Update pet mood based on hunger
         self.pet.update_mood(delta);
         
+        // Catch up with anything the safety governor freed
+        self.handle_governor_events();
+        self.handle_scrub_events();
+        
+        // Let the balloon grow or squeeze the pet to fit spare RAM
+        self.inflate_balloon()?;
+        
+        // Tell on anyone who looks like they're leaking
+        self.watch_for_leaks();
+        
+        // See how the pet measures up against the other processes
+        self.compete();
+        
+        // Give back (or take) RAM so allocations match the pet's size
+        self.reconcile_memory()?;
+        
+        // Keep the pet's memory in real RAM and see how much of it is
+        self.memory_manager.maintain_residency();
+        if now.duration_since(self.residency.1) >= self.config.system.update_interval {
+            self.residency = (self.memory_manager.residency(), now);
+        }
+        
         // Update system monitor
         self.system_monitor.update()?;
+        self.sample_processes(now);
+        let vitals = self.system_monitor.get_vitals().unwrap_or_default();
+        self.pet.set_symptoms(Symptoms {
+            pressure: self.system_monitor.get_pressure().map_or(0.0, |psi| psi.some_avg10),
+            // Per-block residency when we have it, else the process as a whole
+            cold: self.residency.0.cold_fraction().unwrap_or_else(|| vitals.swapped_fraction()),
+            major_fault_rate: vitals.major_fault_rate,
+            poisoned: self.mismatches > 0,
+            squeeze: self.balloon.as_ref().map_or(0.0, Balloon::squeeze),
+        });
         
         // Check for critical RAM levels
         self.check_ram_levels()?;
@@ -119,37 +381,196 @@ impl Game {
             self.stats.max_size_reached = self.pet.get_size_mb();
         }
         
+        self.celebrate_birthday();
+        self.autosave(now);
+        
+        Ok(())
+    }
+    
+    /// Throw a party on each anniversary of the pet's birth
+    fn celebrate_birthday(&mut self) {
+        let years = self.pet.years_old_at(Utc::now());
+        if self.pet.is_dead() || years <= self.birthdays {
+            return;
+        }
+        self.birthdays = years;
+        self.pet.boost_happiness();
+        log::info!("{} turned {}", self.pet.name, years);
+        self.add_message(
+            format!("Happy {} birthday, {}!", ordinal(years), self.pet.name),
+            "🎂".to_string().bright_magenta(),
+        );
+    }
+    
+    /// Save without being asked when the interval is up or something big happened
+    fn autosave(&mut self, now: Instant) {
+        // A dead pet would overwrite the last good save; an adopted one is never saved
+        if self.pet.is_dead() || self.adoption.is_some() {
+            return;
+        }
+        let interval = Duration::from_secs(self.config.game.autosave_interval);
+        let reason = match self.autosave.due(&self.pet, interval, now) {
+            Some(reason) => reason,
+            None => return,
+        };
+        match self.store.save(&self.save_file()) {
+            Ok(()) => log::info!("Autosaved to {} ({})", self.store.path().display(), reason),
+            Err(e) => {
+                log::warn!("Autosave failed: {:#}", e);
+                self.add_message(
+                    "Autosave failed!".to_string(),
+                    "💾".to_string().bright_red(),
+                );
+            }
+        }
+    }
+    
+    fn save_file(&self) -> SaveFile {
+        let data = SaveData {
+            pet: self.pet.clone(),
+            total_mb_eaten: self.stats.total_mb_eaten,
+            feeding_count: self.stats.feeding_count,
+            max_size_reached: self.stats.max_size_reached,
+        };
+        SaveFile::new(data, self.created_at)
+    }
+    
+    /// Feed the pet something from the menu
+    ///
+    /// The RAM is allocated by a background job; the pet eats it once the
+    /// job finishes (see [`Game::update`]).
+    pub async fn feed_pet(&mut self, food: FoodType) -> Result<()> {
+        self.start_meal(food.size, food.content, false);
+        Ok(())
+    }
+    
+    /// Feed pet its favorite food
+    pub async fn feed_pet_favorite(&mut self) -> Result<()> {
+        let favorite_amount = self.pet.get_favorite_food_size();
+        let favorite_content = self.pet.get_favorite_food_content();
+        
+        if self.start_meal(favorite_amount, favorite_content, true) {
+            // Special message for favorite food
+            self.add_message(
+                format!("Favorite food! ({})", favorite_amount),
+                "✨ PURE JOY ✨".bright_green(),
+            );
+        }
+        
         Ok(())
     }
     
-    /// Feed the pet with specified amount of RAM
-    pub async fn feed_pet(&mut self, amount_mb: usize) -> Result<()> {
+    /// Stop the meal (or balloon growth) in progress
+    ///
+    /// What was already allocated for a meal is credited to the pet or
+    /// rolled back, depending on `game.cancelled_meal`; growth keeps what
+    /// it got.
+    pub fn cancel_meal(&mut self) {
+        if let Some(meal) = &self.meal {
+            meal.job.cancel();
+        }
+        if let Some(growth) = &self.growth {
+            growth.cancel();
+        }
+    }
+    
+    /// Start allocating a meal, returning whether it got going
+    fn start_meal(&mut self, amount_mb: MiB, content: FoodContent, favorite: bool) -> bool {
+        if self.refuse_if_adopted("feeds itself") {
+            return false;
+        }
+        
+        if let Some(meal) = &self.meal {
+            let eaten = meal.job.progress();
+            let size = meal.job.target();
+            self.add_message(
+                format!("Still eating ({} of {})! [C] to stop", eaten, size),
+                "⏳".to_string().yellow(),
+            );
+            return false;
+        }
+        
         // Check if we have enough free RAM
-        let free_ram = self.system_monitor.get_free_ram_mb();
+        let free_ram = self.memory_manager.estimated_free_mb();
         if free_ram < amount_mb + self.config.system.min_free_ram_mb {
             self.add_message(
                 "Not enough free RAM! Close some programs first!".to_string(),
                 "❌".to_string().red(),
             );
-            return Ok(());
+            return false;
         }
         
-        // Actually allocate the memory
-        self.memory_manager.allocate(amount_mb)?;
+        // Actually allocate the memory, off the render loop
+        let kind = self.memory_manager.food();
+        match self.memory_manager.start_allocation(amount_mb, kind, content) {
+            Ok(job) => {
+                self.meal = Some(Meal { job, amount_mb, favorite });
+                true
+            }
+            Err(e) => {
+                log::warn!("Feeding failed: {:#}", e);
+                self.add_message(
+                    format!("Couldn't get {} food! The pet goes without.", kind),
+                    "❌".to_string().red(),
+                );
+                false
+            }
+        }
+    }
+    
+    /// Let the pet eat a meal whose allocation job has finished
+    fn finish_meal(&mut self, meal: Meal) -> Result<()> {
+        let keep_partial = self.config.game.cancelled_meal == CancelledMeal::Credit
+            && !self.pet.is_dead();
+        let kind = meal.job.kind();
+        let content = meal.job.content();
+        let outcome = self.memory_manager.finish_allocation(meal.job, keep_partial);
+        
+        if let Some(e) = &outcome.error {
+            log::warn!("Feeding failed: {:#}", e);
+        }
+        
+        // Whole blocks may overshoot the amount asked for
+        let eaten = outcome.kept.min(meal.amount_mb);
+        if self.pet.is_dead() || eaten.is_zero() {
+            let text = if outcome.cancelled {
+                "Meal cancelled. Nothing was eaten.".to_string()
+            } else {
+                format!("Couldn't get {} food! The pet goes without.", kind)
+            };
+            self.add_message(text, "❌".to_string().red());
+            return Ok(());
+        }
         
         // Feed the pet
-        self.pet.eat(amount_mb)?;
+        self.pet.eat(eaten)?;
+        if meal.favorite {
+            self.pet.boost_happiness();
+        }
+        let liking = self.pet.taste(content);
         
         // Update stats
-        self.stats.total_mb_eaten += amount_mb;
+        self.stats.total_mb_eaten += eaten;
         self.stats.feeding_count += 1;
         
         // Add feeding message
-        let food_name = feeding::get_feeding_name(amount_mb);
-        self.add_message(
-            format!("Fed {} ({} MB)", food_name, amount_mb),
-            format!("{}!", self.pet.get_reaction()).green(),
-        );
+        if eaten < meal.amount_mb {
+            self.add_message(
+                format!("Stopped after {} of {} ({})", eaten, meal.amount_mb, kind),
+                "🍽️".to_string().yellow(),
+            );
+        } else {
+            let food_name = feeding::get_feeding_name(eaten);
+            self.add_message(
+                format!("Fed {} ({} of {}, {})", food_name, eaten, content, kind),
+                format!("{}!", self.pet.get_reaction()).green(),
+            );
+        }
+        if liking >= 0.5 {
+            self.add_message(format!("Loves {} food!", content), "😋".to_string().bright_green());
+        } else if liking <= -0.5 {
+            self.add_message(format!("Yuck, {} food!", content), "🤢".to_string().bright_red());
+        }
         
         // Sound effect
         if self.config.game.sound_enabled {
@@ -160,22 +581,28 @@ impl Game {
         Ok(())
     }
     
-    /// Feed pet its favorite food
-    pub async fn feed_pet_favorite(&mut self) -> Result<()> {
-        let favorite_amount = self.pet.get_favorite_food_size();
-        
-        // Special message for favorite food
-        self.add_message(
-            format!("Favorite food! ({} MB)", favorite_amount),
-            "✨ PURE JOY ✨".bright_green(),
-        );
-        
-        self.feed_pet(favorite_amount).await?;
-        self.pet.boost_happiness();
-        
+    /// Finish the meal in progress if its job is done
+    fn poll_meal(&mut self) -> Result<()> {
+        if self.meal.as_ref().is_some_and(|meal| meal.job.is_finished()) {
+            let meal = self.meal.take().unwrap();
+            self.finish_meal(meal)?;
+        }
         Ok(())
     }
     
+    /// Throw away the meal and balloon growth in progress without
+    /// giving them to the pet
+    fn abandon_meal(&mut self) {
+        if let Some(meal) = self.meal.take() {
+            meal.job.cancel();
+            self.memory_manager.finish_allocation(meal.job, false);
+        }
+        if let Some(growth) = self.growth.take() {
+            growth.cancel();
+            self.memory_manager.finish_allocation(growth, false);
+        }
+    }
+    
     /// Render the game screen using the fixed frame renderer
     pub fn render(&mut self) -> Result<()> {
         // Use the new fixed frame renderer for stable display
@@ -183,9 +610,30 @@ impl Game {
             &self.pet,
             &self.system_monitor,
             &self.messages,
-            self.stats.total_mb_eaten,
-            self.stats.play_time,
-            self.show_help
+            &SessionInfo {
+                held_mb: self.memory_manager.get_allocated_mb(),
+                food: self.memory_manager.food(),
+                residency: self.residency.0,
+                meal: self.meal.as_ref().map(|meal| &meal.job).or(self.growth.as_ref()).map(|job| MealProgress {
+                    eaten: job.progress(),
+                    size: job.target(),
+                    cancelling: job.is_cancelled(),
+                }),
+                rivals: self.competition.standings().to_vec(),
+                rank: self.competition.rank(),
+                watched: self.leaks.as_ref().map(LeakDetector::watched),
+                adopted: self.adoption.as_ref().map(|adoption| (adoption.pid(), adoption.name().to_string())),
+                balloon: self.balloon.as_ref().map(|balloon| BalloonStatus {
+                    fraction: balloon.fraction(),
+                    target: balloon.target(),
+                    squeeze: balloon.squeeze(),
+                }),
+                slot: self.slot.clone(),
+                total_eaten: self.stats.total_mb_eaten,
+                play_time: self.stats.play_time,
+            },
+            self.show_help,
+            self.browser.as_ref(),
         )?;
         
         stdout().flush()?;
@@ -210,58 +658,194 @@ impl Game {
     
     /// Save game state
     pub fn save_game(&mut self) -> Result<()> {
-        let save_data = SaveData {
-            pet: self.pet.clone(),
-            total_mb_eaten: self.stats.total_mb_eaten,
-            feeding_count: self.stats.feeding_count,
-            max_size_reached: self.stats.max_size_reached,
-        };
+        // Its size belongs to another process; loading it later would eat that much
+        if self.refuse_if_adopted("can't be saved") {
+            return Ok(());
+        }
         
-        let json = serde_json::to_string_pretty(&save_data)?;
-        std::fs::create_dir_all("saves")?;
-        std::fs::write(&self.config.game.save_path, json)?;
+        self.store.save(&self.save_file())?;
+        self.autosave.last = Instant::now();
         
         self.add_message(
-            "Game saved successfully!".to_string(),
+            format!("Saved to slot '{}'", self.slot),
             "💾".to_string().bright_cyan(),
         );
         
         Ok(())
     }
     
-    /// Load game state
+    /// Load the pet from the current save slot
     pub fn load_game(&mut self) -> Result<()> {
-        if !std::path::Path::new(&self.config.game.save_path).exists() {
-            self.add_message(
-                "No save file found!".to_string(),
-                "❌".to_string().bright_red(),
-            );
+        let slot = self.slot.clone();
+        self.load_slot(&slot)
+    }
+    
+    /// Replace the pet with the one saved in a slot, which becomes the current slot
+    fn load_slot(&mut self, slot: &str) -> Result<()> {
+        if self.refuse_if_adopted("can't be swapped for a saved pet") {
             return Ok(());
         }
         
-        let json = std::fs::read_to_string(&self.config.game.save_path)?;
-        let save_data: SaveData = serde_json::from_str(&json)?;
+        let store = self.slots.store(slot);
+        let loaded = match store.load() {
+            Ok(Some(loaded)) => loaded,
+            Ok(None) => {
+                self.add_message(
+                    "No save file found!".to_string(),
+                    "❌".to_string().bright_red(),
+                );
+                return Ok(());
+            }
+            Err(e) => {
+                // Keep playing with the pet we have rather than quit
+                log::error!("{:#}", e);
+                self.add_message(
+                    "Save and all backups are damaged!".to_string(),
+                    "❌".to_string().bright_red(),
+                );
+                return Ok(());
+            }
+        };
+        if loaded.format < loaded.file.format {
+            log::info!("Loaded a format {} save from {}", loaded.format, loaded.path.display());
+        }
+        self.created_at = loaded.file.created_at;
+        self.slot = slot.to_string();
+        self.store = store;
+        let save_data = loaded.file.data;
+        
+        // A half-eaten meal belonged to the old pet
+        self.abandon_meal();
         
         self.pet = save_data.pet;
         self.stats.total_mb_eaten = save_data.total_mb_eaten;
         self.stats.feeding_count = save_data.feeding_count;
         self.stats.max_size_reached = save_data.max_size_reached;
         
+        // Live through the time the game was closed
+        let report = self.pet.catch_up(loaded.file.saved_at, OfflineRules::from_config(&self.config));
+        log::info!(
+            "Away for {:?}, simulated {:?}: {} -> {}",
+            report.away, report.simulated, report.size_before, report.size_after
+        );
+        self.birthdays = self.pet.years_old_at(loaded.file.saved_at);
+        self.away = (report.away >= AWAY_SCREEN_AFTER).then_some(report);
+        
         // Reallocate memory to match pet size
-        self.memory_manager.clear();
-        self.memory_manager.allocate(self.pet.get_size_mb())?;
+        self.reconcile_memory()?;
+        self.autosave = Autosave::new(&self.pet);
         
-        self.add_message(
-            "Game loaded successfully!".to_string(),
-            "📂".to_string().bright_cyan(),
-        );
+        match loaded.backup {
+            Some(n) => self.add_message(
+                format!("Save was damaged, recovered backup {}", n),
+                "🩹".to_string().bright_yellow(),
+            ),
+            None => self.add_message(
+                format!("Loaded {} from slot '{}'", self.pet.name, slot),
+                "📂".to_string().bright_cyan(),
+            ),
+        }
+        
+        Ok(())
+    }
+    
+    /// Whether there's a "while you were away" report to show
+    pub fn has_away_report(&self) -> bool {
+        self.away.is_some()
+    }
+    
+    /// Show what happened while the game was closed; it's shown once
+    pub fn show_away_screen(&mut self) -> Result<()> {
+        if let Some(report) = self.away.take() {
+            self.renderer.draw_away_screen(&self.pet, &report)?;
+        }
+        Ok(())
+    }
+    
+    /// Open the save/load screen
+    pub fn open_saves(&mut self) {
+        let slots = self.list_slots();
+        self.browser = Some(SaveBrowser::new(slots, &self.slot));
+    }
+    
+    /// Whether the save/load screen is open
+    pub fn is_browsing_saves(&self) -> bool {
+        self.browser.is_some()
+    }
+    
+    /// Pass a key press to the save/load screen and carry out what it asks for
+    pub fn browse_saves(&mut self, key: KeyCode) -> Result<()> {
+        let action = match self.browser.as_mut().and_then(|browser| browser.handle(key)) {
+            Some(action) => action,
+            None => return Ok(()),
+        };
         
+        let (done, select) = match action {
+            BrowserAction::Close => {
+                self.browser = None;
+                return Ok(());
+            }
+            BrowserAction::Load(slot) => {
+                self.browser = None;
+                return self.load_slot(&slot);
+            }
+            BrowserAction::SaveAs(slot) => (self.save_as(&slot), slot),
+            BrowserAction::Rename { from, to } => {
+                let renamed = self.slots.rename(&from, &to).map(|()| format!("Renamed '{}' to '{}'", from, to));
+                if renamed.is_ok() && from == self.slot {
+                    self.store = self.slots.store(&to);
+                    self.slot = to.clone();
+                }
+                (renamed, to)
+            }
+            BrowserAction::Duplicate { from, to } => {
+                (self.slots.duplicate(&from, &to).map(|()| format!("Copied '{}' to '{}'", from, to)), to)
+            }
+            BrowserAction::Delete(slot) => {
+                (self.slots.remove(&slot).map(|_| format!("Deleted slot '{}'", slot)), slot)
+            }
+        };
+        
+        match done {
+            Ok(text) => self.add_message(text, "💾".to_string().bright_cyan()),
+            Err(e) => self.add_message(format!("{:#}", e), "❌".to_string().bright_red()),
+        }
+        let slots = self.list_slots();
+        if let Some(browser) = &mut self.browser {
+            browser.refresh(slots, Some(&select));
+        }
         Ok(())
     }
     
+    /// Save the pet to a new slot and keep playing there
+    fn save_as(&mut self, slot: &str) -> Result<String> {
+        if self.adoption.is_some() {
+            bail!("adopted pets can't be saved");
+        }
+        crate::save::slots::validate_name(slot)?;
+        if self.slots.exists(slot) {
+            bail!("save slot '{}' is already taken", slot);
+        }
+        let store = self.slots.store(slot);
+        store.save(&self.save_file())?;
+        self.slot = slot.to_string();
+        self.store = store;
+        self.autosave.last = Instant::now();
+        Ok(format!("Saved to new slot '{}'", slot))
+    }
+    
+    fn list_slots(&self) -> Vec<SlotInfo> {
+        self.slots.list().unwrap_or_else(|e| {
+            log::warn!("{:#}", e);
+            Vec::new()
+        })
+    }
+    
     /// Emergency exit (pet dies immediately)
     pub fn emergency_exit(&mut self) -> Result<()> {
+        self.abandon_meal();
         self.pet.kill();
+        self.reconcile_memory()?;
         self.add_message(
             "EMERGENCY EXIT ACTIVATED!".to_string(),
             "☠️".to_string().bright_red(),
@@ -279,6 +863,61 @@ impl Game {
         }
     }
     
+    /// Switch to the next food source for future feedings
+    pub fn cycle_food_source(&mut self) {
+        let next = self.memory_manager.food().next();
+        self.memory_manager.set_food(next);
+        self.add_message(
+            format!("Now serving {} food", next),
+            "🍽️".to_string().cyan(),
+        );
+    }
+    
+    /// Warm the pet up by touching whatever the kernel swapped out
+    pub fn warm_up(&mut self) {
+        let warmed = self.memory_manager.warm_up();
+        self.residency = (self.memory_manager.residency(), Instant::now());
+        
+        if warmed.is_zero() {
+            self.add_message("Already toasty warm!".to_string(), "☀️".to_string().yellow());
+        } else {
+            self.add_message(
+                format!("Warmed up {} back from swap", warmed),
+                "🔥".to_string().bright_yellow(),
+            );
+        }
+    }
+    
+    /// Turn balloon mode on or off
+    pub fn toggle_balloon(&mut self) {
+        if self.refuse_if_adopted("can't go into balloon mode") {
+            return;
+        }
+        
+        if self.balloon.take().is_some() {
+            if let Some(growth) = self.growth.take() {
+                growth.cancel();
+                self.memory_manager.finish_allocation(growth, false);
+            }
+            self.add_message(
+                "Balloon mode off - back to normal meals".to_string(),
+                "🎈".to_string().cyan(),
+            );
+        } else {
+            let fraction = self.config.system.balloon_fraction;
+            self.balloon = Some(Balloon::new(fraction, self.config.system.min_free_ram_mb));
+            self.add_message(
+                format!("Balloon mode on - eating {:.0}% of spare RAM", fraction * 100.0),
+                "🎈".to_string().bright_magenta(),
+            );
+        }
+    }
+    
+    /// Switch the stats area to the next panel (stats, vitals)
+    pub fn cycle_panel(&mut self) {
+        self.renderer.cycle_panel();
+    }
+    
     /// Check if help is currently showing
     pub fn is_help_showing(&self) -> bool {
         self.show_help
@@ -294,6 +933,232 @@ impl Game {
         }
     }
     
+    /// Difference between the pet's size and the RAM actually held
+    pub fn memory_gap_mb(&self) -> i64 {
+        self.reconciler.gap_mb
+    }
+    
+    /// Shrink the pet to match RAM the safety governor took back
+    fn handle_governor_events(&mut self) {
+        let events: Vec<GovernorEvent> = match &self.governor {
+            Some(governor) => governor.events().collect(),
+            None => return,
+        };
+        
+        for event in events {
+            match event {
+                GovernorEvent::Vomited { released, available } => {
+                    // No more eating until the system has recovered
+                    self.abandon_meal();
+                    self.pet.vomit(self.memory_manager.get_allocated_mb());
+                    self.add_message(
+                        format!("Threw up {} - only {} left free!", released, available),
+                        "🤮".to_string().bright_red(),
+                    );
+                }
+            }
+        }
+    }
+    
+    /// Poison the pet for every corrupted block the scrubber turned up
+    fn handle_scrub_events(&mut self) {
+        let found = match &self.scrubber {
+            Some(scrubber) => scrubber.events().count(),
+            None => return,
+        };
+        
+        if found > 0 {
+            self.mismatches += found;
+            self.add_message(
+                format!("Corrupted memory found in {} block(s) - see the log", found),
+                "☠".to_string().bright_green(),
+            );
+        }
+    }
+    
+    /// Ask for the full process table only while something needs it
+    ///
+    /// Sampling it means reading every process on the system, so it's
+    /// dropped whenever an adopted pet, the rivals panel or a due leak
+    /// sample doesn't call for it.
+    fn sample_processes(&self, now: Instant) {
+        let wanted = self.adoption.is_some()
+            || (self.browser.is_none() && self.renderer.panel() == Panel::Rivals)
+            || self.leaks.as_ref().is_some_and(|leaks| leaks.wants_table(now));
+        if wanted {
+            self.system_monitor.get_process_table();
+        } else {
+            self.system_monitor.release_process_table();
+        }
+    }
+    
+    /// Mirror the adopted process's size and growth, and die when it exits
+    fn follow_adoption(&mut self) {
+        let adoption = match &mut self.adoption {
+            Some(adoption) => adoption,
+            None => return,
+        };
+        
+        match adoption.observe(self.system_monitor.snapshot()) {
+            Some(Sighting::Alive { size, hunger }) => self.pet.mirror(size, hunger),
+            Some(Sighting::Gone) => {
+                let text = format!("{} (pid {}) has exited", adoption.name(), adoption.pid());
+                log::info!("{}", text);
+                self.pet.kill();
+                self.add_message(text, "🪦".to_string().bright_red());
+            }
+            None => {}
+        }
+    }
+    
+    /// Have the pet complain about processes that just started looking leaky
+    fn watch_for_leaks(&mut self) {
+        let leaks = match &mut self.leaks {
+            Some(leaks) => leaks,
+            None => return,
+        };
+        
+        for suspect in leaks.observe(self.system_monitor.snapshot()) {
+            log::warn!("Possible memory leak: {}", suspect);
+            let warning = self.pet.get_personality().get_leak_warning(&suspect.to_string());
+            self.add_message(warning, "🕵".to_string().bright_red());
+        }
+    }
+    
+    /// Re-rank the pet; climbing makes it happy, and it trash talks either way
+    ///
+    /// Only snapshots with the process table count, which mostly means
+    /// while the rivals panel is up.
+    fn compete(&mut self) {
+        let size = if self.pet.is_dead() { MiB(0) } else { self.pet.get_size_mb() };
+        let change = match self.competition.update(self.system_monitor.snapshot(), &self.pet.name, size) {
+            Some(change) => change,
+            None => return,
+        };
+        
+        // Sizes close together swap places a lot; don't make a scene (or a
+        // happier pet) every time
+        let now = Instant::now();
+        if self.last_taunt.is_some_and(|last| now.duration_since(last) < TAUNT_COOLDOWN) {
+            return;
+        }
+        self.last_taunt = Some(now);
+        
+        let personality = self.pet.get_personality();
+        match change {
+            RankChange::Climbed { rank, passed } => {
+                let boast = personality.get_rank_boast(&passed.name, rank);
+                self.pet.boost_happiness();
+                self.add_message(boast, "🏆".to_string().bright_yellow());
+            }
+            RankChange::Dropped { leader, .. } => {
+                let taunt = personality.get_rival_taunt(&leader.name, leader.size.to_bytes());
+                self.add_message(taunt, "😤".to_string().bright_red());
+            }
+        }
+    }
+    
+    /// Tell the player an adopted pet doesn't do that, returning whether it is adopted
+    fn refuse_if_adopted(&mut self, what: &str) -> bool {
+        let text = match &self.adoption {
+            Some(adoption) => format!("{} is adopted - it {}!", adoption.name(), what),
+            None => return false,
+        };
+        self.add_message(text, "🏠".to_string().cyan());
+        true
+    }
+    
+    /// Grow the pet into spare RAM, or hand RAM back when others need it
+    fn inflate_balloon(&mut self) -> Result<()> {
+        // Growth that finished allocating becomes part of the pet
+        if self.growth.as_ref().is_some_and(AllocationJob::is_finished) {
+            let growth = self.growth.take().unwrap();
+            let outcome = self.memory_manager.finish_allocation(growth, !self.pet.is_dead());
+            if let Some(e) = &outcome.error {
+                log::warn!("Balloon growth stopped early: {:#}", e);
+            }
+            self.pet.grow_to(self.pet.get_size_mb() + outcome.kept);
+        }
+        
+        // Meals and growth in flight would throw the numbers off; wait for them
+        if self.pet.is_dead() || self.meal.is_some() || self.growth.is_some() {
+            return Ok(());
+        }
+        let held = self.memory_manager.get_allocated_mb();
+        let free = self.memory_manager.estimated_free_mb();
+        let target = match &mut self.balloon {
+            Some(balloon) => match balloon.update(self.system_monitor.snapshot(), free, held) {
+                Some(target) => target.min(self.config.pet.max_size_mb),
+                None => return Ok(()),
+            },
+            None => return Ok(()),
+        };
+        
+        let size = self.pet.get_size_mb();
+        if target > size {
+            log::info!("Balloon growing from {} to {}", size, target);
+            // Off the render loop, like a meal, but it isn't one: no feeding
+            let kind = self.memory_manager.food();
+            let content = self.memory_manager.content();
+            match self.memory_manager.start_allocation(target - size, kind, content) {
+                Ok(growth) => self.growth = Some(growth),
+                Err(e) => log::warn!("Balloon couldn't grow: {:#}", e),
+            }
+        } else if target < size {
+            log::info!("Balloon squeezed from {} to {}", size, target);
+            self.pet.shrink_to(target);
+            self.memory_manager.release(held.saturating_sub(target))?;
+            self.add_message(
+                format!("Squeezed! Handed {} back to the system", size - target),
+                "🎈".to_string().bright_magenta(),
+            );
+        }
+        Ok(())
+    }
+    
+    /// What the integrity scrubber has checked and found so far
+    pub fn integrity_report(&self) -> Option<ScrubReport> {
+        self.scrubber.as_ref().map(Scrubber::report)
+    }
+    
+    /// Make the memory manager hold exactly as much RAM as the pet weighs
+    ///
+    /// Digestion, loading and death shrink or grow the pet; this releases or
+    /// allocates blocks to match. If the system won't give us the RAM, the
+    /// pet is shrunk to what we really hold instead.
+    fn reconcile_memory(&mut self) -> Result<()> {
+        // An adopted pet's size is someone else's memory
+        if self.adoption.is_some() {
+            return Ok(());
+        }
+        
+        let target = if self.pet.is_dead() { MiB(0) } else { self.pet.get_size_mb() };
+        let held = self.memory_manager.get_allocated_mb();
+        
+        if held > target {
+            self.memory_manager.digest(held - target)?;
+        } else if held < target {
+            if let Err(e) = self.memory_manager.allocate(target - held) {
+                log::warn!("Could not back pet with {}: {}", target - held, e);
+                
+                let actual = self.memory_manager.get_allocated_mb();
+                self.pet.shrink_to(actual);
+                
+                if self.reconciler.should_report(Instant::now()) {
+                    self.add_message(
+                        format!("Not enough RAM to keep up! Pet shrank to {}", actual),
+                        "📉".to_string().yellow(),
+                    );
+                }
+            }
+        }
+        
+        let pet_size = if self.pet.is_dead() { MiB(0) } else { self.pet.get_size_mb() };
+        self.reconciler.gap_mb = pet_size.delta(self.memory_manager.get_allocated_mb());
+        
+        Ok(())
+    }
+    
     /// Check RAM levels and warn if necessary
     fn check_ram_levels(&mut self) -> Result<()> {
         let free_ram = self.system_monitor.get_free_ram_mb();
@@ -316,9 +1181,13 @@ impl Game {
                     "CRITICAL: RAM dangerously low!".to_string(),
                     "⚠️".to_string().bright_red(),
                 );
-            } else if free_ram < self.config.system.warning_threshold_mb / 2 {
+            } else if free_ram < MiB(self.config.system.warning_threshold_mb.0 / 2) {
+                let scope = match self.system_monitor.get_budget().scope {
+                    BudgetScope::Host => "RAM",
+                    BudgetScope::Cgroup => "cgroup memory",
+                };
                 self.add_message(
-                    format!("Warning: Only {} MB RAM free", free_ram),
+                    format!("Warning: Only {} {} free", free_ram, scope),
                     "⚠️".to_string().yellow(),
                 );
             }
@@ -332,18 +1201,31 @@ impl Game {
     }
 }
 
-/// Save data structure
-#[derive(serde::Serialize, serde::Deserialize)]
-struct SaveData {
-    pet: Pet,
-    total_mb_eaten: usize,
-    feeding_count: usize,
-    max_size_reached: usize,
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::system::food::{FoodKind, StallingSource};
+    use std::sync::Arc;
+    
+    /// A few MB of food, so tests don't need a big machine
+    const CRUMB: FoodType = FoodType { name: "Crumb", size: MiB(2), content: FoodContent::Text };
+    
+    /// Settings for a small pet with no governor, scrubber or leak watch
+    /// threads, that only needs a little free RAM to eat
+    fn small_config() -> Config {
+        let mut config = Config::default();
+        config.system.min_free_ram_mb = MiB(16);
+        config.pet.starting_size_mb = MiB(8);
+        config.pet.max_size_mb = MiB(32);
+        config.system.governor = false;
+        config.system.scrub_rate_mb = MiB(0);
+        config.system.leak_watch = 0;
+        config
+    }
+    
+    fn small_game() -> Game {
+        Game::with_config(small_config()).unwrap()
+    }
     
     #[tokio::test]
     async fn test_game_creation() {
@@ -353,13 +1235,117 @@ mod tests {
     
     #[tokio::test]
     async fn test_feed_pet() {
-        let mut game = Game::new().unwrap();
+        let mut game = small_game();
+        let initial_size = game.pet.get_size_mb();
+        
+        game.feed_pet(CRUMB).await.unwrap();
+        let meal = game.meal.take().expect("the meal should have started");
+        game.finish_meal(meal).unwrap();
+        
+        assert_eq!(game.pet.get_size_mb(), initial_size + CRUMB.size);
+    }
+    
+    #[tokio::test]
+    async fn test_checked_body_is_patterned() {
+        let mut config = small_config();
+        config.system.scrub_rate_mb = MiB(64);
+        let mut game = Game::with_config(config).unwrap();
+        
+        // The body the scrubber checks can show a stuck bit either way...
+        let blocks = game.memory_manager.shared_blocks();
+        assert!(blocks.lock().unwrap().iter().all(|b| b.content() == Some(FoodContent::Pattern)));
+        
+        // ...but a meal is still what the player fed
+        game.feed_pet(CRUMB).await.unwrap();
+        let meal = game.meal.take().expect("the meal should have started");
+        game.finish_meal(meal).unwrap();
+        assert_eq!(blocks.lock().unwrap().last().unwrap().content(), Some(FoodContent::Text));
+    }
+    
+    #[tokio::test]
+    async fn test_memory_follows_pet_size() {
+        let mut game = small_game();
+        assert_eq!(game.memory_manager.get_allocated_mb(), game.pet.get_size_mb());
+        
+        // Shrinking the pet should hand the RAM back
+        game.pet.shrink_to(MiB(3));
+        game.reconcile_memory().unwrap();
+        assert_eq!(game.memory_manager.get_allocated_mb(), MiB(3));
+        assert_eq!(game.memory_gap_mb(), 0);
+        
+        // A dead pet holds nothing, including a meal in progress
+        let _ = game.feed_pet(CRUMB).await;
+        game.emergency_exit().unwrap();
+        assert_eq!(game.memory_manager.get_allocated_mb(), MiB(0));
+    }
+    
+    /// A 16MB meal cancelled once the pet is part-way through it
+    ///
+    /// Four blocks are in when the cancel lands and the stalled fifth
+    /// finishes, so exactly 5MB were allocated.
+    fn cancelled_meal(game: &mut Game) -> Meal {
+        let source = Arc::new(StallingSource::new(4));
+        let job = game.memory_manager.start_allocation_with(source.clone(), MiB(16), FoodContent::Text).unwrap();
+        
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while job.progress() < MiB(4) && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        job.cancel();
+        source.release();
+        Meal { job, amount_mb: MiB(16), favorite: false }
+    }
+    
+    #[tokio::test]
+    async fn test_cancelled_meal_is_credited() {
+        let mut game = small_game();
+        let initial_size = game.pet.get_size_mb();
+        let initial_allocated = game.memory_manager.get_allocated_mb();
+        
+        let meal = cancelled_meal(&mut game);
+        game.finish_meal(meal).unwrap();
+        
+        // Whatever was allocated before the cancel went into the pet
+        let kept = game.memory_manager.get_allocated_mb() - initial_allocated;
+        let eaten = game.pet.get_size_mb() - initial_size;
+        assert_eq!(kept, MiB(5));
+        assert_eq!(eaten, kept);
+        game.reconcile_memory().unwrap();
+        assert_eq!(game.memory_gap_mb(), 0);
+    }
+    
+    #[tokio::test]
+    async fn test_cancelled_meal_is_rolled_back() {
+        let mut game = small_game();
+        game.config.game.cancelled_meal = CancelledMeal::Rollback;
+        let initial_size = game.pet.get_size_mb();
+        let initial_allocated = game.memory_manager.get_allocated_mb();
+        
+        let meal = cancelled_meal(&mut game);
+        game.finish_meal(meal).unwrap();
+        
+        assert_eq!(game.pet.get_size_mb(), initial_size);
+        assert_eq!(game.memory_manager.get_allocated_mb(), initial_allocated);
+    }
+    
+    #[tokio::test]
+    async fn test_balloon_growth_is_not_a_meal() {
+        let mut game = small_game();
         let initial_size = game.pet.get_size_mb();
+        let hunger = game.pet.get_hunger();
         
-        // Try to feed pet (may fail if not enough RAM)
-        let _ = game.feed_pet(10).await;
+        let growth = game.memory_manager.start_allocation(MiB(4), FoodKind::Heap, FoodContent::Zeros).unwrap();
+        game.growth = Some(growth);
+        while !game.growth.as_ref().unwrap().is_finished() {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        game.inflate_balloon().unwrap();
+        game.reconcile_memory().unwrap();
         
-        // Size should either increase or stay same (if feeding failed)
-        assert!(game.pet.get_size_mb() >= initial_size);
+        assert!(game.growth.is_none());
+        assert_eq!(game.pet.get_size_mb(), initial_size + MiB(4));
+        assert_eq!(game.memory_gap_mb(), 0);
+        assert_eq!(game.pet.get_hunger(), hunger);
+        assert_eq!(game.stats.feeding_count, 0);
     }
-}
\ No newline at end of file
+}
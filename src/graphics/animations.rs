@@ -4,6 +4,8 @@
 use colored::*;
 use std::time::{Duration, Instant};
 
+use crate::system::units::MiB;
+
 /// Animation frame data
 #[derive(Debug, Clone)]
 pub struct AnimationFrame {
@@ -277,8 +279,8 @@ pub fn create_death_animation() -> Animation {
 }
 
 /// Particle effect for feeding
-pub fn create_feeding_particles(amount: usize) -> Vec<String> {
-    let particles = match amount {
+pub fn create_feeding_particles(amount: MiB) -> Vec<String> {
+    let particles = match amount.0 {
         0..=30 => vec![".", ".", "."],
         31..=100 => vec!["*", "*", "*", "*"],
         101..=300 => vec!["●", "●", "●", "●", "●"],
@@ -307,7 +309,7 @@ mod tests {
     
     #[test]
     fn test_feeding_particles() {
-        let particles = create_feeding_particles(50);
+        let particles = create_feeding_particles(MiB(50));
         assert!(!particles.is_empty());
     }
 }
\ No newline at end of file
@@ -114,7 +114,7 @@ pub fn random_bright_color() -> Color {
     use rand::Rng;
     let mut rng = rand::thread_rng();
     
-    let colors = vec![
+    let colors = [
         Color::TrueColor { r: 255, g: 0, b: 128 },
         Color::TrueColor { r: 0, g: 255, b: 255 },
         Color::TrueColor { r: 255, g: 255, b: 0 },
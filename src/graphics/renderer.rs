@@ -7,16 +7,111 @@ use crossterm::{cursor, execute, terminal};
 use std::io::{stdout, Write};
 use std::time::{Duration, Instant};
 
-use crate::pet::Pet;
-use crate::system::monitor::SystemMonitor;
-use super::{ROUNDED_BORDER, create_meter, create_box, format_duration, center_text};
+use crate::household::Member;
+use crate::pet::{AwayReport, Pet, COLD_FRACTION, HEARTBURN_FAULT_RATE};
+use crate::save::browser::{Confirm, Naming, Prompt};
+use crate::save::SaveBrowser;
+use crate::system::food::FoodKind;
+use crate::system::leaks::WatchedProcess;
+use crate::system::rivals::{Rival, RIVALS};
+use crate::system::memory::Residency;
+use crate::system::monitor::{SystemMonitor, STALL_PRESSURE};
+use crate::system::sampler::BudgetScope;
+use crate::system::units::MiB;
+use super::{ROUNDED_BORDER, create_meter, create_box, create_progress_bar, create_sparkline, format_duration};
+
+/// Session numbers shown in the stats panel
+#[derive(Debug, Clone, Default)]
+pub struct SessionInfo {
+    /// RAM actually held by the memory manager
+    pub held_mb: MiB,
+    /// Food source the next feeding comes from
+    pub food: FoodKind,
+    /// How much of the held RAM is really resident
+    pub residency: Residency,
+    /// Meal still being allocated, if any
+    pub meal: Option<MealProgress>,
+    /// The biggest processes and the pet, biggest first
+    pub rivals: Vec<Rival>,
+    /// The pet's rank among them (1 = biggest)
+    pub rank: Option<usize>,
+    /// Processes watched for leaks, suspects first, unless the detector is off
+    pub watched: Option<Vec<WatchedProcess>>,
+    /// PID and name of the process an adopted pet mirrors
+    pub adopted: Option<(u32, String)>,
+    /// Balloon mode, if it's on
+    pub balloon: Option<BalloonStatus>,
+    /// Save slot the game saves to
+    pub slot: String,
+    /// Total RAM fed to the pet this session
+    pub total_eaten: MiB,
+    /// Time spent playing this session
+    pub play_time: Duration,
+}
+
+/// Progress of a meal that's still being allocated
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MealProgress {
+    /// RAM allocated so far
+    pub eaten: MiB,
+    /// RAM the meal will take in total
+    pub size: MiB,
+    /// Whether the player asked to stop
+    pub cancelling: bool,
+}
+
+/// What balloon mode is aiming for
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BalloonStatus {
+    /// Share of spare RAM the pet aims for
+    pub fraction: f32,
+    /// Size the balloon last asked for
+    pub target: Option<MiB>,
+    /// How squeezed the pet is (0.0 - 1.0)
+    pub squeeze: f32,
+}
+
+/// Which panel fills the stats area
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Panel {
+    /// Pet and system overview
+    #[default]
+    Stats,
+    /// Our process's memory vital signs
+    Vitals,
+    /// Other processes' memory, with leak suspects
+    Leaks,
+    /// The pet ranked against the biggest processes
+    Rivals,
+}
+
+impl Panel {
+    /// The panel after this one
+    pub fn next(&self) -> Panel {
+        match self {
+            Panel::Stats => Panel::Vitals,
+            Panel::Vitals => Panel::Leaks,
+            Panel::Leaks => Panel::Rivals,
+            Panel::Rivals => Panel::Stats,
+        }
+    }
+    
+    /// Panel title
+    pub fn name(&self) -> &'static str {
+        match self {
+            Panel::Stats => "Stats",
+            Panel::Vitals => "Vitals",
+            Panel::Leaks => "Leaks",
+            Panel::Rivals => "Rivals",
+        }
+    }
+}
 
 /// Main renderer for the game with fixed layout
 pub struct Renderer {
     use_colors: bool,
-    last_render: Instant,
-    frame_count: u64,
     last_comment: Option<String>,  // Store last comment to prevent jumping
+    panel: Panel,
 }
 
 impl Renderer {
@@ -24,12 +119,21 @@ impl Renderer {
     pub fn new(use_colors: bool) -> Self {
         Renderer { 
             use_colors,
-            last_render: Instant::now(),
-            frame_count: 0,
             last_comment: None,
+            panel: Panel::default(),
         }
     }
     
+    /// Switch the stats area to the next panel
+    pub fn cycle_panel(&mut self) {
+        self.panel = self.panel.next();
+    }
+    
+    /// Panel currently shown in the stats area
+    pub fn panel(&self) -> Panel {
+        self.panel
+    }
+    
     /// Clear entire screen and reset
     pub fn full_clear(&self) -> Result<()> {
         execute!(
@@ -45,12 +149,15 @@ impl Renderer {
                       pet: &Pet, 
                       monitor: &SystemMonitor,
                       messages: &[(String, Instant, ColoredString)],
-                      total_eaten: usize,
-                      play_time: Duration,
-                      show_help: bool) -> Result<()> {
+                      session: &SessionInfo,
+                      show_help: bool,
+                      browser: Option<&SaveBrowser>) -> Result<()> {
         
         let mut stdout = stdout();
         
+        // Honor the color setting for everything drawn this frame
+        colored::control::set_override(self.use_colors);
+        
         // Move to top-left
         execute!(stdout, cursor::MoveTo(0, 0))?;
         
@@ -60,20 +167,28 @@ impl Renderer {
         // Line 5-15: Pet (always 11 lines including state/mood)
         self.draw_pet_fixed(pet)?;
         
-        // Line 16-17: Pet comment (always 2 lines, even if empty)
-        self.draw_comment_fixed(pet)?;
+        // Line 16-17: Pet comment and meal progress (always 2 lines, even if empty)
+        self.draw_comment_fixed(pet, session.meal)?;
         
-        // Line 18-29: Stats (always 12 lines)
-        self.draw_stats_fixed(pet, monitor, total_eaten, play_time)?;
+        // Line 18-29: Save slots or the current panel (always 12 lines)
+        match (browser, self.panel) {
+            (Some(browser), _) => self.draw_saves_fixed(browser, session)?,
+            (None, Panel::Stats) => self.draw_stats_fixed(pet, monitor, session)?,
+            (None, Panel::Vitals) => self.draw_vitals_fixed(pet, monitor, session)?,
+            (None, Panel::Leaks) => self.draw_leaks_fixed(session)?,
+            (None, Panel::Rivals) => self.draw_rivals_fixed(pet, session)?,
+        }
         
         // Line 30-34: Messages (always 5 lines, even if no messages)
         self.draw_messages_fixed(messages)?;
         
         // Line 35-40: Controls or Help (always 6 lines)
-        if show_help {
+        if let Some(browser) = browser {
+            self.draw_saves_controls_fixed(pet, browser)?;
+        } else if show_help {
             self.draw_help_fixed()?;
         } else {
-            self.draw_controls_fixed()?;
+            self.draw_controls_fixed(session.food, self.panel.next())?;
         }
         
         // Ensure everything is drawn
@@ -89,7 +204,7 @@ impl Renderer {
                 "RAM EATING PET SIMULATOR".bright_green().bold()
             )
         );
-        println!("{:^60}", format!("Pet: {}", pet.name).bright_cyan());
+        println!("{:^60}", format!("Pet: {} ({:?})", pet.name, pet.get_tuning().difficulty).bright_cyan());
         println!("{:60}", "═".repeat(60).bright_blue());
         Ok(())
     }
@@ -114,13 +229,22 @@ impl Renderer {
             println!("{:60}", " ");
         }
         
-        // Line 9: Empty
-        println!();
+        // Line 9: Frost gauge when part of the pet is swapped out, else empty
+        let cold = pet.get_symptoms().cold;
+        if cold >= COLD_FRACTION {
+            let flakes = (cold.min(1.0) * 10.0).ceil() as usize;
+            println!("{:^60}", format!("{} {:.0}% swapped out [W] warm up",
+                "❄".repeat(flakes),
+                cold * 100.0
+            ).bright_cyan());
+        } else {
+            println!();
+        }
         
         // Line 10: State and mood
         println!("{:^60}", 
             format!("State: {} | Mood: {}", 
-                pet.get_state().name(),
+                pet.get_life_stage(),
                 pet.get_mood().name()
             ).color(color)
         );
@@ -132,7 +256,7 @@ impl Renderer {
     }
     
     /// Draw pet comment - Fixed 2 lines
-    fn draw_comment_fixed(&mut self, pet: &Pet) -> Result<()> {
+    fn draw_comment_fixed(&mut self, pet: &Pet, meal: Option<MealProgress>) -> Result<()> {
         // Update comment occasionally
         if rand::random::<f32>() < 0.05 {  // 5% chance to change comment
             self.last_comment = self.get_pet_comment(pet);
@@ -143,15 +267,40 @@ impl Renderer {
         } else {
             println!("{:60}", " ");  // Empty line to maintain spacing
         }
-        println!();  // Always have blank line after comment
+        
+        // Meal progress goes in the blank line after the comment
+        match meal {
+            Some(meal) => {
+                let label = if meal.cancelling { "Stopping…  " } else { "Digesting… " };
+                let bar = create_progress_bar(meal.eaten.0 as f32, meal.size.0 as f32, 25, Color::Magenta, Color::BrightBlack);
+                println!("{:60}", format!("  {} {} {} / {}  {}",
+                    label.bright_magenta(),
+                    bar,
+                    meal.eaten.0,
+                    meal.size,
+                    "[C] cancel".bright_black()
+                ));
+            }
+            None => println!("{:60}", " "),
+        }
         
         Ok(())
     }
     
     /// Draw stats panel - Fixed 12 lines
-    fn draw_stats_fixed(&mut self, pet: &Pet, monitor: &SystemMonitor, total_eaten: usize, play_time: Duration) -> Result<()> {
+    fn draw_stats_fixed(&mut self, pet: &Pet, monitor: &SystemMonitor, session: &SessionInfo) -> Result<()> {
         println!("{:60}", "┌─ Stats ─────────────────────────────────────────────┐".bright_blue());
-        println!("{:60}", format!("│ Pet Size: {:44} │", format!("{} MB", pet.get_size_mb()).bright_green()));
+        
+        // Pet size alongside the RAM really held, flagging any drift
+        let pet_size = if pet.is_dead() { MiB(0) } else { pet.get_size_mb() };
+        let held = if let Some((pid, name)) = &session.adopted {
+            format!("RSS of {} ({})", name, pid).bright_black()
+        } else if session.held_mb == pet_size {
+            format!("{} held", session.held_mb).bright_black()
+        } else {
+            format!("{} held, {:+} MB off", session.held_mb, session.held_mb.delta(pet_size)).yellow()
+        };
+        println!("{:60}", format!("│ Pet Size: {:44} │", format!("{} ({})", pet.get_size_mb(), held).bright_green()));
         
         // Hunger meter
         let hunger_color = match pet.get_hunger() {
@@ -171,31 +320,309 @@ impl Renderer {
         let happiness_bar = create_meter("Happiness", pet.get_happiness(), 100.0, happiness_color);
         println!("│ {:54} │", happiness_bar);
         
-        println!("│{:56}│", " ");
+        // Resident vs virtual, so the pet's size can be checked against top
+        println!("{:60}", format!("│ Resident: {:44} │", Self::format_residency(&session.residency, monitor)));
         
-        // System stats
-        let total_ram = monitor.get_total_ram_mb();
-        let used_ram = monitor.get_used_ram_mb();
+        // Memory budget: the host, or our cgroup if that's tighter
+        let budget = monitor.get_budget();
+        let label = match budget.scope {
+            BudgetScope::Host => "System RAM",
+            BudgetScope::Cgroup => "Cgroup RAM",
+        };
+        let total_ram = budget.total.to_mib();
+        let used_ram = budget.used.to_mib();
+        let psi = match monitor.get_pressure() {
+            Some(psi) if psi.some_avg10 >= STALL_PRESSURE => format!("  PSI {:.1}%", psi.some_avg10).bright_red(),
+            Some(psi) => format!("  PSI {:.1}%", psi.some_avg10).bright_black(),
+            None => "".normal(),
+        };
         
-        println!("{:60}", format!("│ System RAM: {} / {} MB{:>26} │", 
-            used_ram.to_string().bright_red(),
-            total_ram.to_string().bright_green(),
-            " "
+        let width = format!("{}: {} / {} MB", label, used_ram.0, total_ram.0).chars().count() + psi.chars().count();
+        println!("{:60}", format!("│ {}: {} / {} MB{}{} │",
+            label,
+            used_ram.0.to_string().bright_red(),
+            total_ram.0.to_string().bright_green(),
+            psi,
+            " ".repeat(54usize.saturating_sub(width))
         ));
         
-        let ram_bar = create_meter("RAM Usage", used_ram as f32, total_ram as f32, Color::Cyan);
+        let ram_bar = create_meter("RAM Usage", used_ram.0 as f32, total_ram.0 as f32, Color::Cyan);
         println!("│ {:54} │", ram_bar);
         
-        println!("│{:56}│", " ");
+        // Balloon mode's aim, in the spare line when it's on
+        match session.balloon {
+            Some(balloon) => {
+                let target = balloon.target.map_or("-".to_string(), |target| target.to_string());
+                let status = format!("{:.0}% of spare, aiming for {}, {:.0}% squeezed",
+                    balloon.fraction * 100.0, target, balloon.squeeze * 100.0);
+                println!("{:60}", format!("│ Balloon: {:45} │", status.bright_magenta()));
+            }
+            None => println!("│{:56}│", " "),
+        }
         
         // Game stats
-        println!("{:60}", format!("│ Total Eaten: {:42} │", format!("{} MB", total_eaten).bright_yellow()));
-        println!("{:60}", format!("│ Play Time: {:44} │", format_duration(play_time).bright_cyan()));
+        println!("{:60}", format!("│ Total Eaten: {:42} │", session.total_eaten.to_string().bright_yellow()));
+        println!("{:60}", format!("│ Play Time: {:44} │", format_duration(session.play_time).bright_cyan()));
+        
+        println!("{:60}", "└──────────────────────────────────────────────────────┘".bright_blue());
+        Ok(())
+    }
+    
+    /// Draw process vital signs panel - Fixed 12 lines
+    fn draw_vitals_fixed(&self, pet: &Pet, monitor: &SystemMonitor, session: &SessionInfo) -> Result<()> {
+        println!("{:60}", "┌─ Vitals ────────────────────────────────────────────┐".bright_blue());
+        
+        let Some(vitals) = monitor.get_vitals() else {
+            println!("│ {:54} │", "Process vitals need Linux /proc/self".bright_black());
+            for _ in 0..9 {
+                println!("│{:56}│", " ");
+            }
+            println!("{:60}", "└──────────────────────────────────────────────────────┘".bright_blue());
+            return Ok(());
+        };
+        
+        let line = |text: String| format!("{:54}", text);
+        
+        println!("│ {} │", line(format!("RSS: {:<14} PSS: {}", vitals.rss, vitals.pss)).bright_white());
+        println!("│ {} │", line(format!("Anonymous: {:<8} File: {:<10} Shmem: {}",
+            vitals.anonymous, vitals.file, vitals.shmem)).bright_white());
+        
+        let swapped = line(format!("Swapped: {} ({:.0}%)", vitals.swap, vitals.swapped_fraction() * 100.0));
+        if vitals.swap.0 > 0 {
+            println!("│ {} │", swapped.bright_cyan());
+        } else {
+            println!("│ {} │", swapped.bright_black());
+        }
+        
+        println!("│{:56}│", " ");
+        println!("│ {} │", line(format!("Minor faults: {:<12} ({:.0}/s)",
+            vitals.minor_faults, vitals.minor_fault_rate)).bright_white());
+        let major = line(format!("Major faults: {:<12} ({:.0}/s)",
+            vitals.major_faults, vitals.major_fault_rate));
+        if vitals.major_fault_rate >= HEARTBURN_FAULT_RATE {
+            println!("│ {} │", major.bright_red());
+        } else {
+            println!("│ {} │", major.bright_white());
+        }
+        
+        println!("│{:56}│", " ");
+        
+        // How much of the process is the pet
+        let pet_share = if vitals.rss.0 > 0 {
+            session.held_mb.to_bytes().0 as f64 / vitals.rss.0 as f64 * 100.0
+        } else {
+            0.0
+        };
+        println!("│ {} │", line(format!("Pet: {} held ({:.0}% of RSS)",
+            session.held_mb, pet_share.min(100.0))).bright_green());
+        
+        let psi = monitor.get_pressure().map_or_else(
+            || "unavailable".to_string(),
+            |psi| format!("some {:.1}%  full {:.1}%", psi.some_avg10, psi.full_avg10),
+        );
+        println!("│ {} │", line(format!("Pressure: {}  Mood: {}", psi, pet.get_mood().name())).bright_magenta());
+        println!("│{:56}│", " ");
         
         println!("{:60}", "└──────────────────────────────────────────────────────┘".bright_blue());
         Ok(())
     }
     
+    /// Draw the leak detector's watch list with sparklines - Fixed 12 lines
+    fn draw_leaks_fixed(&self, session: &SessionInfo) -> Result<()> {
+        println!("{:60}", "┌─ Leaks ─────────────────────────────────────────────┐".bright_blue());
+        
+        let line = |text: String| format!("{:54}", text);
+        let watched = session.watched.as_deref().unwrap_or_default();
+        let suspects = watched.iter().filter(|process| process.suspect).count();
+        let status = match &session.watched {
+            None => "Leak detector is off (system.leak_watch = 0)".to_string(),
+            Some(watched) if watched.is_empty() => "Waiting for the process table...".to_string(),
+            Some(watched) => format!("Watching {} processes, {} suspect(s)", watched.len(), suspects),
+        };
+        if suspects > 0 {
+            println!("│ {} │", line(status).bright_red());
+        } else {
+            println!("│ {} │", line(status).bright_white());
+        }
+        println!("│ {} │", line(format!("  {:12} {:>7} {:>8} {:14} {:>6}", "Process", "PID", "RSS", "History", "Growth")).bright_black());
+        
+        let rows = 8;
+        for process in watched.iter().take(rows) {
+            let name: String = process.name.chars().take(12).collect();
+            let row = line(format!("{}{:12} {:>7} {:>8} {:14} {:>+6}",
+                if process.suspect { "! " } else { "  " },
+                name,
+                process.pid,
+                process.size,
+                create_sparkline(&process.sparkline_points(14), 14),
+                process.growth,
+            ));
+            if process.suspect {
+                println!("│ {} │", row.bright_red());
+            } else {
+                println!("│ {} │", row.bright_white());
+            }
+        }
+        for _ in watched.len().min(rows)..rows {
+            println!("│{:56}│", " ");
+        }
+        
+        println!("{:60}", "└──────────────────────────────────────────────────────┘".bright_blue());
+        Ok(())
+    }
+    
+    /// Draw the food competition between the pet and the top processes - Fixed 12 lines
+    fn draw_rivals_fixed(&self, pet: &Pet, session: &SessionInfo) -> Result<()> {
+        println!("{:60}", "┌─ Food Competition ──────────────────────────────────┐".bright_blue());
+        
+        let line = |text: String| format!("{:54}", text);
+        let status = match session.rank {
+            Some(1) => format!("{} is the biggest eater on the system!", pet.name),
+            Some(rank) => format!("{} is #{} on the food chain", pet.name, rank),
+            None => "Waiting for the process table...".to_string(),
+        };
+        println!("│ {} │", line(status).bright_yellow());
+        
+        // Bars are relative to whoever is on top
+        let leader = session.rivals.first().map_or(MiB(0), |rival| rival.size);
+        for (index, rival) in session.rivals.iter().enumerate() {
+            let rank = if rival.is_pet() { session.rank.unwrap_or(index + 1) } else { index + 1 };
+            let name: String = rival.name.chars().take(16).collect();
+            let bar = if leader.is_zero() { 0 } else { rival.size.0 * 20 / leader.0 };
+            let row = line(format!("{:>3}. {:16} {:>9} {}", rank, name, rival.size.to_string(), "█".repeat(bar)));
+            if rival.is_pet() {
+                println!("│ {} │", row.bright_green().bold());
+            } else {
+                println!("│ {} │", row.bright_white());
+            }
+        }
+        for _ in session.rivals.len()..RIVALS + 1 {
+            println!("│{:56}│", " ");
+        }
+        
+        println!("{:60}", "└──────────────────────────────────────────────────────┘".bright_blue());
+        Ok(())
+    }
+    
+    /// Draw the save slots, most recently played first - Fixed 12 lines
+    fn draw_saves_fixed(&self, browser: &SaveBrowser, session: &SessionInfo) -> Result<()> {
+        println!("{:60}", "┌─ Save Slots ────────────────────────────────────────┐".bright_blue());
+        
+        let line = |text: String| format!("{:54}", text);
+        let slots = browser.slots();
+        let status = match browser.selected().map(|slot| &slot.summary) {
+            Some(Err(problem)) => line(format!("Can't read this slot: {}", problem)).bright_red(),
+            _ => line(format!("{} slot(s), playing '{}'", slots.len(), session.slot)).bright_white(),
+        };
+        println!("│ {} │", status);
+        println!("│ {} │", line(format!("  {:11} {:11} {:8} {:>7} {:11}", "Slot", "Pet", "Stage", "Size", "Last played")).bright_black());
+        
+        // Scroll so the selection stays in view
+        let rows = 8;
+        let first = browser.selected_index().saturating_sub(rows - 1);
+        for (index, slot) in slots.iter().enumerate().skip(first).take(rows) {
+            let marker = if index == browser.selected_index() { ">" } else { " " };
+            let name: String = slot.name.chars().take(11).collect();
+            let row = match &slot.summary {
+                Ok(summary) => {
+                    let pet_name: String = summary.pet_name.chars().take(11).collect();
+                    line(format!("{} {:11} {:11} {:8} {:>7} {:11}{}",
+                        marker,
+                        name,
+                        pet_name,
+                        summary.stage.name(),
+                        summary.size.to_string(),
+                        summary.saved_at.with_timezone(&chrono::Local).format("%m-%d %H:%M"),
+                        if summary.alive { "" } else { "✝" },
+                    ))
+                }
+                Err(_) => line(format!("{} {:11} (damaged)", marker, name)),
+            };
+            let row = match &slot.summary {
+                _ if index == browser.selected_index() => row.bright_green().bold(),
+                Ok(summary) if !summary.alive => row.red(),
+                Ok(_) => row.bright_white(),
+                Err(_) => row.bright_black(),
+            };
+            println!("│ {} │", row);
+        }
+        if slots.is_empty() {
+            println!("│ {} │", line("No saves yet - press [N] to save this pet".to_string()).bright_black());
+        }
+        for _ in slots.len().max(1).min(rows)..rows {
+            println!("│{:56}│", " ");
+        }
+        
+        println!("{:60}", "└──────────────────────────────────────────────────────┘".bright_blue());
+        Ok(())
+    }
+    
+    /// Draw the save browser's keys, or the question it's asking - Fixed 6 lines
+    fn draw_saves_controls_fixed(&self, pet: &Pet, browser: &SaveBrowser) -> Result<()> {
+        let slot = browser.selected().map_or("", |slot| slot.name.as_str());
+        println!("{:60}", "─".repeat(60).bright_black());
+        match browser.prompt() {
+            None => {
+                println!("{:60}", format!("{}  {} Select  {} Load",
+                    "Save slots:".bright_white().bold(),
+                    "[↑/↓]".bright_blue(),
+                    "[ENTER]".bright_green(),
+                ));
+                println!("{:60}", format!("  {} New slot  {} Rename  {} Copy  {} Delete",
+                    "[N]".bright_yellow(),
+                    "[R]".bright_yellow(),
+                    "[C]".bright_yellow(),
+                    "[D]".bright_red(),
+                ));
+                println!("{:60}", format!("  {} Back to the game", "[L/ESC]".bright_blue()));
+            }
+            Some(Prompt::Name { naming, input }) => {
+                let question = match naming {
+                    Naming::SaveAs => format!("Save {} to a new slot named:", pet.name),
+                    Naming::Rename => format!("Rename '{}' to:", slot),
+                    Naming::Duplicate => format!("Copy '{}' to a new slot named:", slot),
+                };
+                println!("{:60}", question.bright_white().bold());
+                println!("{:60}", format!("  > {}_", input).bright_cyan());
+                println!("{:60}", format!("  {} OK  {} Cancel", "[ENTER]".bright_green(), "[ESC]".bright_red()));
+            }
+            Some(Prompt::Confirm(confirm)) => {
+                let question = match confirm {
+                    Confirm::Load => format!("Replace {} with the pet in '{}'?", pet.name, slot),
+                    Confirm::Delete => format!("Delete '{}' and its backups for good?", slot),
+                };
+                println!("{:60}", question.bright_yellow().bold());
+                println!("{:60}", " ");
+                println!("{:60}", format!("  {} Yes  {} No", "[Y]".bright_green(), "[any key]".bright_red()));
+            }
+        }
+        println!("{:60}", "─".repeat(60).bright_black());
+        println!();  // Bottom padding
+        Ok(())
+    }
+    
+    /// Describe how much of the pet is really in RAM
+    fn format_residency(residency: &Residency, monitor: &SystemMonitor) -> ColoredString {
+        let rss = monitor.snapshot().process_rss
+            .map_or_else(|| "?".to_string(), |rss| rss.to_string());
+        
+        let Some(resident) = residency.resident else {
+            return format!("unknown (process RSS {})", rss).bright_black();
+        };
+        
+        let mut text = format!("{} of {}", resident, residency.virtual_size);
+        if residency.locked.0 > 0 {
+            text.push_str(&format!(", {} locked", residency.locked));
+        }
+        text.push_str(&format!(" (RSS {})", rss));
+        
+        if resident < residency.virtual_size {
+            text.yellow()
+        } else {
+            text.bright_black()
+        }
+    }
+    
     /// Draw messages - Fixed 5 lines
     fn draw_messages_fixed(&self, messages: &[(String, Instant, ColoredString)]) -> Result<()> {
         if !messages.is_empty() {
@@ -224,16 +651,24 @@ impl Renderer {
     }
     
     /// Draw controls - Fixed 6 lines
-    fn draw_controls_fixed(&self) -> Result<()> {
+    fn draw_controls_fixed(&self, food: FoodKind, next_panel: Panel) -> Result<()> {
         println!("{:60}", "─".repeat(60).bright_black());
-        println!("{:60}", "Controls:".bright_white().bold());
-        println!("{:60}", format!("  {} Feed (50 MB)    {} Favorite Food    {} Save",
+        println!("{:60}", format!("{}  {} {:10} {} Food: {}",
+            "Controls:".bright_white().bold(),
+            "[TAB]".bright_blue(),
+            next_panel.name(),
+            "[M]".bright_magenta(),
+            food.name().bright_white()
+        ));
+        println!("{:60}", format!("  {} Feed (50 MB)  {} Menu  {} Favorite  {} Save",
             "[SPACE]".bright_green(),
+            "[1-4]".bright_green(),
             "[F]".bright_cyan(),
             "[S]".bright_yellow()
         ));
-        println!("{:60}", format!("  {} Load Game       {} Help            {} Quit",
+        println!("{:60}", format!("  {} Saves  {} Balloon  {} Help  {} Quit",
             "[L]".bright_yellow(),
+            "[B]".bright_magenta(),
             "[H]".bright_blue(),
             "[Q/ESC]".bright_red()
         ));
@@ -263,24 +698,115 @@ impl Renderer {
         self.draw_pet_fixed(pet)
     }
     
-    pub fn draw_stats(&mut self, pet: &Pet, monitor: &SystemMonitor, total_eaten: usize, play_time: Duration) -> Result<()> {
-        self.draw_stats_fixed(pet, monitor, total_eaten, play_time)
+    pub fn draw_stats(&mut self, pet: &Pet, monitor: &SystemMonitor, session: &SessionInfo) -> Result<()> {
+        self.draw_stats_fixed(pet, monitor, session)
     }
     
     pub fn draw_messages(&self, messages: &[(String, Instant, ColoredString)]) -> Result<()> {
         self.draw_messages_fixed(messages)
     }
     
-    pub fn draw_controls(&self) -> Result<()> {
-        self.draw_controls_fixed()
+    pub fn draw_controls(&self, food: FoodKind) -> Result<()> {
+        self.draw_controls_fixed(food, self.panel.next())
     }
     
     pub fn draw_help(&self) -> Result<()> {
         self.draw_help_fixed()
     }
     
+    /// Draw the household leaderboard, one mini pet per user - Fixed 28 lines
+    pub fn draw_household(&mut self, members: &[Member]) -> Result<()> {
+        colored::control::set_override(self.use_colors);
+        execute!(stdout(), cursor::MoveTo(0, 0))?;
+        
+        println!("{:60}", "═".repeat(60).bright_blue());
+        println!("{:^60}", "🏠 THE HOUSEHOLD - RAM BY USER 🏠".bright_cyan().bold());
+        println!("{:60}", "═".repeat(60).bright_blue());
+        
+        // Two lines per user: owner and usage, then their pet
+        let shown = 10;
+        if members.is_empty() {
+            println!("{:60}", "Waiting for the process table...".bright_black());
+            println!("{:60}", " ");
+        }
+        for (rank, member) in members.iter().take(shown).enumerate() {
+            let (r, g, b) = member.mood.get_color();
+            println!("{:60}", format!("{:>2}. {:16} {:>10}  {} processes",
+                rank + 1,
+                member.user.bright_white().bold(),
+                member.usage.to_string().bright_yellow(),
+                member.processes,
+            ));
+            println!("{:60}", format!("    {:22} {} ({})",
+                member.state.get_mini_art(&member.mood).truecolor(r, g, b),
+                member.pet_name,
+                member.state.name(),
+            ));
+        }
+        for _ in members.len().max(1).min(shown)..shown {
+            println!("{:60}", " ");
+            println!("{:60}", " ");
+        }
+        
+        let total: MiB = members.iter().map(|member| member.usage).sum();
+        println!("{:60}", "─".repeat(60).bright_black());
+        println!("{:60}", format!("{} users, {} in total", members.len(), total).bright_white());
+        println!("{:60}", format!("Press {} to leave", "[Q/ESC]".bright_red()));
+        
+        stdout().flush()?;
+        Ok(())
+    }
+    
+    /// Draw what happened while the game was closed
+    pub fn draw_away_screen(&self, pet: &Pet, report: &AwayReport) -> Result<()> {
+        colored::control::set_override(self.use_colors);
+        execute!(stdout(), terminal::Clear(terminal::ClearType::All), cursor::MoveTo(0, 0))?;
+        
+        println!();
+        println!("{:^60}", "════════════════════════════════════════".bright_blue());
+        println!("{:^60}", "WHILE YOU WERE AWAY".bright_cyan().bold());
+        println!("{:^60}", "════════════════════════════════════════".bright_blue());
+        println!();
+        
+        println!("{:^60}", format!("You were gone for {}", format_duration(report.away)).bright_white());
+        if report.simulated < report.away {
+            println!("{:^60}", format!("(only the first {} counted)", format_duration(report.simulated)).bright_black());
+        }
+        println!();
+        
+        println!("{:^60}", format!("Size:      {} -> {}", report.size_before, report.size_after).bright_white());
+        if report.stage_after != report.stage_before {
+            println!("{:^60}", format!("Stage:     {} -> {}", report.stage_before.name(), report.stage_after.name()).bright_yellow());
+        }
+        println!("{:^60}", format!("Hunger:    {:.0}% -> {:.0}%", report.hunger_before, report.hunger_after).bright_white());
+        println!("{:^60}", format!("Happiness: {:.0}% -> {:.0}%", report.happiness_before, report.happiness_after).bright_white());
+        if report.snacks > 0 {
+            println!("{:^60}", format!("The pet sitter fed {} {} times", pet.name, report.snacks).bright_green());
+        }
+        println!();
+        
+        match report.died_after {
+            Some(after) if pet.died_of_old_age() => {
+                println!("{:^60}", format!("{} passed away of old age {} after you left 🕊️", pet.name, format_duration(after)).bright_white().bold());
+            }
+            Some(after) => {
+                println!("{:^60}", format!("{} starved {} after you left 💀", pet.name, format_duration(after)).bright_red().bold());
+            }
+            None => {
+                println!("{:^60}", format!("{} is glad to see you!", pet.name).bright_green());
+            }
+        }
+        
+        println!();
+        println!("{:^60}", "════════════════════════════════════════".bright_blue());
+        println!("{:^60}", "Press any key to continue...");
+        
+        stdout().flush()?;
+        Ok(())
+    }
+    
     /// Draw death screen
-    pub fn draw_death_screen(&self, pet: &Pet, total_eaten: usize, play_time: Duration, max_size: usize) -> Result<()> {
+    pub fn draw_death_screen(&self, pet: &Pet, total_eaten: MiB, play_time: Duration, max_size: MiB) -> Result<()> {
         execute!(stdout(), terminal::Clear(terminal::ClearType::All), cursor::MoveTo(0, 0))?;
         
         println!();
@@ -303,13 +829,15 @@ impl Renderer {
         println!();
         
         println!("{:^60}", "Final Statistics:".bright_yellow().bold());
-        println!("{:^60}", format!("Total RAM Consumed: {} MB", total_eaten).bright_white());
-        println!("{:^60}", format!("Maximum Size Reached: {} MB", max_size).bright_white());
+        println!("{:^60}", format!("Total RAM Consumed: {}", total_eaten).bright_white());
+        println!("{:^60}", format!("Maximum Size Reached: {}", max_size).bright_white());
         println!("{:^60}", format!("Survived For: {}", format_duration(play_time)).bright_white());
         println!();
         
-        let cause = if pet.get_hunger() >= 100.0 {
+        let cause = if pet.has_starved() {
             "Died of starvation 💀"
+        } else if pet.died_of_old_age() {
+            "Passed away peacefully of old age 🕊️"
         } else {
             "Terminated by user 🔌"
         };
@@ -327,7 +855,15 @@ impl Renderer {
         let hunger = pet.get_hunger();
         let happiness = pet.get_happiness();
         
-        let comment = if hunger > 80.0 {
+        let cold = pet.get_symptoms().cold;
+        
+        let comment = if cold >= 0.75 {
+            vec!["F-f-frozen solid...", "I can't feel my pages!", "Is this... swap?"]
+        } else if cold >= 0.4 {
+            vec!["Brrr, half of me is on disk", "So c-cold...", "Page me back in, please"]
+        } else if cold >= COLD_FRACTION {
+            vec!["A bit chilly in here", "Feeling a draft...", "Did something get paged out?"]
+        } else if hunger > 80.0 {
             vec!["I'm starving!", "FEED ME!", "So... hungry..."]
         } else if hunger > 60.0 {
             vec!["Getting hungry...", "Food would be nice", "Rumble rumble"]
@@ -336,7 +872,7 @@ impl Renderer {
         } else if happiness < 30.0 {
             vec!["I'm sad...", "This isn't fun", "Sigh..."]
         } else {
-            vec!["*yawn*", "Hmm...", "RAM tastes good", "Hi there!"]
+            pet.get_phase().remarks().to_vec()
         };
         
         Some(comment[rand::random::<usize>() % comment.len()].to_string())
@@ -346,7 +882,6 @@ impl Renderer {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::Config;
     
     #[test]
     fn test_renderer_creation() {
@@ -0,0 +1,201 @@
+// src/household.rs
+// RAM Eating Pet Simulator - Household Leaderboard (RAM by User)
+
+use std::collections::HashMap;
+use sysinfo::Uid;
+
+use crate::pet::state::PetState;
+use crate::pet::{Mood, Personality};
+use crate::system::monitor::ProcessInfo;
+use crate::system::sampler::{FreshSnapshots, SystemSnapshot};
+use crate::system::units::MiB;
+
+/// Owner shown for processes whose user couldn't be read
+pub const UNKNOWN_USER: &str = "unknown";
+
+/// One user's pet, as of the last snapshot
+#[derive(Debug, Clone)]
+pub struct Member {
+    /// Login name of the owner, or the bare UID if it has no account
+    pub user: String,
+    /// The pet's name, the same every time for the same user
+    pub pet_name: String,
+    /// Total RSS of the user's processes
+    pub usage: MiB,
+    /// How many processes the user is running
+    pub processes: usize,
+    pub state: PetState,
+    pub mood: Mood,
+}
+
+/// A user's pet between snapshots
+struct Resident {
+    user: String,
+    pet_name: String,
+    usage: MiB,
+    previous: MiB,
+    processes: usize,
+}
+
+/// A household of pets, one per user, each as big as its owner's processes
+///
+/// Users are told apart by UID. Personalities and names are seeded from
+/// the username, so a user gets the same pet every time, on every machine.
+#[derive(Default)]
+pub struct Household {
+    residents: HashMap<Option<Uid>, Resident>,
+    fresh: FreshSnapshots,
+}
+
+impl Household {
+    /// An empty household; it fills up on the first snapshot with processes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Regroup the process table from a new snapshot
+    ///
+    /// Returns whether anything was updated; snapshots without the table,
+    /// or already seen, are skipped. Users with no processes left move out.
+    pub fn update(&mut self, snapshot: &SystemSnapshot) -> bool {
+        let table = match self.fresh.processes(snapshot) {
+            Some(table) => table,
+            None => return false,
+        };
+
+        let totals = tally(table);
+        self.residents.retain(|uid, _| totals.contains_key(uid));
+        for (uid, total) in totals {
+            let resident = self.residents.entry(uid).or_insert_with(|| Resident {
+                user: String::new(),
+                pet_name: String::new(),
+                usage: total.usage,
+                previous: total.usage,
+                processes: total.processes,
+            });
+            // An account created after the pet moved in gives it a proper name
+            if resident.user != total.user {
+                resident.pet_name = Personality::from_seed(&total.user).seeded_name(&total.user);
+                resident.user = total.user;
+            }
+            resident.previous = resident.usage;
+            resident.usage = total.usage;
+            resident.processes = total.processes;
+        }
+        true
+    }
+
+    /// Everyone in the household, biggest eater first
+    pub fn members(&self) -> Vec<Member> {
+        let mut members: Vec<Member> = self.residents.values()
+            .map(|resident| Member {
+                user: resident.user.clone(),
+                pet_name: resident.pet_name.clone(),
+                usage: resident.usage,
+                processes: resident.processes,
+                state: PetState::for_size(resident.usage),
+                // Growing pets are tucking in, shrinking ones want more
+                mood: match resident.usage.cmp(&resident.previous) {
+                    std::cmp::Ordering::Greater => Mood::Excited,
+                    std::cmp::Ordering::Less => Mood::Hungry,
+                    std::cmp::Ordering::Equal => Mood::Happy,
+                },
+            })
+            .collect();
+        members.sort_by(|a, b| b.usage.cmp(&a.usage).then_with(|| a.user.cmp(&b.user)));
+        members
+    }
+}
+
+/// One user's share of the process table
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tally {
+    /// Login name, the bare UID or [`UNKNOWN_USER`]
+    pub user: String,
+    /// Total RSS of the user's processes
+    pub usage: MiB,
+    pub processes: usize,
+}
+
+/// Total RSS and process count per UID (`None` for processes whose owner
+/// couldn't be read)
+pub fn tally(table: &[ProcessInfo]) -> HashMap<Option<Uid>, Tally> {
+    let mut totals: HashMap<Option<Uid>, Tally> = HashMap::new();
+    for process in table {
+        let total = totals.entry(process.uid.clone()).or_insert_with(|| Tally {
+            user: process.user.clone().unwrap_or_else(|| UNKNOWN_USER.to_string()),
+            usage: MiB(0),
+            processes: 0,
+        });
+        total.usage += process.memory_mb;
+        total.processes += 1;
+    }
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    /// Processes as (uid, user, MB); a uid of 0 means the owner is unknown
+    fn snapshot(secs: u64, table: &[(usize, &str, usize)]) -> SystemSnapshot {
+        let table = table.iter().enumerate()
+            .map(|(i, &(uid, user, mb))| ProcessInfo {
+                pid: i as u32 + 1,
+                name: format!("proc{}", i),
+                memory_mb: MiB(mb),
+                uid: (uid != 0).then(|| Uid::try_from(uid).unwrap()),
+                user: (uid != 0).then(|| user.to_string()),
+            })
+            .collect();
+        SystemSnapshot::fake(Instant::now() + Duration::from_secs(secs), table)
+    }
+
+    #[test]
+    fn test_ranks_users_by_total_rss() {
+        let mut household = Household::new();
+        assert!(household.update(&snapshot(1, &[(1000, "alice", 300), (1001, "bob", 700), (1000, "alice", 600), (0, "", 5)])));
+
+        let members = household.members();
+        let users: Vec<&str> = members.iter().map(|member| member.user.as_str()).collect();
+        assert_eq!(users, ["alice", "bob", UNKNOWN_USER]);
+        assert_eq!(members[0].usage, MiB(900));
+        assert_eq!(members[0].processes, 2);
+        assert_eq!(members[0].state, PetState::Chubby);
+
+        // Bob lets go of memory, alice logs out
+        household.update(&snapshot(2, &[(1001, "bob", 100)]));
+        let members = household.members();
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].mood, Mood::Hungry);
+        assert_eq!(members[0].state, PetState::Child);
+    }
+
+    #[test]
+    fn test_same_pet_for_same_user() {
+        let mut first = Household::new();
+        let mut second = Household::new();
+        first.update(&snapshot(1, &[(1000, "carol", 10)]));
+        second.update(&snapshot(2, &[(2000, "carol", 2000)]));
+        assert_eq!(first.members()[0].pet_name, second.members()[0].pet_name);
+    }
+
+    #[test]
+    fn test_users_are_told_apart_by_uid() {
+        let mut household = Household::new();
+
+        // Two accounts sharing a name are still two pets
+        household.update(&snapshot(1, &[(1000, "dave", 10), (1005, "dave", 20), (1007, "1007", 30)]));
+        assert_eq!(household.members().len(), 3);
+        assert_eq!(household.members()[0].user, "1007");
+
+        // Once the account shows up, the same pet takes its name
+        household.update(&snapshot(2, &[(1007, "erin", 30)]));
+        let members = household.members();
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].user, "erin");
+        assert_eq!(members[0].pet_name, Personality::from_seed("erin").seeded_name("erin"));
+        assert_eq!(members[0].mood, Mood::Happy);
+    }
+}
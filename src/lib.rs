@@ -13,10 +13,15 @@
 //! - Save/load game state
 //! - Multiple feeding modes
 
+pub mod adopt;
+pub mod cli;
 pub mod config;
+pub mod diet;
 pub mod game;
 pub mod graphics;
+pub mod household;
 pub mod pet;
+pub mod save;
 pub mod system;
 
 // Re-export commonly used types
@@ -11,27 +11,82 @@ use crossterm::{
 };
 use log::info;
 use std::io::stdout;
+use std::path::Path;
 use std::time::Duration;
 use tokio::time::interval;
 
-mod config;
-mod game;
-mod graphics;
-mod pet;
-mod system;
-
-use crate::game::Game;
+use ram_eating_pet_simulator::cli::{CliArgs, Command, SavesCommand};
+use ram_eating_pet_simulator::config::{feeding::FoodType, Config};
+use ram_eating_pet_simulator::diet::{self, DietPlan};
+use ram_eating_pet_simulator::graphics::renderer::Renderer;
+use ram_eating_pet_simulator::household::Household;
+use ram_eating_pet_simulator::save::SaveSlots;
+use ram_eating_pet_simulator::system::monitor::SystemMonitor;
+use ram_eating_pet_simulator::system::sampler::SystemSnapshot;
+use ram_eating_pet_simulator::system::scrubber::ScrubReport;
+use ram_eating_pet_simulator::Game;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize logger for debugging
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
     
+    // Work out what we're doing and with which settings before touching the terminal
+    let args = match CliArgs::from_env() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{} {:#}", "error:".bright_red().bold(), e);
+            std::process::exit(2);
+        }
+    };
+    
+    match args.command {
+        Command::Help => {
+            println!("{}", CliArgs::usage());
+            return Ok(());
+        }
+        Command::Version => {
+            println!("{} {}", ram_eating_pet_simulator::NAME, ram_eating_pet_simulator::VERSION);
+            return Ok(());
+        }
+        Command::Play | Command::Diet(_) | Command::Adopt(_) | Command::Household | Command::Saves(_) => {}
+    }
+    
+    let total_ram_mb = SystemSnapshot::capture().total.to_mib();
+    let config = match Config::resolve(args.config_path.as_deref(), &args.overrides, total_ram_mb) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{} {:#}", "Configuration error:".bright_red().bold(), e);
+            std::process::exit(2);
+        }
+    };
+    
+    if let Command::Diet(plan) = &args.command {
+        std::process::exit(run_diet(plan, &config));
+    }
+    if args.command == Command::Household {
+        return run_household(&config);
+    }
+    if let Command::Saves(command) = &args.command {
+        std::process::exit(run_saves(command, &config));
+    }
+    
     info!("Starting RAM Eating Pet Simulator...");
     
     // Print welcome message
     print_welcome();
     
+    let mut game = match &args.command {
+        Command::Adopt(target) => match Game::adopt(config, target) {
+            Ok(game) => game,
+            Err(e) => {
+                eprintln!("{} {:#}", "error:".bright_red().bold(), e);
+                std::process::exit(2);
+            }
+        },
+        _ => Game::with_config(config)?,
+    };
+    
     // Setup terminal with alternate screen to prevent flickering
     terminal::enable_raw_mode()?;
     let mut stdout = stdout();
@@ -42,8 +97,8 @@ async fn main() -> Result<()> {
         cursor::Hide
     )?;
     
-    // Create and run the game
-    let result = run_game().await;
+    // Run the game
+    let result = run_game(&mut game).await;
     
     // Cleanup terminal on exit
     terminal::disable_raw_mode()?;
@@ -55,12 +110,109 @@ async fn main() -> Result<()> {
     
     // Print goodbye message
     print_goodbye();
+    if let Some(report) = game.integrity_report() {
+        print_integrity_summary(&report);
+    }
     
     result
 }
 
-async fn run_game() -> Result<()> {
-    let mut game = Game::new()?;
+/// Follow a diet plan without the game UI, returning the exit status
+fn run_diet(path: &Path, config: &Config) -> i32 {
+    let diet = DietPlan::load(path).and_then(|plan| Ok((plan.compile()?, plan)));
+    let (diet, plan) = match diet {
+        Ok(diet) => diet,
+        Err(e) => {
+            eprintln!("{} {:#}", "Diet plan error:".bright_red().bold(), e);
+            return 2;
+        }
+    };
+    
+    match diet::run(&plan, &diet, config) {
+        Ok(report) => report.exit_code(),
+        Err(e) => {
+            eprintln!("{} {:#}", "error:".bright_red().bold(), e);
+            1
+        }
+    }
+}
+
+/// List or manage the save slots, returning the exit status
+fn run_saves(command: &SavesCommand, config: &Config) -> i32 {
+    let (slots, current) = SaveSlots::around(config.game.save_path.as_ref(), config.game.backups);
+    let done = match command {
+        SavesCommand::List => slots.list().map(|listed| {
+            if listed.is_empty() {
+                println!("No saves in {}", slots.dir().display());
+            }
+            for slot in listed {
+                let marker = if slot.name == current { "*" } else { " " };
+                match slot.summary {
+                    Ok(summary) => println!("{} {:16} {:20} {:8} {:>9}  {}  {}{}",
+                        marker,
+                        slot.name,
+                        summary.pet_name,
+                        summary.stage.name(),
+                        summary.size.to_string(),
+                        summary.saved_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"),
+                        if summary.alive { "alive" } else { "dead" },
+                        if summary.recovered { " (recovered from backup)" } else { "" },
+                    ),
+                    Err(problem) => println!("{} {:16} {}", marker, slot.name, format!("damaged: {}", problem).bright_red()),
+                }
+            }
+        }),
+        SavesCommand::Remove(slot) => slots.remove(slot)
+            .map(|files| println!("Deleted slot '{}' ({} file(s))", slot, files)),
+        SavesCommand::Rename { from, to } => slots.rename(from, to)
+            .map(|()| println!("Renamed '{}' to '{}'", from, to)),
+        SavesCommand::Copy { from, to } => slots.duplicate(from, to)
+            .map(|()| println!("Copied '{}' to '{}'", from, to)),
+    };
+    
+    match done {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("{} {:#}", "error:".bright_red().bold(), e);
+            1
+        }
+    }
+}
+
+/// Show the household leaderboard until the user quits; nothing is allocated
+fn run_household(config: &Config) -> Result<()> {
+    let mut monitor = SystemMonitor::with_interval(config.system.update_interval);
+    let mut renderer = Renderer::new(config.graphics.use_colors);
+    let mut household = Household::new();
+    
+    terminal::enable_raw_mode()?;
+    let mut stdout = stdout();
+    execute!(stdout, EnterAlternateScreen, terminal::Clear(ClearType::All), cursor::Hide)?;
+    
+    let result = (|| -> Result<()> {
+        loop {
+            monitor.update()?;
+            // Asking for the table keeps it coming in the snapshots
+            monitor.get_process_table();
+            household.update(monitor.snapshot());
+            renderer.draw_household(&household.members())?;
+            
+            if event::poll(Duration::from_millis(200))? {
+                if let Event::Key(key) = event::read()? {
+                    if matches!(key.code, KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc) {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    })();
+    
+    terminal::disable_raw_mode()?;
+    execute!(stdout, LeaveAlternateScreen, cursor::Show)?;
+    result
+}
+
+async fn run_game(game: &mut Game) -> Result<()> {
     // Reduced tick rate to prevent flickering
     let mut tick_interval = interval(Duration::from_millis(200));
     let mut last_update = std::time::Instant::now();
@@ -69,6 +221,20 @@ async fn run_game() -> Result<()> {
     game.render()?;
     
     loop {
+        // After loading a save, say what happened while we were away
+        if game.has_away_report() {
+            game.show_away_screen()?;
+            loop {
+                if event::poll(Duration::from_millis(100))? {
+                    if let Event::Key(_) = event::read()? {
+                        break;
+                    }
+                }
+            }
+            execute!(stdout(), terminal::Clear(ClearType::All))?;
+            game.render()?;
+        }
+        
         // Check if pet died FIRST before any rendering
         if game.is_pet_dead() {
             // Show death screen
@@ -88,7 +254,7 @@ async fn run_game() -> Result<()> {
         if event::poll(Duration::from_millis(50))? {
             if let Event::Key(key_event) = event::read()? {
                 // Check if we should exit
-                if !handle_input(&mut game, key_event).await? {
+                if !handle_input(game, key_event).await? {
                     return Ok(()); // Exit game
                 }
                 
@@ -117,6 +283,12 @@ async fn run_game() -> Result<()> {
 }
 
 async fn handle_input(game: &mut Game, key: KeyEvent) -> Result<bool> {
+    // The save screen takes every key until it's closed
+    if game.is_browsing_saves() {
+        game.browse_saves(key.code)?;
+        return Ok(true);
+    }
+    
     // If help is showing, only allow H to close it or Q to quit
     if game.is_help_showing() {
         match key.code {
@@ -138,19 +310,44 @@ async fn handle_input(game: &mut Game, key: KeyEvent) -> Result<bool> {
     match key.code {
         KeyCode::Char(' ') => {
             // Space bar - feed the pet
-            game.feed_pet(50).await?;
+            game.feed_pet(FoodType::MEAL).await?;
+        }
+        KeyCode::Char(digit @ '1'..='4') => {
+            // 1-4 - feed something from the menu (snack, meal, feast, gorge)
+            let food = FoodType::MENU[digit as usize - '1' as usize];
+            game.feed_pet(food).await?;
         }
         KeyCode::Char('f') | KeyCode::Char('F') => {
             // F - give favorite food
             game.feed_pet_favorite().await?;
         }
+        KeyCode::Char('w') | KeyCode::Char('W') => {
+            // W - warm up: bring swapped-out blocks back into RAM
+            game.warm_up();
+        }
+        KeyCode::Char('b') | KeyCode::Char('B') => {
+            // B - balloon mode: size the pet to whatever RAM is spare
+            game.toggle_balloon();
+        }
+        KeyCode::Tab => {
+            // TAB - switch between the stats and vitals panels
+            game.cycle_panel();
+        }
+        KeyCode::Char('c') | KeyCode::Char('C') => {
+            // C - stop the meal in progress
+            game.cancel_meal();
+        }
+        KeyCode::Char('m') | KeyCode::Char('M') => {
+            // M - switch food source for the next feedings
+            game.cycle_food_source();
+        }
         KeyCode::Char('s') | KeyCode::Char('S') => {
             // S - save game
             game.save_game()?;
         }
         KeyCode::Char('l') | KeyCode::Char('L') => {
-            // L - load game
-            game.load_game()?;
+            // L - open the save slots to load, rename, copy or delete
+            game.open_saves();
         }
         KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => {
             // Q or ESC - quit game
@@ -191,13 +388,27 @@ fn print_goodbye() {
     println!("{}", "═".repeat(50).bright_blue());
 }
 
+fn print_integrity_summary(report: &ScrubReport) {
+    println!(
+        "Memory integrity: {} checked in {} blocks ({} full passes)",
+        report.checked, report.blocks_checked, report.passes
+    );
+    if report.mismatches.is_empty() {
+        println!("{}", "No corruption found ✓".bright_green());
+        return;
+    }
+    
+    println!("{}", format!("{} corrupted block(s) found:", report.mismatches.len()).bright_red().bold());
+    for mismatch in &report.mismatches {
+        println!("  {}", mismatch);
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::*;
-    
     #[test]
     fn test_main_exists() {
-        // Basic test to ensure main compiles
-        assert!(true);
+        // Basic test to ensure main compiles and links against the library
+        assert!(!ram_eating_pet_simulator::VERSION.is_empty());
     }
 }
\ No newline at end of file
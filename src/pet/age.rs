@@ -0,0 +1,100 @@
+// src/pet/age.rs
+// RAM Eating Pet Simulator - Aging and Life Phases
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Share of its lifespan a pet spends as a youngster
+const YOUNGSTER_UNTIL: f64 = 0.2;
+/// Share of its lifespan after which a pet is an elder
+const ELDER_FROM: f64 = 0.7;
+
+/// How far through its life the pet is, whatever its size
+///
+/// Size gives the [`PetState`](super::state::PetState); the two together
+/// make e.g. a "Gigantic Youngster" or a "Baby Elder".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum LifePhase {
+    Youngster,
+    Adult,
+    Elder,
+}
+
+impl LifePhase {
+    /// The phase a pet of this age is in, given how long it lives
+    pub fn for_age(age: Duration, lifespan: Duration) -> Self {
+        let lived = age.as_secs_f64() / lifespan.as_secs_f64().max(1.0);
+        if lived < YOUNGSTER_UNTIL {
+            LifePhase::Youngster
+        } else if lived < ELDER_FROM {
+            LifePhase::Adult
+        } else {
+            LifePhase::Elder
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            LifePhase::Youngster => "Youngster",
+            LifePhase::Adult => "Adult",
+            LifePhase::Elder => "Elder",
+        }
+    }
+
+    /// How fast the pet digests compared to an adult
+    pub fn metabolism_factor(&self) -> f32 {
+        match self {
+            LifePhase::Youngster => 1.25, // Growing pets burn through RAM
+            LifePhase::Adult => 1.0,
+            LifePhase::Elder => 0.75,
+        }
+    }
+
+    /// Idle things a pet says at this time of life
+    pub fn remarks(&self) -> &'static [&'static str] {
+        match self {
+            LifePhase::Youngster => &[
+                "Is it snack time yet?",
+                "When I grow up I'll be a database!",
+                "Watch me allocate!",
+            ],
+            LifePhase::Adult => &["*yawn*", "Hmm...", "RAM tastes good", "Hi there!"],
+            LifePhase::Elder => &[
+                "Back in my day, 640 KB was plenty",
+                "My pages aren't what they used to be",
+                "I remember when swap was on floppies",
+            ],
+        }
+    }
+}
+
+/// "1st", "2nd", "3rd", "4th", ... "11th", "21st"
+pub fn ordinal(n: u32) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phase_for_age() {
+        let day = Duration::from_secs(86_400);
+        let lifespan = day * 100;
+        assert_eq!(LifePhase::for_age(Duration::ZERO, lifespan), LifePhase::Youngster);
+        assert_eq!(LifePhase::for_age(day * 20, lifespan), LifePhase::Adult);
+        assert_eq!(LifePhase::for_age(day * 70, lifespan), LifePhase::Elder);
+        assert_eq!(LifePhase::for_age(day * 200, lifespan), LifePhase::Elder);
+
+        assert_eq!(ordinal(1), "1st");
+        assert_eq!(ordinal(12), "12th");
+        assert_eq!(ordinal(22), "22nd");
+    }
+}
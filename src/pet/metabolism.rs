@@ -3,6 +3,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::system::units::MiB;
+
 /// Manages how the pet digests RAM over time
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Metabolism {
@@ -26,10 +28,12 @@ impl Metabolism {
     
     /// Process metabolism for a time delta
     /// Returns the amount of MB to digest
-    pub fn process(&mut self, current_size: usize, delta_time: f32) -> usize {
+    ///
+    /// `age_factor` speeds up or slows down digestion for the pet's time of life.
+    pub fn process(&mut self, current_size: MiB, delta_time: f32, age_factor: f32) -> MiB {
         // Don't digest if too small
-        if current_size < 10 {
-            return 0;
+        if current_size < MiB(10) {
+            return MiB(0);
         }
         
         // Accumulate digestion time
@@ -37,7 +41,7 @@ impl Metabolism {
         
         // Calculate effective rate based on size
         let size_modifier = self.calculate_size_modifier(current_size);
-        let effective_rate = self.base_rate * self.modifier * size_modifier;
+        let effective_rate = self.base_rate * self.modifier * size_modifier * age_factor;
         
         // Calculate how much to digest
         let to_digest = (effective_rate * self.digestion_timer) as usize;
@@ -45,16 +49,16 @@ impl Metabolism {
         if to_digest > 0 {
             // Reset timer, keeping remainder
             self.digestion_timer = self.digestion_timer.fract();
-            to_digest.min(current_size / 2) // Never digest more than half
+            MiB(to_digest.min(current_size.0 / 2)) // Never digest more than half
         } else {
-            0
+            MiB(0)
         }
     }
     
     /// Calculate metabolic modifier based on size
-    fn calculate_size_modifier(&self, size_mb: usize) -> f32 {
+    fn calculate_size_modifier(&self, size_mb: MiB) -> f32 {
         // Larger pets digest faster
-        match size_mb {
+        match size_mb.0 {
             0..=100 => 0.5,      // Baby metabolism - slow
             101..=300 => 0.8,    // Young metabolism
             301..=600 => 1.0,    // Normal metabolism
@@ -81,7 +85,7 @@ impl Metabolism {
     }
     
     /// Get current metabolic rate
-    pub fn get_rate(&self, size_mb: usize) -> f32 {
+    pub fn get_rate(&self, size_mb: MiB) -> f32 {
         self.base_rate * self.modifier * self.calculate_size_modifier(size_mb)
     }
 }
@@ -121,16 +125,21 @@ mod tests {
     #[test]
     fn test_metabolism_process() {
         let mut metabolism = Metabolism::new(10.0);
-        let digested = metabolism.process(100, 1.0);
-        assert!(digested > 0);
-        assert!(digested <= 50); // Should not digest more than half
+        let digested = metabolism.process(MiB(100), 1.0, 1.0);
+        assert!(digested > MiB(0));
+        assert!(digested <= MiB(50)); // Should not digest more than half
+        
+        // Young pets digest faster than old ones
+        let young = Metabolism::new(10.0).process(MiB(1000), 1.0, 1.25);
+        let old = Metabolism::new(10.0).process(MiB(1000), 1.0, 0.75);
+        assert!(young > old);
     }
     
     #[test]
     fn test_size_affects_metabolism() {
         let metabolism = Metabolism::new(1.0);
-        let small_rate = metabolism.get_rate(50);
-        let large_rate = metabolism.get_rate(1500);
+        let small_rate = metabolism.get_rate(MiB(50));
+        let large_rate = metabolism.get_rate(MiB(1500));
         assert!(large_rate > small_rate);
     }
 }
\ No newline at end of file
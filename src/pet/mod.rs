@@ -1,28 +1,125 @@
 // src/pet/mod.rs
 // RAM Eating Pet Simulator - Pet Module
 
+pub mod age;
 pub mod metabolism;
+pub mod offline;
 pub mod personality;
 pub mod state;
 
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::time::Instant;
+use std::time::Duration;
 
-use crate::config::Config;
+use crate::config::{Config, Difficulty};
+use crate::system::food::FoodContent;
+use crate::system::units::MiB;
 // Make Personality and Mood public by re-exporting
-pub use personality::{Personality, Mood};
+pub use age::LifePhase;
+pub use offline::{AwayReport, OfflineRules};
+pub use personality::{Personality, Mood, Tastes};
 use state::PetState;
 use metabolism::Metabolism;
 // Re-export for public use
 
+/// How much happiness is lost per second (per unit of `happiness_decay`) while hungry
+const HUNGRY_SADNESS_FACTOR: f32 = 3.0;
+
+/// Difficulty-dependent tuning the pet was born with
+///
+/// Stored with the pet so a saved pet keeps the difficulty it was created
+/// with. Thresholds derive from `critical_hunger`: the pet gets sad 10
+/// points before it, and starves to death 20 points after it (capped at
+/// 100), so Nightmare's lower critical level kills sooner.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PetTuning {
+    /// Difficulty the pet was created on
+    pub difficulty: Difficulty,
+    /// Hunger gained per second
+    pub hunger_rate: f32,
+    /// Hunger level at which the pet is in danger
+    pub critical_hunger: f32,
+    /// Happiness decay rate while hungry
+    pub happiness_decay: f32,
+    /// Days the pet lives before dying of old age
+    pub lifespan_days: f32,
+}
+
+impl PetTuning {
+    /// Take tuning from the (difficulty-adjusted) config
+    pub fn from_config(config: &Config) -> Self {
+        PetTuning {
+            difficulty: config.game.difficulty,
+            hunger_rate: config.pet.hunger_rate,
+            critical_hunger: config.pet.critical_hunger,
+            happiness_decay: config.pet.happiness_decay,
+            lifespan_days: config.pet.lifespan_days,
+        }
+    }
+    
+    /// How long the pet lives
+    pub fn lifespan(&self) -> Duration {
+        Duration::from_secs_f32(self.lifespan_days.max(0.0) * 86_400.0)
+    }
+    
+    /// Hunger above which the pet starts losing happiness
+    pub fn sadness_threshold(&self) -> f32 {
+        (self.critical_hunger - 10.0).max(0.0)
+    }
+    
+    /// Hunger at which the pet dies of starvation
+    pub fn starvation_threshold(&self) -> f32 {
+        (self.critical_hunger + 20.0).min(100.0)
+    }
+}
+
+impl Default for PetTuning {
+    fn default() -> Self {
+        Self::from_config(&Config::default())
+    }
+}
+
+/// PSI "some" percentage at which the pet feels sluggish
+pub const SLUGGISH_PRESSURE: f32 = 10.0;
+
+/// Share of the pet's body swapped out at which it feels cold
+pub const COLD_FRACTION: f32 = 0.1;
+
+/// Share of its recent size a balloon pet has to give back to feel squeezed
+pub const SQUEEZED_FRACTION: f32 = 0.25;
+
+/// Major page faults per second that give the pet heartburn
+pub const HEARTBURN_FAULT_RATE: f32 = 20.0;
+
+/// Happiness gained from food the pet loves (or lost to food it hates)
+const TASTE_HAPPINESS: f32 = 15.0;
+
+/// Conditions the pet picks up from the machine it lives on
+///
+/// These aren't saved; they're re-read from the system every update.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Symptoms {
+    /// Share of time tasks stalled waiting for memory (PSI some avg10, %)
+    pub pressure: f32,
+    /// Share of the pet's body that's been swapped out (0.0 - 1.0)
+    pub cold: f32,
+    /// Major page faults per second
+    pub major_fault_rate: f32,
+    /// Whether the integrity scrubber has found any of the pet's memory corrupted
+    pub poisoned: bool,
+    /// How far a balloon pet has been squeezed below its recent size (0.0 - 1.0)
+    pub squeeze: f32,
+}
+
 /// The main Pet structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Pet {
     /// Pet's name
     pub name: String,
     /// Current size in MB
-    size_mb: usize,
+    size_mb: MiB,
     /// Pet's personality traits
     personality: Personality,
     /// Pet's current state
@@ -37,9 +134,17 @@ pub struct Pet {
     happiness: f32,
     /// Is the pet alive?
     alive: bool,
-    /// Birth time
+    /// Tuning from the difficulty the pet was created with
+    #[serde(default)]
+    tuning: PetTuning,
+    /// When the pet was born, in wall-clock time so it survives saving
+    born_at: DateTime<Utc>,
+    /// When the pet died, if it has
+    #[serde(default)]
+    died_at: Option<DateTime<Utc>>,
+    /// What the system is currently doing to the pet
     #[serde(skip)]
-    birth_time: Option<Instant>,
+    symptoms: Symptoms,
 }
 
 impl Pet {
@@ -58,19 +163,22 @@ impl Pet {
             hunger: 30.0,
             happiness: 80.0,
             alive: true,
-            birth_time: Some(Instant::now()),
+            tuning: PetTuning::from_config(config),
+            born_at: Utc::now(),
+            died_at: None,
+            symptoms: Symptoms::default(),
         })
     }
     
     /// Feed the pet (consume RAM)
-    pub fn eat(&mut self, amount_mb: usize) -> Result<()> {
+    pub fn eat(&mut self, amount_mb: MiB) -> Result<()> {
         if !self.alive {
             return Ok(());
         }
         
         self.size_mb += amount_mb;
-        self.hunger = (self.hunger - (amount_mb as f32 * 2.0)).max(0.0);
-        self.happiness = (self.happiness + (amount_mb as f32 * 0.5)).min(100.0);
+        self.hunger = (self.hunger - (amount_mb.0 as f32 * 2.0)).max(0.0);
+        self.happiness = (self.happiness + (amount_mb.0 as f32 * 0.5)).min(100.0);
         
         // Update state based on new size
         self.update_state();
@@ -83,27 +191,41 @@ impl Pet {
     
     /// Process metabolism (digest RAM over time)
     pub fn metabolize(&mut self, delta_time: f32) -> Result<()> {
+        self.metabolize_at(delta_time, Utc::now())
+    }
+    
+    /// Process metabolism as of a point in time, which sets the pet's age
+    fn metabolize_at(&mut self, delta_time: f32, now: DateTime<Utc>) -> Result<()> {
         if !self.alive {
             return Ok(());
         }
         
-        // Digest some RAM
-        let digested = self.metabolism.process(self.size_mb, delta_time);
-        if digested > 0 {
+        // Everything runs out eventually
+        if self.age_at(now) >= self.tuning.lifespan() {
+            self.die_at(now);
+            return Ok(());
+        }
+        
+        // Digest some RAM, faster when young and slower when old
+        let factor = self.phase_at(now).metabolism_factor();
+        let digested = self.metabolism.process(self.size_mb, delta_time, factor);
+        if !digested.is_zero() {
             self.size_mb = self.size_mb.saturating_sub(digested);
+            self.update_state();
         }
         
         // Increase hunger over time
-        self.hunger = (self.hunger + delta_time * 2.0).min(100.0);
+        self.hunger = (self.hunger + delta_time * self.tuning.hunger_rate).min(100.0);
         
         // Decrease happiness if too hungry
-        if self.hunger > 70.0 {
-            self.happiness = (self.happiness - delta_time * 3.0).max(0.0);
+        if self.hunger > self.tuning.sadness_threshold() {
+            let decay = self.tuning.happiness_decay * HUNGRY_SADNESS_FACTOR;
+            self.happiness = (self.happiness - delta_time * decay).max(0.0);
         }
         
         // Check if pet dies from starvation
-        if self.hunger >= 100.0 {
-            self.alive = false;
+        if self.has_starved() {
+            self.die_at(now);
         }
         
         Ok(())
@@ -120,9 +242,17 @@ impl Pet {
             return Mood::Dead;
         }
         
+        let starving = self.tuning.starvation_threshold() - 10.0;
+        let hungry = self.tuning.sadness_threshold();
+        
         match (self.hunger, self.happiness) {
-            (h, _) if h > 90.0 => Mood::Starving,
-            (h, _) if h > 70.0 => Mood::Hungry,
+            (h, _) if h > starving => Mood::Starving,
+            _ if self.symptoms.poisoned => Mood::Poisoned,
+            _ if self.symptoms.major_fault_rate >= HEARTBURN_FAULT_RATE => Mood::Heartburn,
+            _ if self.symptoms.cold >= COLD_FRACTION => Mood::Cold,
+            _ if self.symptoms.squeeze >= SQUEEZED_FRACTION => Mood::Squeezed,
+            _ if self.symptoms.pressure >= SLUGGISH_PRESSURE => Mood::Sluggish,
+            (h, _) if h > hungry => Mood::Hungry,
             (_, hp) if hp < 20.0 => Mood::Sad,
             (_, hp) if hp > 80.0 => Mood::Excited,
             (h, hp) if h < 30.0 && hp > 60.0 => Mood::Happy,
@@ -132,16 +262,7 @@ impl Pet {
     
     /// Update state based on size
     fn update_state(&mut self) {
-        self.state = match self.size_mb {
-            0..=50 => PetState::Baby,
-            51..=150 => PetState::Child,
-            151..=300 => PetState::Teen,
-            301..=500 => PetState::Adult,
-            501..=1000 => PetState::Chubby,
-            1001..=1500 => PetState::Fat,
-            1501..=2000 => PetState::Huge,
-            _ => PetState::Gigantic,
-        };
+        self.state = PetState::for_size(self.size_mb);
     }
     
     /// Get pet's reaction to feeding
@@ -150,33 +271,146 @@ impl Pet {
     }
     
     /// Get favorite food size based on personality
-    pub fn get_favorite_food_size(&self) -> usize {
+    pub fn get_favorite_food_size(&self) -> MiB {
         self.personality.get_favorite_food_size()
     }
     
+    /// Food content the pet likes best
+    pub fn get_favorite_food_content(&self) -> FoodContent {
+        self.personality.get_tastes().favorite()
+    }
+    
+    /// Taste what a meal was filled with, returning how much the pet liked
+    /// it (-1.0 to 1.0)
+    pub fn taste(&mut self, content: FoodContent) -> f32 {
+        if !self.alive {
+            return 0.0;
+        }
+        
+        let liking = self.personality.get_tastes().liking(content);
+        self.happiness = (self.happiness + liking * TASTE_HAPPINESS).clamp(0.0, 100.0);
+        self.mood = self.calculate_mood();
+        liking
+    }
+    
     /// Boost happiness (for favorite food)
     pub fn boost_happiness(&mut self) {
         self.happiness = (self.happiness + 20.0).min(100.0);
     }
     
+    /// Shrink the pet to a given size (e.g. when RAM couldn't be kept)
+    pub fn shrink_to(&mut self, size_mb: MiB) {
+        if size_mb < self.size_mb {
+            self.size_mb = size_mb;
+            self.update_state();
+        }
+    }
+    
+    /// Grow the pet to a given size without feeding it (e.g. in balloon mode)
+    pub fn grow_to(&mut self, size_mb: MiB) {
+        if self.alive && size_mb > self.size_mb {
+            self.size_mb = size_mb;
+            self.update_state();
+        }
+    }
+    
+    /// Take on another process's size and hunger (when adopted)
+    pub fn mirror(&mut self, size_mb: MiB, hunger: f32) {
+        if !self.alive {
+            return;
+        }
+        
+        self.size_mb = size_mb;
+        self.hunger = hunger.clamp(0.0, 100.0);
+        self.update_state();
+    }
+    
+    /// Throw up down to a given size (when the system needs RAM back)
+    ///
+    /// Unlike [`Pet::shrink_to`] this is unpleasant: the pet loses
+    /// happiness and gets hungry again.
+    pub fn vomit(&mut self, size_mb: MiB) {
+        if !self.alive || size_mb >= self.size_mb {
+            return;
+        }
+        
+        let lost = self.size_mb - size_mb;
+        self.shrink_to(size_mb);
+        self.hunger = (self.hunger + lost.0 as f32 * 0.5).min(100.0);
+        self.happiness = (self.happiness - 15.0).max(0.0);
+        self.mood = self.calculate_mood();
+    }
+    
+    /// Update what the system is doing to the pet
+    pub fn set_symptoms(&mut self, symptoms: Symptoms) {
+        self.symptoms = symptoms;
+    }
+    
     /// Kill the pet
     pub fn kill(&mut self) {
+        self.die_at(Utc::now());
+    }
+    
+    fn die_at(&mut self, at: DateTime<Utc>) {
         self.alive = false;
         self.mood = Mood::Dead;
+        self.died_at = Some(at);
     }
     
     // Getters
-    pub fn get_size_mb(&self) -> usize { self.size_mb }
+    pub fn get_size_mb(&self) -> MiB { self.size_mb }
     pub fn get_state(&self) -> &PetState { &self.state }
     pub fn get_mood(&self) -> &Mood { &self.mood }
     pub fn get_hunger(&self) -> f32 { self.hunger }
     pub fn get_happiness(&self) -> f32 { self.happiness }
     pub fn is_dead(&self) -> bool { !self.alive }
+    pub fn has_starved(&self) -> bool { self.hunger >= self.tuning.starvation_threshold() }
+    pub fn get_tuning(&self) -> &PetTuning { &self.tuning }
+    pub fn get_symptoms(&self) -> &Symptoms { &self.symptoms }
     pub fn get_personality(&self) -> &Personality { &self.personality }
+    pub fn get_born_at(&self) -> DateTime<Utc> { self.born_at }
+    /// How long the pet has lived, or did live if it's dead
+    pub fn get_age(&self) -> Duration { self.age_at(self.died_at.unwrap_or_else(Utc::now)) }
+    pub fn get_phase(&self) -> LifePhase { self.phase_at(self.died_at.unwrap_or_else(Utc::now)) }
+    
+    /// Size and age together, e.g. "Gigantic Youngster"
+    pub fn get_life_stage(&self) -> String {
+        format!("{} {}", self.state.name(), self.get_phase().name())
+    }
+    
+    /// Whether the pet died of old age rather than hunger or the off switch
+    pub fn died_of_old_age(&self) -> bool {
+        self.died_at.is_some_and(|at| !self.has_starved() && self.age_at(at) >= self.tuning.lifespan())
+    }
+    
+    /// Whole years the pet had lived at a point in time (its birthdays so far)
+    pub fn years_old_at(&self, at: DateTime<Utc>) -> u32 {
+        at.years_since(self.born_at).unwrap_or(0)
+    }
+    
+    fn age_at(&self, at: DateTime<Utc>) -> Duration {
+        (at - self.born_at).to_std().unwrap_or_default()
+    }
+    
+    fn phase_at(&self, at: DateTime<Utc>) -> LifePhase {
+        LifePhase::for_age(self.age_at(at), self.tuning.lifespan())
+    }
     
     /// Get ASCII art for current state
+    ///
+    /// A cold pet frosts over from the top, one line per share of its body
+    /// that's been swapped out.
     pub fn get_ascii_art(&self) -> Vec<String> {
-        self.state.get_ascii_art(&self.mood)
+        let art = self.state.get_ascii_art(&self.mood);
+        if self.symptoms.cold < COLD_FRACTION {
+            return art;
+        }
+        
+        let frosted = (self.symptoms.cold.min(1.0) * art.len() as f32).ceil() as usize;
+        art.into_iter()
+            .enumerate()
+            .map(|(i, line)| if i < frosted { format!("❄{}❄", line) } else { format!(" {} ", line) })
+            .collect()
     }
     
     /// Get color for current mood
@@ -201,7 +435,133 @@ mod tests {
         let config = Config::default();
         let mut pet = Pet::new(&config).unwrap();
         let initial_size = pet.get_size_mb();
-        pet.eat(50).unwrap();
-        assert_eq!(pet.get_size_mb(), initial_size + 50);
+        pet.eat(MiB(50)).unwrap();
+        assert_eq!(pet.get_size_mb(), initial_size + MiB(50));
+    }
+    
+    #[test]
+    fn test_difficulty_changes_hunger() {
+        let mut easy = Pet::new(&Config::default().with_difficulty(Difficulty::Easy)).unwrap();
+        let mut hard = Pet::new(&Config::default().with_difficulty(Difficulty::Hard)).unwrap();
+        easy.metabolize(5.0).unwrap();
+        hard.metabolize(5.0).unwrap();
+        assert!(hard.get_hunger() > easy.get_hunger());
+        assert_eq!(hard.get_tuning().difficulty, Difficulty::Hard);
+    }
+    
+    #[test]
+    fn test_nightmare_starves_sooner() {
+        let mut pet = Pet::new(&Config::default().with_difficulty(Difficulty::Nightmare)).unwrap();
+        pet.hunger = 85.0;
+        pet.metabolize(0.1).unwrap();
+        assert!(pet.is_dead());
+        assert!(pet.has_starved());
+        
+        let mut pet = Pet::new(&Config::default()).unwrap();
+        pet.hunger = 85.0;
+        pet.metabolize(0.1).unwrap();
+        assert!(!pet.is_dead());
+    }
+    
+    #[test]
+    fn test_tuning_survives_save() {
+        let pet = Pet::new(&Config::default().with_difficulty(Difficulty::Nightmare)).unwrap();
+        let json = serde_json::to_string(&pet).unwrap();
+        let loaded: Pet = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.get_tuning().difficulty, Difficulty::Nightmare);
+        assert_eq!(loaded.get_tuning().critical_hunger, 60.0);
+    }
+    
+    #[test]
+    fn test_cause_of_death_is_kept() {
+        let mut pet = Pet::new(&Config::default()).unwrap();
+        pet.kill();
+        let json = serde_json::to_string(&pet).unwrap();
+        let mut loaded: Pet = serde_json::from_str(&json).unwrap();
+        
+        // Reloaded long after its lifespan, it still didn't die of old age
+        let lifespan = chrono::TimeDelta::from_std(loaded.get_tuning().lifespan()).unwrap();
+        loaded.born_at -= lifespan;
+        loaded.died_at = loaded.died_at.map(|at| at - lifespan);
+        assert!(loaded.died_at.is_some());
+        assert!(!loaded.died_of_old_age());
+    }
+    
+    #[test]
+    fn test_pet_shrink_to() {
+        let config = Config::default();
+        let mut pet = Pet::new(&config).unwrap();
+        pet.eat(MiB(200)).unwrap();
+        pet.shrink_to(MiB(40));
+        assert_eq!(pet.get_size_mb(), MiB(40));
+        assert_eq!(*pet.get_state(), PetState::Baby);
+        
+        // Never grows the pet
+        pet.shrink_to(MiB(400));
+        assert_eq!(pet.get_size_mb(), MiB(40));
+    }
+    
+    #[test]
+    fn test_system_symptoms_change_mood() {
+        let mut pet = Pet::new(&Config::default()).unwrap();
+        pet.set_symptoms(Symptoms { pressure: 25.0, ..Default::default() });
+        pet.update_mood(0.0);
+        assert_eq!(*pet.get_mood(), Mood::Sluggish);
+        
+        // Swapping and thrashing are felt more than a general slowdown
+        pet.set_symptoms(Symptoms { pressure: 25.0, cold: 0.5, ..Default::default() });
+        pet.update_mood(0.0);
+        assert_eq!(*pet.get_mood(), Mood::Cold);
+        
+        pet.set_symptoms(Symptoms { cold: 0.5, major_fault_rate: 100.0, ..Default::default() });
+        pet.update_mood(0.0);
+        assert_eq!(*pet.get_mood(), Mood::Heartburn);
+        
+        pet.set_symptoms(Symptoms { pressure: 25.0, squeeze: 0.5, ..Default::default() });
+        pet.update_mood(0.0);
+        assert_eq!(*pet.get_mood(), Mood::Squeezed);
+        
+        // Bad RAM trumps everything short of starving
+        pet.set_symptoms(Symptoms { major_fault_rate: 100.0, poisoned: true, ..Default::default() });
+        pet.update_mood(0.0);
+        assert_eq!(*pet.get_mood(), Mood::Poisoned);
+        
+        pet.set_symptoms(Symptoms::default());
+        pet.update_mood(0.0);
+        assert_ne!(*pet.get_mood(), Mood::Sluggish);
+    }
+    
+    #[test]
+    fn test_cold_pet_frosts_over() {
+        let mut pet = Pet::new(&Config::default()).unwrap();
+        let warm = pet.get_ascii_art();
+        
+        pet.set_symptoms(Symptoms { cold: 0.5, ..Default::default() });
+        let cold = pet.get_ascii_art();
+        let frosted = cold.iter().filter(|line| line.starts_with('❄')).count();
+        assert_eq!(frosted, warm.len().div_ceil(2));
+    }
+    
+    #[test]
+    fn test_pet_vomit() {
+        let mut pet = Pet::new(&Config::default()).unwrap();
+        pet.eat(MiB(100)).unwrap();
+        let (hunger, happiness) = (pet.get_hunger(), pet.get_happiness());
+        
+        pet.vomit(MiB(60));
+        assert_eq!(pet.get_size_mb(), MiB(60));
+        assert!(pet.get_hunger() > hunger);
+        assert!(pet.get_happiness() < happiness);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_taste_affects_happiness() {
+        let mut pet = Pet::new(&Config::default()).unwrap();
+        pet.happiness = 50.0;
+        
+        let favorite = pet.get_favorite_food_content();
+        let liking = pet.taste(favorite);
+        assert!(FoodContent::ALL.iter().all(|c| pet.get_personality().get_tastes().liking(*c) <= liking));
+        assert_eq!(pet.get_happiness(), 50.0 + liking * TASTE_HAPPINESS);
+    }
+}
@@ -0,0 +1,179 @@
+// src/pet/offline.rs
+// RAM Eating Pet Simulator - Offline Progression
+
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+use super::state::PetState;
+use super::Pet;
+use crate::config::{feeding, Config};
+use crate::system::units::MiB;
+
+/// Shortest step the catch-up simulation takes
+const MIN_STEP: Duration = Duration::from_secs(5);
+/// Most steps the catch-up simulation takes, however long we were away
+const MAX_STEPS: u32 = 100_000;
+
+/// How time away from the game is simulated
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OfflineRules {
+    /// Simulate at most this much of the time away
+    pub cap: Option<Duration>,
+    /// A pet sitter feeds the pet a snack whenever it gets hungry, up to this size
+    pub sitter: Option<MiB>,
+}
+
+impl OfflineRules {
+    /// The rules `game.offline_cap_mins` and `game.pet_sitter` ask for
+    pub fn from_config(config: &Config) -> Self {
+        OfflineRules {
+            cap: (config.game.offline_cap_mins > 0)
+                .then(|| Duration::from_secs(config.game.offline_cap_mins * 60)),
+            sitter: config.game.pet_sitter.then_some(config.pet.max_size_mb),
+        }
+    }
+}
+
+/// What happened to the pet while the game was closed
+#[derive(Debug, Clone)]
+pub struct AwayReport {
+    /// How long the game was closed
+    pub away: Duration,
+    /// How much of that was simulated
+    pub simulated: Duration,
+    pub size_before: MiB,
+    pub size_after: MiB,
+    pub stage_before: PetState,
+    pub stage_after: PetState,
+    pub hunger_before: f32,
+    pub hunger_after: f32,
+    pub happiness_before: f32,
+    pub happiness_after: f32,
+    /// Snacks the pet sitter gave it
+    pub snacks: usize,
+    /// How long after we left it died (of hunger or old age), if it did
+    pub died_after: Option<Duration>,
+}
+
+impl Pet {
+    /// Live through the time since the game was closed at `left`, in coarse steps
+    ///
+    /// Hunger, happiness, metabolism and aging run as they would in game,
+    /// so a pet left alone long enough starves, and an old one may not
+    /// see us again.
+    pub fn catch_up(&mut self, left: DateTime<Utc>, rules: OfflineRules) -> AwayReport {
+        let away = (Utc::now() - left).to_std().unwrap_or_default();
+        let simulated = rules.cap.map_or(away, |cap| away.min(cap));
+        let mut report = AwayReport {
+            away,
+            simulated,
+            size_before: self.size_mb,
+            size_after: self.size_mb,
+            stage_before: self.state,
+            stage_after: self.state,
+            hunger_before: self.hunger,
+            hunger_after: self.hunger,
+            happiness_before: self.happiness,
+            happiness_after: self.happiness,
+            snacks: 0,
+            died_after: None,
+        };
+
+        let step = MIN_STEP.max(simulated / MAX_STEPS);
+        let mut elapsed = Duration::ZERO;
+        while self.alive && elapsed < simulated {
+            let delta = step.min(simulated - elapsed);
+            elapsed += delta;
+
+            // The sitter feeds the pet before it would get hungry during the step
+            let coming = delta.as_secs_f32() * self.tuning.hunger_rate;
+            let sad = self.tuning.sadness_threshold();
+            if let Some(limit) = rules.sitter.filter(|_| self.hunger + coming > sad) {
+                let _ = self.eat(feeding::SNACK.min(limit.saturating_sub(self.size_mb)));
+                // Even when it's too big for more, it's kept from going hungry
+                self.hunger = self.hunger.min((sad - coming).max(0.0));
+                report.snacks += 1;
+            }
+
+            // Metabolism only fails on a dead pet, which ends the loop anyway
+            let now = left + chrono::TimeDelta::from_std(elapsed).unwrap_or_default();
+            let _ = self.metabolize_at(delta.as_secs_f32(), now);
+            if !self.alive {
+                report.died_after = Some(elapsed);
+            }
+        }
+        self.mood = self.calculate_mood();
+
+        report.size_after = self.size_mb;
+        report.stage_after = self.state;
+        report.hunger_after = self.hunger;
+        report.happiness_after = self.happiness;
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hours_ago(hours: i64) -> DateTime<Utc> {
+        Utc::now() - chrono::TimeDelta::hours(hours)
+    }
+
+    /// A pet born just before we left it `hours` ago
+    fn left_alone(config: &Config, hours: i64) -> Pet {
+        let mut pet = Pet::new(config).unwrap();
+        pet.born_at = hours_ago(hours);
+        pet
+    }
+
+    #[test]
+    fn test_left_alone_it_starves() {
+        let mut pet = left_alone(&Config::default(), 8);
+        let report = pet.catch_up(hours_ago(8), OfflineRules::default());
+
+        assert!(pet.is_dead() && !pet.died_of_old_age());
+        assert!(report.simulated >= Duration::from_secs(8 * 3600));
+        assert!(report.died_after.unwrap() < Duration::from_secs(120));
+        assert!(report.hunger_after > report.hunger_before);
+
+        // A short cap keeps it alive
+        let mut pet = left_alone(&Config::default(), 8);
+        let report = pet.catch_up(hours_ago(8), OfflineRules {
+            cap: Some(Duration::from_secs(10)),
+            sitter: None,
+        });
+        assert!(!pet.is_dead() && report.died_after.is_none());
+        assert_eq!(report.simulated, Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_default_config_survives_a_day_away() {
+        let config = Config::default();
+        let mut pet = left_alone(&config, 24);
+        pet.catch_up(hours_ago(24), OfflineRules::from_config(&config));
+        assert!(!pet.is_dead());
+    }
+
+    #[test]
+    fn test_pet_sitter_keeps_it_alive() {
+        let mut pet = left_alone(&Config::default(), 24);
+        let report = pet.catch_up(hours_ago(24), OfflineRules {
+            cap: None,
+            sitter: Some(MiB(200)),
+        });
+
+        assert!(!pet.is_dead());
+        assert!(report.snacks > 0);
+        assert!(pet.get_size_mb() <= MiB(200));
+
+        // It can't keep an old pet going forever, though
+        let mut config = Config::default();
+        config.pet.lifespan_days = 0.5;
+        let mut pet = left_alone(&config, 24);
+        let report = pet.catch_up(hours_ago(24), OfflineRules { cap: None, sitter: Some(MiB(200)) });
+        assert!(pet.died_of_old_age());
+        let died = report.died_after.unwrap().as_secs();
+        assert!((12 * 3600..12 * 3600 + 60).contains(&died), "died after {} s", died);
+    }
+}
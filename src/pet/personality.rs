@@ -1,9 +1,13 @@
 // src/pet/personality.rs
 // RAM Eating Pet Simulator - Personality System
 
-use rand::{thread_rng, Rng};
+use rand::rngs::StdRng;
+use rand::{thread_rng, Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
 
+use crate::system::food::FoodContent;
+use crate::system::units::{Bytes, MiB};
+
 /// Pet personality traits
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Personality {
@@ -19,6 +23,9 @@ pub struct Personality {
     food_preference: FoodPreference,
     /// Pet's unique quirks
     quirks: Vec<Quirk>,
+    /// How much it likes each kind of food content
+    #[serde(default)]
+    tastes: Tastes,
 }
 
 /// Food preferences
@@ -30,6 +37,38 @@ pub enum FoodPreference {
     Chaotic,             // Random preferences
 }
 
+/// How much the pet likes each kind of food content
+///
+/// Each liking runs from -1.0 (hates it) to 1.0 (loves it). Pets saved
+/// before tastes existed are indifferent to everything.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Tastes {
+    pub zeros: f32,
+    pub pattern: f32,
+    pub text: f32,
+    pub random: f32,
+}
+
+impl Tastes {
+    /// How much the pet likes a kind of content
+    pub fn liking(&self, content: FoodContent) -> f32 {
+        match content {
+            FoodContent::Zeros => self.zeros,
+            FoodContent::Pattern => self.pattern,
+            FoodContent::Text => self.text,
+            FoodContent::Random => self.random,
+        }
+    }
+    
+    /// The content the pet likes best
+    pub fn favorite(&self) -> FoodContent {
+        FoodContent::ALL.into_iter()
+            .max_by(|a, b| self.liking(*a).total_cmp(&self.liking(*b)))
+            .unwrap_or_default()
+    }
+}
+
 /// Unique quirks
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Quirk {
@@ -54,14 +93,26 @@ pub enum Mood {
     Sad,
     Angry,
     Sleepy,
+    Sluggish,
+    Cold,
+    Heartburn,
+    Poisoned,
+    Squeezed,
     Dead,
 }
 
 impl Personality {
     /// Generate a random personality
     pub fn generate_random() -> Self {
-        let mut rng = thread_rng();
-        
+        Self::generate_with(&mut thread_rng())
+    }
+    
+    /// Generate the same personality every time for the same seed (e.g. a username)
+    pub fn from_seed(seed: &str) -> Self {
+        Self::generate_with(&mut seeded_rng(seed))
+    }
+    
+    fn generate_with(rng: &mut impl Rng) -> Self {
         // Generate random traits
         let sass_level = rng.gen_range(0.0..1.0);
         let attention_need = rng.gen_range(0.0..1.0);
@@ -95,6 +146,16 @@ impl Personality {
             }
         }
         
+        // Random tastes, with chaos lovers leaning towards noise and nerds
+        // towards text
+        let nerd: f32 = if quirks.iter().any(|q| matches!(q, Quirk::Nerd)) { 0.5 } else { 0.0 };
+        let tastes = Tastes {
+            zeros: rng.gen_range(-1.0..1.0),
+            pattern: rng.gen_range(-1.0..1.0),
+            text: (rng.gen_range(-1.0..1.0) + nerd).min(1.0),
+            random: (rng.gen_range(-1.0f32..1.0) + chaos_affinity * 0.5).min(1.0),
+        };
+        
         Personality {
             sass_level,
             attention_need,
@@ -102,13 +163,21 @@ impl Personality {
             drama_level,
             food_preference,
             quirks,
+            tastes,
         }
     }
     
     /// Generate a name based on personality
     pub fn generate_name(&self) -> String {
-        let mut rng = thread_rng();
-        
+        self.name_with(&mut thread_rng())
+    }
+    
+    /// Generate the same name every time for the same seed
+    pub fn seeded_name(&self, seed: &str) -> String {
+        self.name_with(&mut seeded_rng(seed))
+    }
+    
+    fn name_with(&self, rng: &mut impl Rng) -> String {
         let prefix = if self.chaos_affinity > 0.7 {
             ["Chaos", "Havoc", "Mayhem", "Riot", "Anarchy"]
         } else if self.sass_level > 0.7 {
@@ -164,6 +233,21 @@ impl Personality {
             Mood::Starving => {
                 vec!["FEED ME NOW!", "I'M WASTING AWAY!", "EMERGENCY FOOD REQUIRED!"]
             },
+            Mood::Sluggish => {
+                vec!["So... slow...", "*chews very slowly*", "Everything is stalling..."]
+            },
+            Mood::Cold => {
+                vec!["Brrr, warm food!", "Part of me is out in swap...", "*shivers while eating*"]
+            },
+            Mood::Heartburn => {
+                vec!["Ugh, my page tables...", "*burp* major fault", "Too much, too fast!"]
+            },
+            Mood::Squeezed => {
+                vec!["Everyone wants my RAM back...", "*deflates a little*", "Squished, but still eating"]
+            },
+            Mood::Poisoned => {
+                vec!["Something I ate flipped a bit...", "*checksum gurgles*", "That byte tasted wrong"]
+            },
             _ => {
                 vec!["Munch munch", "Nom", "...", "*eating sounds*"]
             }
@@ -172,9 +256,69 @@ impl Personality {
         reactions[thread_rng().gen_range(0..reactions.len())]
     }
     
+    /// What the pet says about a process hoarding RAM (e.g. "node (pid 4121) +300 MB in 10 min")
+    pub fn get_leak_warning(&self, suspect: &str) -> String {
+        if self.has_quirk(&Quirk::DramaQueen) {
+            format!("THIEF! {} IS TAKING ALL THE RAM!", suspect)
+        } else if self.has_quirk(&Quirk::Nerd) {
+            format!("Possible memory leak: {}", suspect)
+        } else if self.has_quirk(&Quirk::Grumpy) {
+            format!("Typical. {} won't share", suspect)
+        } else if self.sass_level > 0.7 {
+            format!("Excuse me? {} - that's MY food", suspect)
+        } else {
+            format!("Someone is hoarding RAM: {}", suspect)
+        }
+    }
+    
+    /// Trash talk for a process that's eating more than the pet
+    pub fn get_rival_taunt(&self, rival: &str, size: Bytes) -> String {
+        let mut rng = thread_rng();
+        let taunts = if self.has_quirk(&Quirk::DramaQueen) {
+            vec![
+                format!("{} HAS {}?! THE INJUSTICE!", rival.to_uppercase(), size),
+                format!("I'm WASTING AWAY while {} feasts!", rival),
+            ]
+        } else if self.has_quirk(&Quirk::Nerd) {
+            vec![
+                format!("{} is using {}. Probably Electron", rival, size),
+                format!("kill -9 {}? Just a thought", rival),
+            ]
+        } else if self.has_quirk(&Quirk::Grumpy) {
+            vec![
+                format!("Of course {} gets {}. Nobody feeds ME", rival, size),
+                format!("{}. Hmph.", rival),
+            ]
+        } else if self.sass_level > 0.7 {
+            vec![
+                format!("{} ate {}, I demand equal rations", rival, size),
+                format!("Who does {} think it is?", rival),
+            ]
+        } else {
+            vec![
+                format!("{} has {}... I want to be that big!", rival, size),
+                format!("One day I'll be bigger than {}", rival),
+            ]
+        };
+        taunts[rng.gen_range(0..taunts.len())].clone()
+    }
+    
+    /// Gloating after overtaking another process
+    pub fn get_rank_boast(&self, rival: &str, rank: usize) -> String {
+        if self.has_quirk(&Quirk::DramaQueen) {
+            format!("I HAVE DEFEATED {}! #{} AND RISING!", rival.to_uppercase(), rank)
+        } else if self.has_quirk(&Quirk::Sweetheart) {
+            format!("Sorry {}, I'm #{} now!", rival, rank)
+        } else if self.has_quirk(&Quirk::Philosopher) {
+            format!("Today I outgrew {}. Tomorrow, #{} will seem small", rival, rank)
+        } else {
+            format!("Bigger than {} now! #{} on the food chain", rival, rank)
+        }
+    }
+    
     /// Get favorite food size based on personality
-    pub fn get_favorite_food_size(&self) -> usize {
-        match self.food_preference {
+    pub fn get_favorite_food_size(&self) -> MiB {
+        let size = match self.food_preference {
             FoodPreference::SmallFrequentMeals => thread_rng().gen_range(10..30),
             FoodPreference::BingeEater => thread_rng().gen_range(200..500),
             FoodPreference::Gourmet => {
@@ -183,7 +327,13 @@ impl Personality {
                 gourmet_sizes[thread_rng().gen_range(0..gourmet_sizes.len())]
             },
             FoodPreference::Chaotic => thread_rng().gen_range(1..1000),
-        }
+        };
+        MiB(size)
+    }
+    
+    /// How the pet feels about each kind of food content
+    pub fn get_tastes(&self) -> &Tastes {
+        &self.tastes
     }
     
     /// Check if pet has a specific quirk
@@ -225,6 +375,15 @@ impl Personality {
     }
 }
 
+/// A random number generator that always starts the same for a given seed
+fn seeded_rng(seed: &str) -> StdRng {
+    // FNV-1a, which unlike the std hasher is stable between releases
+    let hash = seed.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    StdRng::seed_from_u64(hash)
+}
+
 impl Mood {
     /// Get RGB color for mood
     pub fn get_color(&self) -> (u8, u8, u8) {
@@ -237,6 +396,11 @@ impl Mood {
             Mood::Sad => (128, 128, 128),     // Gray
             Mood::Angry => (255, 0, 128),     // Red-Pink
             Mood::Sleepy => (192, 192, 255),  // Light Blue
+            Mood::Sluggish => (153, 102, 204), // Purple
+            Mood::Cold => (128, 224, 255),    // Icy Blue
+            Mood::Heartburn => (255, 96, 0),  // Burnt Orange
+            Mood::Poisoned => (154, 205, 50), // Sickly Green
+            Mood::Squeezed => (255, 105, 180), // Balloon Pink
             Mood::Dead => (64, 64, 64),       // Dark Gray
         }
     }
@@ -252,6 +416,11 @@ impl Mood {
             Mood::Sad => "Sad",
             Mood::Angry => "Angry",
             Mood::Sleepy => "Sleepy",
+            Mood::Sluggish => "Sluggish",
+            Mood::Cold => "Cold",
+            Mood::Heartburn => "Heartburn",
+            Mood::Poisoned => "POISONED",
+            Mood::Squeezed => "Squeezed",
             Mood::Dead => "Dead",
         }
     }
@@ -275,4 +444,25 @@ mod tests {
         assert!(!name.is_empty());
         assert!(name.contains(' '));
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_seeded_personality() {
+        let alice = Personality::from_seed("alice");
+        let again = Personality::from_seed("alice");
+        assert_eq!(format!("{:?}", alice), format!("{:?}", again));
+        assert_eq!(alice.seeded_name("alice"), again.seeded_name("alice"));
+    }
+    
+    #[test]
+    fn test_tastes() {
+        let tastes = Tastes { zeros: -0.5, pattern: 0.2, text: 0.9, random: -1.0 };
+        assert_eq!(tastes.favorite(), FoodContent::Text);
+        assert_eq!(tastes.liking(FoodContent::Random), -1.0);
+        
+        // Pets from before tastes existed don't mind what they eat
+        let mut json = serde_json::to_value(Personality::generate_random()).unwrap();
+        json.as_object_mut().unwrap().remove("tastes");
+        let old: Personality = serde_json::from_value(json).unwrap();
+        assert_eq!(old.tastes, Tastes::default());
+    }
+}
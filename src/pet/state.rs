@@ -3,6 +3,7 @@
 
 use serde::{Deserialize, Serialize};
 use crate::pet::personality::Mood;
+use crate::system::units::MiB;
 
 /// Pet development states based on size
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
@@ -18,6 +19,35 @@ pub enum PetState {
 }
 
 impl PetState {
+    /// The life stage a pet of this size is in
+    pub fn for_size(size: MiB) -> Self {
+        match size.0 {
+            0..=50 => PetState::Baby,
+            51..=150 => PetState::Child,
+            151..=300 => PetState::Teen,
+            301..=500 => PetState::Adult,
+            501..=1000 => PetState::Chubby,
+            1001..=1500 => PetState::Fat,
+            1501..=2000 => PetState::Huge,
+            _ => PetState::Gigantic,
+        }
+    }
+    
+    /// One-line face for when there are lots of pets on screen; it gets
+    /// chubbier cheeks as it grows
+    pub fn get_mini_art(&self, mood: &Mood) -> String {
+        let (eye, mouth) = match mood {
+            Mood::Happy => ("◕", "◡"),
+            Mood::Excited => ("★", "▽"),
+            Mood::Hungry => ("◔", "﹏"),
+            Mood::Sad => ("╥", "︵"),
+            Mood::Dead => ("✖", "_"),
+            _ => ("•", "ᴗ"),
+        };
+        let cheeks = " ".repeat(*self as usize);
+        format!("({}{}{}{}{})", cheeks, eye, mouth, eye, cheeks)
+    }
+    
     /// Get ASCII art for the current state and mood
     pub fn get_ascii_art(&self, mood: &Mood) -> Vec<String> {
         match self {
@@ -40,6 +70,11 @@ impl PetState {
             Mood::Hungry => "◔ ◔",
             Mood::Starving => "✖ ✖",
             Mood::Sad => "╥ ╥",
+            Mood::Sluggish => "- -",
+            Mood::Cold => "* *",
+            Mood::Heartburn => "> <",
+            Mood::Poisoned => "@ @",
+            Mood::Squeezed => "≥ ≤",
             Mood::Dead => "✖ ✖",
             _ => "• •",
         };
@@ -68,6 +103,11 @@ impl PetState {
             Mood::Hungry => "◎ ◎",
             Mood::Starving => "⊗ ⊗",
             Mood::Sad => "┬ ┬",
+            Mood::Sluggish => "- -",
+            Mood::Cold => "* *",
+            Mood::Heartburn => "> <",
+            Mood::Poisoned => "@ @",
+            Mood::Squeezed => "≥ ≤",
             Mood::Dead => "✖ ✖",
             _ => "○ ○",
         };
@@ -97,6 +137,11 @@ impl PetState {
             Mood::Hungry => "◔   ◔",
             Mood::Starving => "☓   ☓",
             Mood::Sad => "╥   ╥",
+            Mood::Sluggish => "-   -",
+            Mood::Cold => "*   *",
+            Mood::Heartburn => ">   <",
+            Mood::Poisoned => "@   @",
+            Mood::Squeezed => "≥   ≤",
             Mood::Dead => "✖   ✖",
             _ => "●   ●",
         };
@@ -190,6 +235,11 @@ impl PetState {
             Mood::Sad => "╥",
             Mood::Angry => "▼",
             Mood::Sleepy => "━",
+            Mood::Sluggish => "-",
+            Mood::Cold => "*",
+            Mood::Heartburn => "×",
+            Mood::Poisoned => "☠",
+            Mood::Squeezed => "o",
             Mood::Dead => "✖",
             _ => "●",
         }
@@ -205,6 +255,11 @@ impl PetState {
             Mood::Sad => "╯",
             Mood::Angry => "▼",
             Mood::Sleepy => "━",
+            Mood::Sluggish => "~",
+            Mood::Cold => "≈",
+            Mood::Heartburn => "﹏",
+            Mood::Poisoned => "≋",
+            Mood::Squeezed => "﹋",
             Mood::Dead => "✖",
             _ => "─",
         }
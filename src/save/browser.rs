@@ -0,0 +1,196 @@
+// src/save/browser.rs
+// RAM Eating Pet Simulator - In-Game Save Browser
+
+use crossterm::event::KeyCode;
+
+use super::slots::{SlotInfo, MAX_SLOT_NAME};
+
+/// What a name being typed is for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Naming {
+    /// Save the current pet to a new slot
+    SaveAs,
+    /// Rename the selected slot
+    Rename,
+    /// Copy the selected slot
+    Duplicate,
+}
+
+/// What a yes/no question is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Confirm {
+    /// Replace the living pet with the selected slot
+    Load,
+    /// Delete the selected slot
+    Delete,
+}
+
+/// A question the browser is waiting on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Prompt {
+    Name { naming: Naming, input: String },
+    Confirm(Confirm),
+}
+
+/// Something the player asked the browser to do
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BrowserAction {
+    Load(String),
+    SaveAs(String),
+    Rename { from: String, to: String },
+    Duplicate { from: String, to: String },
+    Delete(String),
+    Close,
+}
+
+/// The save/load screen: a list of slots and the question being asked, if any
+///
+/// Keys go in through [`SaveBrowser::handle`]; anything that touches the
+/// disk comes back out as a [`BrowserAction`] for the game to carry out.
+#[derive(Debug, Default)]
+pub struct SaveBrowser {
+    slots: Vec<SlotInfo>,
+    selected: usize,
+    prompt: Option<Prompt>,
+}
+
+impl SaveBrowser {
+    /// Open on a list of slots, with the current slot selected if it's there
+    pub fn new(slots: Vec<SlotInfo>, current: &str) -> Self {
+        let mut browser = SaveBrowser::default();
+        browser.refresh(slots, Some(current));
+        browser
+    }
+
+    /// Swap in a fresh list, selecting `select` if given and present
+    pub fn refresh(&mut self, slots: Vec<SlotInfo>, select: Option<&str>) {
+        let keep = select.map(str::to_string).or_else(|| self.selected().map(|slot| slot.name.clone()));
+        self.slots = slots;
+        self.selected = keep
+            .and_then(|name| self.slots.iter().position(|slot| slot.name == name))
+            .unwrap_or(self.selected)
+            .min(self.slots.len().saturating_sub(1));
+        self.prompt = None;
+    }
+
+    pub fn slots(&self) -> &[SlotInfo] {
+        &self.slots
+    }
+
+    /// Index of the highlighted slot
+    pub fn selected_index(&self) -> usize {
+        self.selected
+    }
+
+    /// The highlighted slot, unless there are none
+    pub fn selected(&self) -> Option<&SlotInfo> {
+        self.slots.get(self.selected)
+    }
+
+    pub fn prompt(&self) -> Option<&Prompt> {
+        self.prompt.as_ref()
+    }
+
+    /// React to a key press
+    pub fn handle(&mut self, key: KeyCode) -> Option<BrowserAction> {
+        match self.prompt.take() {
+            None => self.browse(key),
+            Some(Prompt::Confirm(confirm)) => {
+                let name = self.selected()?.name.clone();
+                match key {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => Some(match confirm {
+                        Confirm::Load => BrowserAction::Load(name),
+                        Confirm::Delete => BrowserAction::Delete(name),
+                    }),
+                    // Anything else backs out
+                    _ => None,
+                }
+            }
+            Some(Prompt::Name { naming, mut input }) => match key {
+                KeyCode::Esc => None,
+                KeyCode::Enter if !input.is_empty() => {
+                    let from = self.selected().map(|slot| slot.name.clone()).unwrap_or_default();
+                    Some(match naming {
+                        Naming::SaveAs => BrowserAction::SaveAs(input),
+                        Naming::Rename => BrowserAction::Rename { from, to: input },
+                        Naming::Duplicate => BrowserAction::Duplicate { from, to: input },
+                    })
+                }
+                KeyCode::Backspace => {
+                    input.pop();
+                    self.prompt = Some(Prompt::Name { naming, input });
+                    None
+                }
+                KeyCode::Char(c) if (c.is_alphanumeric() || c == '-' || c == '_')
+                    && input.chars().count() < MAX_SLOT_NAME =>
+                {
+                    input.push(c);
+                    self.prompt = Some(Prompt::Name { naming, input });
+                    None
+                }
+                _ => {
+                    self.prompt = Some(Prompt::Name { naming, input });
+                    None
+                }
+            },
+        }
+    }
+
+    fn browse(&mut self, key: KeyCode) -> Option<BrowserAction> {
+        let has_selection = self.selected().is_some();
+        match key {
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => self.selected = (self.selected + 1).min(self.slots.len().saturating_sub(1)),
+            KeyCode::Enter if has_selection => self.prompt = Some(Prompt::Confirm(Confirm::Load)),
+            KeyCode::Char('d') | KeyCode::Char('D') | KeyCode::Delete if has_selection => {
+                self.prompt = Some(Prompt::Confirm(Confirm::Delete));
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') => self.ask_name(Naming::SaveAs),
+            KeyCode::Char('r') | KeyCode::Char('R') if has_selection => self.ask_name(Naming::Rename),
+            KeyCode::Char('c') | KeyCode::Char('C') if has_selection => self.ask_name(Naming::Duplicate),
+            KeyCode::Esc | KeyCode::Char('l') | KeyCode::Char('L') | KeyCode::Char('q') | KeyCode::Char('Q') => {
+                return Some(BrowserAction::Close);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn ask_name(&mut self, naming: Naming) {
+        self.prompt = Some(Prompt::Name { naming, input: String::new() });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slot(name: &str) -> SlotInfo {
+        SlotInfo { name: name.to_string(), summary: Err("empty".to_string()) }
+    }
+
+    #[test]
+    fn test_browser_keys() {
+        let mut browser = SaveBrowser::new(vec![slot("a"), slot("b")], "b");
+        assert_eq!(browser.selected_index(), 1);
+
+        // Loading always asks first, and anything but Y backs out
+        assert_eq!(browser.handle(KeyCode::Enter), None);
+        assert_eq!(browser.prompt(), Some(&Prompt::Confirm(Confirm::Load)));
+        assert_eq!(browser.handle(KeyCode::Char('n')), None);
+        assert_eq!(browser.prompt(), None);
+        browser.handle(KeyCode::Enter);
+        assert_eq!(browser.handle(KeyCode::Char('y')), Some(BrowserAction::Load("b".to_string())));
+
+        browser.handle(KeyCode::Up);
+        browser.handle(KeyCode::Char('r'));
+        for key in [KeyCode::Char('x'), KeyCode::Char('/'), KeyCode::Char('y'), KeyCode::Backspace, KeyCode::Char('2')] {
+            assert_eq!(browser.handle(key), None);
+        }
+        assert_eq!(
+            browser.handle(KeyCode::Enter),
+            Some(BrowserAction::Rename { from: "a".to_string(), to: "x2".to_string() })
+        );
+        assert_eq!(browser.handle(KeyCode::Esc), Some(BrowserAction::Close));
+    }
+}
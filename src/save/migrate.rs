@@ -0,0 +1,142 @@
+// src/save/migrate.rs
+// RAM Eating Pet Simulator - Save Format Migrations
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+
+/// The save format this version of the game writes
+///
+/// Bump it and add a step to [`MIGRATIONS`] whenever a change to the save
+/// would stop older saves from loading.
+pub const FORMAT_VERSION: u32 = 2;
+
+/// One upgrade step, from format `n` to `n + 1`, given when the file was
+/// last written
+type Migration = fn(Value, DateTime<Utc>) -> Result<Value>;
+
+/// `MIGRATIONS[n]` upgrades format `n` to `n + 1`
+const MIGRATIONS: [Migration; FORMAT_VERSION as usize] = [
+    v0_to_v1,
+    v1_to_v2,
+];
+
+/// The format a parsed save was written in
+///
+/// Saves from before the envelope existed have no `format` field; they
+/// are format 0.
+pub fn format_of(value: &Value) -> Result<u32> {
+    let object = value.as_object().context("not a save file")?;
+    let format = match object.get("format") {
+        None => return Ok(0),
+        Some(format) => format.as_u64().context("save format isn't a number")?,
+    };
+    if format > FORMAT_VERSION as u64 {
+        let by = object.get("game_version").and_then(Value::as_str).unwrap_or("unknown");
+        bail!(
+            "save format {} was written by version {} and is newer than this game understands ({})",
+            format,
+            by,
+            FORMAT_VERSION
+        );
+    }
+    Ok(format as u32)
+}
+
+/// Upgrade a save written in format `from` to the current format, one step at a time
+///
+/// `modified` is when the file was last written, for steps that need a time
+/// the old format didn't record.
+pub fn upgrade(mut value: Value, from: u32, modified: DateTime<Utc>) -> Result<Value> {
+    for (step, migration) in MIGRATIONS.iter().enumerate().skip(from as usize) {
+        value = migration(value, modified)
+            .with_context(|| format!("couldn't upgrade save from format {} to {}", step, step + 1))?;
+        log::debug!("Upgraded save from format {} to {}", step, step + 1);
+    }
+    Ok(value)
+}
+
+/// Wrap a bare save in the envelope
+///
+/// When it was made wasn't recorded, so both timestamps are when the file
+/// was last written. That keeps time away (and the slot list) right, even
+/// if the pet seems younger than it is.
+fn v0_to_v1(data: Value, modified: DateTime<Utc>) -> Result<Value> {
+    Ok(json!({
+        "format": 1,
+        "game_version": "unknown",
+        "created_at": modified,
+        "saved_at": modified,
+        "data": data,
+    }))
+}
+
+/// Give the pet a wall-clock birthday
+///
+/// Until now its age wasn't saved; the best guess is when the save was
+/// started.
+fn v1_to_v2(mut file: Value, _modified: DateTime<Utc>) -> Result<Value> {
+    let created_at = file.get("created_at").cloned().context("save has no created_at")?;
+    let pet = file.pointer_mut("/data/pet")
+        .and_then(Value::as_object_mut)
+        .context("save has no pet")?;
+    pet.insert("born_at".to_string(), created_at);
+    file["format"] = json!(2);
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::save::SaveFile;
+    use crate::system::units::MiB;
+    use chrono::TimeZone;
+
+    /// A save from every format there has been, oldest first
+    const FIXTURES: [&str; FORMAT_VERSION as usize + 1] = [
+        include_str!("fixtures/v0.json"),
+        include_str!("fixtures/v1.json"),
+        include_str!("fixtures/v2.json"),
+    ];
+
+    #[test]
+    fn test_loads_every_past_format() {
+        for (format, json) in FIXTURES.iter().enumerate() {
+            let (file, written) = SaveFile::parse(json, Utc::now())
+                .unwrap_or_else(|e| panic!("format {} fixture: {:#}", format, e));
+            assert_eq!(written, format as u32);
+            assert_eq!(file.format, FORMAT_VERSION);
+            assert!(file.saved_at >= file.created_at);
+        }
+
+        // A bare save's timestamps are all the file has to go on
+        let modified = Utc.with_ymd_and_hms(2026, 2, 1, 18, 30, 0).unwrap();
+        let (legacy, _) = SaveFile::parse(FIXTURES[0], modified).unwrap();
+        assert_eq!(legacy.game_version, "unknown");
+        assert_eq!((legacy.created_at, legacy.saved_at), (modified, modified));
+        assert_eq!(legacy.data.pet.get_born_at(), modified);
+        assert_eq!(legacy.data.pet.name, "Byte Muncher");
+        assert_eq!(legacy.data.pet.get_size_mb(), MiB(342));
+        assert_eq!(legacy.data.feeding_count, 9);
+
+        let (v1, _) = SaveFile::parse(FIXTURES[1], modified).unwrap();
+        assert_eq!(v1.game_version, "0.1.0");
+        assert_eq!(v1.created_at.to_rfc3339(), "2026-03-14T09:26:53+00:00");
+        assert_eq!(v1.data.max_size_reached, MiB(1290));
+        assert_eq!(v1.data.pet.get_born_at(), v1.created_at);
+
+        let (v2, _) = SaveFile::parse(FIXTURES[2], modified).unwrap();
+        assert_eq!(v2.data.pet.get_born_at().to_rfc3339(), "2026-05-02T08:14:59+00:00");
+    }
+
+    #[test]
+    fn test_rejects_newer_formats() {
+        let mut value: Value = serde_json::from_str(FIXTURES[FORMAT_VERSION as usize]).unwrap();
+        value["format"] = json!(FORMAT_VERSION + 1);
+        value["game_version"] = json!("9.9.9");
+
+        let error = SaveFile::parse(&value.to_string(), Utc::now()).unwrap_err().to_string();
+        assert!(error.contains("9.9.9"), "{}", error);
+        assert!(SaveFile::parse("[1, 2, 3]", Utc::now()).is_err());
+    }
+}
@@ -0,0 +1,72 @@
+// src/save/mod.rs
+// RAM Eating Pet Simulator - Save Games
+
+pub mod browser;
+pub mod migrate;
+pub mod slots;
+pub mod store;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::pet::Pet;
+use crate::system::units::MiB;
+
+pub use migrate::FORMAT_VERSION;
+pub use browser::{BrowserAction, SaveBrowser};
+pub use slots::{SaveSlots, SlotInfo};
+pub use store::{Loaded, SaveStore};
+
+/// Everything written to a save file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveData {
+    pub pet: Pet,
+    pub total_mb_eaten: MiB,
+    pub feeding_count: usize,
+    pub max_size_reached: MiB,
+}
+
+/// A save file: the game state wrapped in an envelope saying what wrote it
+///
+/// The envelope lets older saves be upgraded (see [`migrate`]) instead of
+/// failing to parse whenever the pet gains a field.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveFile {
+    /// Layout of the file, bumped whenever an old save needs migrating
+    pub format: u32,
+    /// Version of the game that last wrote it
+    pub game_version: String,
+    /// When the game this save belongs to was started
+    pub created_at: DateTime<Utc>,
+    /// When it was last saved
+    pub saved_at: DateTime<Utc>,
+    pub data: SaveData,
+}
+
+impl SaveFile {
+    /// Wrap game state for saving now, in the current format
+    pub fn new(data: SaveData, created_at: DateTime<Utc>) -> Self {
+        SaveFile {
+            format: FORMAT_VERSION,
+            game_version: crate::VERSION.to_string(),
+            created_at,
+            saved_at: Utc::now(),
+            data,
+        }
+    }
+
+    /// Read a save from any past format, upgrading it to the current one
+    ///
+    /// `modified` is when the file was last written, which stands in for
+    /// timestamps older formats didn't keep. Also returns the format it was
+    /// written in.
+    pub fn parse(json: &str, modified: DateTime<Utc>) -> Result<(Self, u32)> {
+        let value: serde_json::Value = serde_json::from_str(json).context("not valid JSON")?;
+        let written = migrate::format_of(&value)?;
+        let value = migrate::upgrade(value, written, modified)?;
+        let file = serde_json::from_value(value)
+            .with_context(|| format!("doesn't match save format {}", written))?;
+        Ok((file, written))
+    }
+}
@@ -0,0 +1,381 @@
+// src/save/slots.rs
+// RAM Eating Pet Simulator - Named Save Slots
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::SaveStore;
+use crate::pet::state::PetState;
+use crate::system::units::MiB;
+
+/// Longest slot name we accept
+pub const MAX_SLOT_NAME: usize = 32;
+
+/// Extension of save slot files, so nothing else in the directory is
+/// mistaken for a slot
+pub const SLOT_EXTENSION: &str = "ramsave";
+
+/// What a save slot holds, read from its save (or newest good backup)
+#[derive(Debug, Clone)]
+pub struct SlotSummary {
+    pub pet_name: String,
+    pub stage: PetState,
+    pub size: MiB,
+    /// When the slot was last saved
+    pub saved_at: DateTime<Utc>,
+    pub alive: bool,
+    /// Whether the save was damaged and this came from a backup
+    pub recovered: bool,
+}
+
+/// One save slot in the saves directory
+#[derive(Debug, Clone)]
+pub struct SlotInfo {
+    pub name: String,
+    /// What's in it, or why it couldn't be read
+    pub summary: Result<SlotSummary, String>,
+}
+
+/// The saves directory: one `<slot>.ramsave` per slot, each with its backups
+#[derive(Debug, Clone)]
+pub struct SaveSlots {
+    dir: PathBuf,
+    backups: usize,
+}
+
+impl SaveSlots {
+    /// Slots in `dir`, each keeping `backups` older generations
+    pub fn new(dir: impl Into<PathBuf>, backups: usize) -> Self {
+        SaveSlots { dir: dir.into(), backups }
+    }
+
+    /// The slots next to a save file, and the name of that file's slot
+    ///
+    /// A save from before slots had their own extension, either at
+    /// `save_path` itself or at `<stem>.json` beside it, is moved into its
+    /// slot the first time.
+    pub fn around(save_path: &Path, backups: usize) -> (Self, String) {
+        let dir = save_path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+        let slot = save_path.file_stem().map_or_else(|| "pet_save".into(), |stem| stem.to_string_lossy());
+        let slots = Self::new(dir, backups);
+        let mut legacy = vec![dir.join(format!("{}.json", slot))];
+        if save_path.extension().is_none_or(|ext| ext != SLOT_EXTENSION) {
+            legacy.insert(0, save_path.to_path_buf());
+        }
+        for old in &legacy {
+            if let Err(e) = slots.import(old, &slot) {
+                log::warn!("Couldn't move {} into its save slot: {:#}", old.display(), e);
+            }
+        }
+        (slots, slot.into_owned())
+    }
+
+    /// The saves directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The save store behind a slot
+    pub fn store(&self, name: &str) -> SaveStore {
+        SaveStore::new(self.dir.join(format!("{}.{}", name, SLOT_EXTENSION)), self.backups)
+    }
+
+    /// Every slot, most recently played first; unreadable ones come last
+    pub fn list(&self) -> Result<Vec<SlotInfo>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e).with_context(|| format!("couldn't read {}", self.dir.display())),
+        };
+
+        let mut slots = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != SLOT_EXTENSION) {
+                continue;
+            }
+            if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                slots.push(SlotInfo { name: name.to_string(), summary: self.summarize(name) });
+            }
+        }
+        slots.sort_by(|a, b| match (&a.summary, &b.summary) {
+            (Ok(a), Ok(b)) => b.saved_at.cmp(&a.saved_at),
+            (Ok(_), Err(_)) => std::cmp::Ordering::Less,
+            (Err(_), Ok(_)) => std::cmp::Ordering::Greater,
+            (Err(_), Err(_)) => std::cmp::Ordering::Equal,
+        }.then_with(|| a.name.cmp(&b.name)));
+        Ok(slots)
+    }
+
+    /// Whether a slot has anything in it
+    pub fn exists(&self, name: &str) -> bool {
+        self.store(name).exists()
+    }
+
+    /// Move a slot, backups and all, to a new name
+    pub fn rename(&self, from: &str, to: &str) -> Result<()> {
+        let (source, target) = self.pair(from, to)?;
+        for n in 0..=source.backups() {
+            let path = generation(&source, n);
+            if path.exists() {
+                fs::rename(&path, generation(&target, n))
+                    .with_context(|| format!("couldn't rename {}", path.display()))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Copy a slot's save (not its backups) to a new slot
+    pub fn duplicate(&self, from: &str, to: &str) -> Result<()> {
+        let (source, target) = self.pair(from, to)?;
+        let loaded = source.load()?.with_context(|| format!("slot '{}' is empty", from))?;
+        target.save(&loaded.file)
+    }
+
+    /// Delete a slot and its backups, returning how many files went
+    pub fn remove(&self, name: &str) -> Result<usize> {
+        validate_name(name)?;
+        let store = self.checked_store(name)?;
+        let mut removed = 0;
+        for n in 0..=store.backups() {
+            let path = generation(&store, n);
+            if path.exists() {
+                fs::remove_file(&path).with_context(|| format!("couldn't delete {}", path.display()))?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    fn summarize(&self, name: &str) -> Result<SlotSummary, String> {
+        match self.store(name).load() {
+            Ok(Some(loaded)) => {
+                let pet = &loaded.file.data.pet;
+                Ok(SlotSummary {
+                    pet_name: pet.name.clone(),
+                    stage: *pet.get_state(),
+                    size: pet.get_size_mb(),
+                    saved_at: loaded.file.saved_at,
+                    alive: !pet.is_dead(),
+                    recovered: loaded.backup.is_some(),
+                })
+            }
+            Ok(None) => Err("empty".to_string()),
+            Err(e) => Err(format!("{:#}", e)),
+        }
+    }
+
+    /// Stores for an existing slot and a new, free one
+    fn pair(&self, from: &str, to: &str) -> Result<(SaveStore, SaveStore)> {
+        validate_name(from)?;
+        validate_name(to)?;
+        let source = self.checked_store(from)?;
+        if self.exists(to) {
+            bail!("save slot '{}' is already taken", to);
+        }
+        Ok((source, self.store(to)))
+    }
+
+    /// The store behind a slot that holds a save we can read
+    ///
+    /// Files are only moved or deleted once we know they're ours.
+    fn checked_store(&self, name: &str) -> Result<SaveStore> {
+        let store = self.store(name);
+        match store.load() {
+            Ok(Some(_)) => Ok(store),
+            Ok(None) => bail!("there is no save slot '{}'", name),
+            Err(e) => Err(e.context(format!(
+                "slot '{}' doesn't hold a save; leaving its files alone", name
+            ))),
+        }
+    }
+
+    /// Move a save from before slots had their own extension, backups and
+    /// all, into slot `name`
+    ///
+    /// Does nothing if the slot is already taken or the file isn't a save.
+    fn import(&self, save_path: &Path, name: &str) -> Result<()> {
+        let legacy = SaveStore::new(save_path, self.backups);
+        let slot = self.store(name);
+        if slot.exists() || !legacy.exists() || !matches!(legacy.load(), Ok(Some(_))) {
+            return Ok(());
+        }
+        fs::create_dir_all(&self.dir)?;
+        for n in 0..=self.backups {
+            let path = generation(&legacy, n);
+            if path.exists() {
+                fs::rename(&path, generation(&slot, n))
+                    .with_context(|| format!("couldn't move {}", path.display()))?;
+            }
+        }
+        log::info!("Moved {} into save slot '{}'", save_path.display(), name);
+        Ok(())
+    }
+}
+
+/// Check a slot name is safe to use as a file name
+pub fn validate_name(name: &str) -> Result<()> {
+    if name.is_empty() {
+        bail!("slot name can't be empty");
+    }
+    if name.chars().count() > MAX_SLOT_NAME {
+        bail!("slot name '{}' is longer than {} characters", name, MAX_SLOT_NAME);
+    }
+    if !name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+        bail!("slot name '{}' may only use letters, digits, '-' and '_'", name);
+    }
+    Ok(())
+}
+
+/// The save (`n = 0`) or backup `n` of a store
+fn generation(store: &SaveStore, n: usize) -> PathBuf {
+    if n == 0 {
+        store.path().to_path_buf()
+    } else {
+        store.backup_path(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::pet::Pet;
+    use crate::save::{SaveData, SaveFile};
+
+    fn slots(name: &str) -> SaveSlots {
+        let dir = std::env::temp_dir().join(format!("ram_pet_slots_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        SaveSlots::new(dir, 2)
+    }
+
+    fn save(slots: &SaveSlots, slot: &str, pet_name: &str) {
+        let mut pet = Pet::new(&Config::default()).unwrap();
+        pet.name = pet_name.to_string();
+        let data = SaveData { pet, total_mb_eaten: MiB(0), feeding_count: 0, max_size_reached: MiB(50) };
+        slots.store(slot).save(&SaveFile::new(data, Utc::now())).unwrap();
+    }
+
+    #[test]
+    fn test_lists_slots_with_metadata() {
+        let slots = slots("list");
+        assert!(slots.list().unwrap().is_empty());
+
+        save(&slots, "older", "Nibbles");
+        save(&slots, "newer", "Chomper");
+        save(&slots, "newer", "Chomper");
+        fs::write(slots.dir().join("broken.ramsave"), "not json").unwrap();
+        fs::write(slots.dir().join("package.json"), "{}").unwrap();
+
+        let listed = slots.list().unwrap();
+        let names: Vec<&str> = listed.iter().map(|slot| slot.name.as_str()).collect();
+        // Backups, temp files and other people's JSON aren't slots
+        assert_eq!(names, ["newer", "older", "broken"]);
+
+        let newest = listed[0].summary.as_ref().unwrap();
+        assert_eq!(newest.pet_name, "Chomper");
+        assert_eq!(newest.stage, PetState::Baby);
+        assert!(newest.alive && !newest.recovered);
+        assert!(listed[2].summary.is_err());
+
+        fs::remove_dir_all(slots.dir()).unwrap();
+    }
+
+    #[test]
+    fn test_rename_duplicate_remove() {
+        let slots = slots("manage");
+        save(&slots, "main", "Chomper");
+        save(&slots, "main", "Chomper");
+
+        slots.rename("main", "first").unwrap();
+        assert!(!slots.exists("main"));
+        assert!(slots.store("first").backup_path(1).exists());
+
+        slots.duplicate("first", "second").unwrap();
+        assert!(slots.duplicate("first", "second").is_err());
+        assert!(slots.rename("missing", "third").is_err());
+        assert!(slots.rename("first", "../escape").is_err());
+
+        // Only files that hold a save are ever moved or deleted
+        fs::write(slots.store("broken").path(), "not json").unwrap();
+        assert!(slots.remove("broken").is_err());
+        assert!(slots.rename("broken", "fixed").is_err());
+        assert!(slots.store("broken").path().exists());
+        fs::remove_file(slots.store("broken").path()).unwrap();
+
+        assert_eq!(slots.remove("first").unwrap(), 2);
+        let listed = slots.list().unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].summary.as_ref().unwrap().pet_name, "Chomper");
+
+        fs::remove_dir_all(slots.dir()).unwrap();
+    }
+
+    #[test]
+    fn test_old_save_is_as_old_as_its_file() {
+        let slots = slots("legacy");
+        fs::create_dir_all(slots.dir()).unwrap();
+        let path = slots.store("old").path().to_path_buf();
+        fs::write(&path, include_str!("fixtures/v0.json")).unwrap();
+        let three_days_ago = DateTime::<Utc>::from_timestamp(Utc::now().timestamp() - 3 * 86400, 0).unwrap();
+        fs::File::options().write(true).open(&path).unwrap()
+            .set_modified(three_days_ago.into()).unwrap();
+
+        // The list and the loaded save agree, and so does the pet's birthday
+        let listed = slots.list().unwrap();
+        assert_eq!(listed[0].summary.as_ref().unwrap().saved_at, three_days_ago);
+        let loaded = slots.store("old").load().unwrap().unwrap();
+        assert_eq!((loaded.file.created_at, loaded.file.saved_at), (three_days_ago, three_days_ago));
+        assert_eq!(loaded.file.data.pet.get_born_at(), three_days_ago);
+
+        fs::remove_dir_all(slots.dir()).unwrap();
+    }
+
+    #[test]
+    fn test_slots_beside_a_bare_save_path() {
+        let (slots, slot) = SaveSlots::around(Path::new("pet.json"), 2);
+        assert_eq!(slots.dir(), Path::new("."));
+        assert_eq!(slot, "pet");
+        assert_eq!(slots.store(&slot).path(), Path::new("./pet.ramsave"));
+    }
+
+    #[test]
+    fn test_moves_old_save_into_its_slot() {
+        let slots = slots("import");
+        let old = SaveStore::new(slots.dir().join("pet_save.json"), 2);
+        let mut pet = Pet::new(&Config::default()).unwrap();
+        pet.name = "Oldie".to_string();
+        let data = SaveData { pet, total_mb_eaten: MiB(0), feeding_count: 0, max_size_reached: MiB(50) };
+        old.save(&SaveFile::new(data.clone(), Utc::now())).unwrap();
+        old.save(&SaveFile::new(data, Utc::now())).unwrap();
+
+        let (slots, slot) = SaveSlots::around(old.path(), 2);
+        assert!(!old.path().exists() && !old.backup_path(1).exists());
+        assert!(slots.store(&slot).backup_path(1).exists());
+        assert_eq!(slots.list().unwrap()[0].summary.as_ref().unwrap().pet_name, "Oldie");
+
+        fs::remove_dir_all(slots.dir()).unwrap();
+    }
+
+    #[test]
+    fn test_default_config_finds_old_json_save() {
+        let root = slots("default").dir().to_path_buf();
+        let config = Config::default();
+        let save_path = root.join(&config.game.save_path);
+        let old = SaveStore::new(save_path.with_extension("json"), config.game.backups);
+        let mut pet = Pet::new(&config).unwrap();
+        pet.name = "Oldie".to_string();
+        let data = SaveData { pet, total_mb_eaten: MiB(0), feeding_count: 0, max_size_reached: MiB(50) };
+        old.save(&SaveFile::new(data, Utc::now())).unwrap();
+
+        let (slots, slot) = SaveSlots::around(&save_path, config.game.backups);
+        assert_eq!(slots.store(&slot).path(), save_path);
+        assert!(!old.path().exists());
+        let loaded = slots.store(&slot).load().unwrap().unwrap();
+        assert_eq!(loaded.file.data.pet.name, "Oldie");
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
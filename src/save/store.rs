@@ -0,0 +1,257 @@
+// src/save/store.rs
+// RAM Eating Pet Simulator - Crash-Safe Save Files
+
+use anyhow::{anyhow, Context, Result};
+use std::ffi::OsString;
+use chrono::{DateTime, Utc};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use super::SaveFile;
+
+/// A save that was read back, and where it came from
+#[derive(Debug)]
+pub struct Loaded {
+    /// The save, upgraded to the current format
+    pub file: SaveFile,
+    /// The format it was written in
+    pub format: u32,
+    /// The file it was read from
+    pub path: PathBuf,
+    /// Which backup it is (1 = newest), or `None` for the save itself
+    pub backup: Option<usize>,
+}
+
+/// A save file that is never left half-written, with rotating backups
+///
+/// Each save goes to `<path>.tmp`, is fsynced and then renamed over the
+/// save, so a crash leaves either the old save or the new one and there is
+/// always a save at `path`. Before that, `.1` moves to `.2` and so on and
+/// the old save is linked (or copied) into `.1`, keeping the last
+/// `backups` generations. A damaged save isn't kept as a backup. Loading
+/// falls back to the newest backup that still parses.
+#[derive(Debug, Clone)]
+pub struct SaveStore {
+    path: PathBuf,
+    backups: usize,
+}
+
+impl SaveStore {
+    /// Save to `path`, keeping `backups` older generations next to it
+    pub fn new(path: impl Into<PathBuf>, backups: usize) -> Self {
+        SaveStore { path: path.into(), backups }
+    }
+
+    /// The save file itself
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// How many older generations are kept
+    pub fn backups(&self) -> usize {
+        self.backups
+    }
+
+    /// Where generation `n` of the backups lives (1 = newest)
+    pub fn backup_path(&self, n: usize) -> PathBuf {
+        self.sibling(&n.to_string())
+    }
+
+    /// Whether there is anything to load, even if it turns out to be damaged
+    pub fn exists(&self) -> bool {
+        self.path.exists() || (1..=self.backups).any(|n| self.backup_path(n).exists())
+    }
+
+    /// Write a save atomically, rotating the previous one into the backups
+    pub fn save(&self, file: &SaveFile) -> Result<()> {
+        let json = serde_json::to_string_pretty(file)?;
+        if let Some(dir) = self.dir() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("couldn't create save directory {}", dir.display()))?;
+        }
+
+        let tmp = self.sibling("tmp");
+        let mut file = File::create(&tmp)
+            .with_context(|| format!("couldn't create {}", tmp.display()))?;
+        file.write_all(json.as_bytes())?;
+        file.sync_all()?;
+        drop(file);
+
+        self.rotate()?;
+        fs::rename(&tmp, &self.path)
+            .with_context(|| format!("couldn't move the new save into {}", self.path.display()))?;
+        self.sync_dir();
+        Ok(())
+    }
+
+    /// Read the save, or the newest backup that parses if it's damaged
+    ///
+    /// Returns `None` when there's nothing saved at all.
+    pub fn load(&self) -> Result<Option<Loaded>> {
+        let candidates = std::iter::once((None, self.path.clone()))
+            .chain((1..=self.backups).map(|n| (Some(n), self.backup_path(n))));
+
+        let mut problems = Vec::new();
+        for (backup, path) in candidates {
+            if !path.exists() {
+                continue;
+            }
+            match read(&path) {
+                Ok((file, format)) => {
+                    for problem in &problems {
+                        log::warn!("Skipped damaged save: {}", problem);
+                    }
+                    return Ok(Some(Loaded { file, format, path, backup }));
+                }
+                Err(e) => problems.push(format!("{:#}", e)),
+            }
+        }
+
+        match problems.len() {
+            0 => Ok(None),
+            1 => Err(anyhow!("{}", problems[0])),
+            _ => Err(anyhow!("no save could be read:\n  - {}", problems.join("\n  - "))),
+        }
+    }
+
+    /// Shift every backup down a generation and keep a copy of the save in `.1`
+    ///
+    /// The save itself stays put until the new one is renamed over it. One
+    /// that doesn't parse isn't worth a generation, so the backups are left
+    /// alone.
+    fn rotate(&self) -> Result<()> {
+        if self.backups == 0 || !self.path.exists() {
+            return Ok(());
+        }
+        if let Err(e) = read(&self.path) {
+            log::warn!("Not backing up damaged save: {:#}", e);
+            return Ok(());
+        }
+        for n in (1..self.backups).rev() {
+            let from = self.backup_path(n);
+            if from.exists() {
+                fs::rename(&from, self.backup_path(n + 1))?;
+            }
+        }
+
+        // Still there when only one backup is kept
+        let first = self.backup_path(1);
+        if first.exists() {
+            fs::remove_file(&first)?;
+        }
+        fs::hard_link(&self.path, &first)
+            .or_else(|_| fs::copy(&self.path, &first).map(drop))
+            .with_context(|| format!("couldn't back up {}", self.path.display()))?;
+        Ok(())
+    }
+
+    /// `<path>.<suffix>`
+    fn sibling(&self, suffix: &str) -> PathBuf {
+        let mut name = OsString::from(self.path.as_os_str());
+        name.push(".");
+        name.push(suffix);
+        PathBuf::from(name)
+    }
+
+    fn dir(&self) -> Option<&Path> {
+        self.path.parent().filter(|dir| !dir.as_os_str().is_empty())
+    }
+
+    /// Make the renames themselves durable (best effort; not possible everywhere)
+    fn sync_dir(&self) {
+        #[cfg(unix)]
+        if let Ok(dir) = File::open(self.dir().unwrap_or(Path::new("."))) {
+            let _ = dir.sync_all();
+        }
+    }
+}
+
+fn read(path: &Path) -> Result<(SaveFile, u32)> {
+    let json = fs::read_to_string(path)
+        .with_context(|| format!("couldn't read {}", path.display()))?;
+    let modified = fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .map_or_else(|_| Utc::now(), DateTime::<Utc>::from);
+    SaveFile::parse(&json, modified).with_context(|| format!("{} is damaged", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::pet::Pet;
+    use crate::save::SaveData;
+    use crate::system::units::MiB;
+    use chrono::Utc;
+
+    fn data(feedings: usize) -> SaveFile {
+        let data = SaveData {
+            pet: Pet::new(&Config::default()).unwrap(),
+            total_mb_eaten: MiB(feedings * 10),
+            feeding_count: feedings,
+            max_size_reached: MiB(50),
+        };
+        SaveFile::new(data, Utc::now())
+    }
+
+    fn store(name: &str, backups: usize) -> (PathBuf, SaveStore) {
+        let dir = std::env::temp_dir().join(format!("ram_pet_save_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let store = SaveStore::new(dir.join("pet_save.json"), backups);
+        (dir, store)
+    }
+
+    #[test]
+    fn test_rotates_backups() {
+        let (dir, store) = store("rotate", 2);
+        assert!(store.load().unwrap().is_none());
+
+        for feedings in 1..=4 {
+            store.save(&data(feedings)).unwrap();
+        }
+        let loaded = store.load().unwrap().unwrap();
+        assert_eq!(loaded.file.data.feeding_count, 4);
+        assert_eq!(loaded.backup, None);
+
+        // Only the last two older generations are kept, newest first
+        assert!(store.backup_path(1).exists() && store.backup_path(2).exists());
+        assert!(!store.backup_path(3).exists());
+        assert!(!dir.join("pet_save.json.tmp").exists());
+        assert_eq!(read(&store.backup_path(2)).unwrap().0.data.feeding_count, 2);
+
+        // A single backup is replaced rather than shifted
+        let single = SaveStore::new(dir.join("single.json"), 1);
+        for feedings in 1..=3 {
+            single.save(&data(feedings)).unwrap();
+        }
+        assert_eq!(read(&single.backup_path(1)).unwrap().0.data.feeding_count, 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_recovers_from_damaged_save() {
+        let (dir, store) = store("recover", 3);
+        store.save(&data(1)).unwrap();
+        store.save(&data(2)).unwrap();
+
+        // A crash mid-write under the old scheme: a truncated save
+        fs::write(store.path(), "{\"pet\": {\"name\"").unwrap();
+        let loaded = store.load().unwrap().unwrap();
+        assert_eq!(loaded.file.data.feeding_count, 1);
+        assert_eq!(loaded.backup, Some(1));
+
+        // Saving over the damaged file keeps the good generations
+        store.save(&data(3)).unwrap();
+        assert_eq!(read(&store.backup_path(1)).unwrap().0.data.feeding_count, 1);
+        assert!(!store.backup_path(2).exists());
+        assert_eq!(store.load().unwrap().unwrap().file.data.feeding_count, 3);
+
+        fs::write(store.path(), "").unwrap();
+        fs::write(store.backup_path(1), "").unwrap();
+        assert!(store.load().is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
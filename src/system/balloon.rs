@@ -0,0 +1,153 @@
+// src/system/balloon.rs
+// RAM Eating Pet Simulator - Memory Balloon
+
+use super::monitor::RamTracker;
+use super::sampler::{FreshSnapshots, SystemSnapshot};
+use super::units::MiB;
+
+/// Smallest change in size the balloon bothers making
+///
+/// Bigger pets use 5% of their size instead, so small wobbles in free RAM
+/// don't turn into a stream of allocations and releases.
+pub const BALLOON_BAND: MiB = MiB(32);
+
+/// Samples of everyone else's usage used to judge whether they're growing
+const TREND_SAMPLES: usize = 10;
+
+/// Samples of the balloon's ideal size the squeeze is measured against
+const SQUEEZE_SAMPLES: usize = 60;
+
+/// Grows the pet into spare RAM and shrinks it when others need it back,
+/// like a VM balloon driver
+///
+/// The pet aims for `fraction` of the RAM that would be free above the
+/// reserve if it weren't there. It gives memory back as soon as the aim
+/// drops, but only creeps back up while other processes' usage isn't
+/// rising, so it doesn't fight them for every megabyte.
+pub struct Balloon {
+    fraction: f32,
+    reserve: MiB,
+    /// Memory used by everything except us
+    others: RamTracker,
+    /// The size the balloon would like to be
+    ideals: RamTracker,
+    ideal: MiB,
+    target: Option<MiB>,
+    fresh: FreshSnapshots,
+}
+
+impl Balloon {
+    /// Aim for `fraction` (0.0 - 1.0) of the spare RAM above `reserve`
+    pub fn new(fraction: f32, reserve: MiB) -> Self {
+        Balloon {
+            fraction: fraction.clamp(0.0, 1.0),
+            reserve,
+            others: RamTracker::new(TREND_SAMPLES),
+            ideals: RamTracker::new(SQUEEZE_SAMPLES),
+            ideal: MiB(0),
+            target: None,
+            fresh: FreshSnapshots::default(),
+        }
+    }
+
+    /// Share of the spare RAM the balloon aims for
+    pub fn fraction(&self) -> f32 {
+        self.fraction
+    }
+
+    /// Size the balloon last asked for, if it has decided yet
+    pub fn target(&self) -> Option<MiB> {
+        self.target
+    }
+
+    /// Work out how big the pet should be
+    ///
+    /// `free` is free RAM as the memory manager estimates it and `held` is
+    /// what the pet holds now. Returns `None` for a snapshot it has
+    /// already seen.
+    pub fn update(&mut self, snapshot: &SystemSnapshot, free: MiB, held: MiB) -> Option<MiB> {
+        if !self.fresh.accept(snapshot) {
+            return None;
+        }
+
+        // Everyone else's usage, with our own RSS from the same sample taken out
+        let ours = snapshot.vitals.map(|vitals| vitals.rss).or(snapshot.process_rss).unwrap_or_default();
+        let others = snapshot.budget().used.saturating_sub(ours).to_mib();
+        self.others.record_usage(snapshot.taken_at, others);
+
+        // The pet's own RAM counts as spare; it's ours to give back
+        let spare = (free + held).saturating_sub(self.reserve);
+        self.ideal = MiB((spare.0 as f64 * self.fraction as f64) as usize);
+        self.ideals.record_usage(snapshot.taken_at, self.ideal);
+
+        let band = BALLOON_BAND.max(MiB(held.0 / 20));
+        let target = if self.ideal + band < held {
+            // Someone needs the room: give it all back at once
+            self.ideal
+        } else if self.ideal > held + band && self.others.get_trend() <= band.0 as i64 {
+            // Creep back up a quarter of the gap at a time
+            let gap = self.ideal - held;
+            held + MiB(gap.0 / 4).max(band).min(gap)
+        } else {
+            held
+        };
+
+        self.target = Some(target);
+        Some(target)
+    }
+
+    /// How squeezed the balloon is (0.0 - 1.0): how far the size it would
+    /// like has fallen below its recent peak
+    pub fn squeeze(&self) -> f32 {
+        let peak = self.ideals.get_peak();
+        if peak.is_zero() {
+            0.0
+        } else {
+            1.0 - self.ideal.0 as f32 / peak.0 as f32
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::units::Bytes;
+    use std::time::{Duration, Instant};
+
+    /// A snapshot `secs` in the future where others use `others` MB
+    fn snapshot(secs: u64, others: MiB) -> SystemSnapshot {
+        SystemSnapshot {
+            used: others.to_bytes(),
+            process_rss: Some(Bytes(0)),
+            ..SystemSnapshot::fake(Instant::now() + Duration::from_secs(secs), Vec::new())
+        }
+    }
+
+    #[test]
+    fn test_grows_gently_and_shrinks_at_once() {
+        let mut balloon = Balloon::new(0.5, MiB(100));
+
+        // 1000 MB spare above the reserve: aim for 500, a quarter at a time
+        let first = snapshot(1, MiB(2000));
+        assert_eq!(balloon.update(&first, MiB(1100), MiB(0)), Some(MiB(125)));
+        assert_eq!(balloon.update(&first, MiB(1100), MiB(0)), None);
+        assert_eq!(balloon.update(&snapshot(2, MiB(2000)), MiB(975), MiB(125)), Some(MiB(218)));
+
+        // Close enough to the aim: hold still
+        assert_eq!(balloon.update(&snapshot(3, MiB(2000)), MiB(620), MiB(480)), Some(MiB(480)));
+        assert_eq!(balloon.squeeze(), 0.0);
+
+        // Someone else takes 600 MB: hand back everything over the new aim
+        assert_eq!(balloon.update(&snapshot(4, MiB(2600)), MiB(20), MiB(480)), Some(MiB(200)));
+        assert!((balloon.squeeze() - 0.6).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_waits_while_others_are_growing() {
+        let mut balloon = Balloon::new(0.5, MiB(0));
+        balloon.update(&snapshot(1, MiB(1000)), MiB(1000), MiB(100));
+
+        // Plenty of room, but usage elsewhere is climbing fast
+        assert_eq!(balloon.update(&snapshot(2, MiB(1400)), MiB(900), MiB(100)), Some(MiB(100)));
+    }
+}
@@ -0,0 +1,869 @@
+// src/system/food.rs
+// RAM Eating Pet Simulator - Food Sources (Allocation Backends)
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use super::units::{Bytes, MiB};
+
+/// Where the pet's memory comes from
+///
+/// Each kind shows up differently in `free`, `/proc/meminfo` and cgroup
+/// accounting, which makes the pet useful for reproducing specific kinds
+/// of memory pressure.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FoodKind {
+    /// 1 MB blocks from the global allocator (anonymous memory)
+    #[default]
+    Heap,
+    /// Anonymous private `mmap` regions
+    Mmap,
+    /// `memfd_create` regions (counted as shared memory / Shmem)
+    Memfd,
+    /// Shared mappings of files in a directory (e.g. tmpfs page cache)
+    File,
+    /// 2 MB anonymous regions advised for transparent huge pages
+    HugePage,
+}
+
+impl FoodKind {
+    /// Every food kind, in menu order
+    pub const ALL: [FoodKind; 5] = [
+        FoodKind::Heap,
+        FoodKind::Mmap,
+        FoodKind::Memfd,
+        FoodKind::File,
+        FoodKind::HugePage,
+    ];
+
+    /// Short name as used in config files
+    pub fn name(&self) -> &'static str {
+        match self {
+            FoodKind::Heap => "heap",
+            FoodKind::Mmap => "mmap",
+            FoodKind::Memfd => "memfd",
+            FoodKind::File => "file",
+            FoodKind::HugePage => "hugepage",
+        }
+    }
+
+    /// Next kind on the menu (wraps around)
+    pub fn next(&self) -> FoodKind {
+        let index = Self::ALL.iter().position(|k| k == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+impl fmt::Display for FoodKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for FoodKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL.iter()
+            .copied()
+            .find(|kind| kind.name().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| anyhow!(
+                "unknown food source '{}' (expected heap, mmap, memfd, file or hugepage)", s
+            ))
+    }
+}
+
+/// What the pet's memory is filled with
+///
+/// Memory compression (zram, zswap) and page merging (KSM) treat these very
+/// differently: zeros and patterns squash to almost nothing, text roughly
+/// halves, and random data doesn't compress at all.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FoodContent {
+    /// Nothing but zero bytes
+    #[default]
+    Zeros,
+    /// A short seeded pattern repeated over and over
+    Pattern,
+    /// Words from a small vocabulary, like a text file
+    Text,
+    /// Seeded pseudo-random bytes
+    Random,
+}
+
+impl FoodContent {
+    /// Every content profile, from most to least compressible
+    pub const ALL: [FoodContent; 4] = [
+        FoodContent::Zeros,
+        FoodContent::Pattern,
+        FoodContent::Text,
+        FoodContent::Random,
+    ];
+
+    /// Short name as used in config files
+    pub fn name(&self) -> &'static str {
+        match self {
+            FoodContent::Zeros => "zeros",
+            FoodContent::Pattern => "pattern",
+            FoodContent::Text => "text",
+            FoodContent::Random => "random",
+        }
+    }
+}
+
+impl fmt::Display for FoodContent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for FoodContent {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL.iter()
+            .copied()
+            .find(|content| content.name().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| anyhow!(
+                "unknown food content '{}' (expected zeros, pattern, text or random)", s
+            ))
+    }
+}
+
+/// A region of memory the pet is holding
+pub trait MemoryBlock: Send {
+    /// The block's contents
+    fn as_slice(&self) -> &[u8];
+    /// The block's contents, writable
+    fn as_mut_slice(&mut self) -> &mut [u8];
+}
+
+/// A way of getting memory for the pet to eat
+pub trait FoodSource: Send + Sync {
+    /// Which kind of food this source serves
+    fn kind(&self) -> FoodKind;
+
+    /// Size of each block handed out
+    fn block_size(&self) -> MiB {
+        MiB(1)
+    }
+
+    /// Allocate one block
+    fn allocate_block(&self) -> Result<Box<dyn MemoryBlock>>;
+}
+
+/// A block of pet memory along with where it came from
+pub struct FoodBlock {
+    region: Box<dyn MemoryBlock>,
+    kind: FoodKind,
+    locked: bool,
+    touched: bool,
+    fill: Option<Fill>,
+}
+
+/// What [`FoodBlock::fill`] wrote into a block
+#[derive(Debug, Clone, Copy)]
+struct Fill {
+    content: FoodContent,
+    seed: u64,
+    checksum: u64,
+}
+
+/// The first byte of a block that no longer holds what was written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Corruption {
+    /// Offset from the start of the block
+    pub offset: usize,
+    /// Byte the fill pattern put there
+    pub expected: u8,
+    /// Byte found instead
+    pub found: u8,
+}
+
+impl FoodBlock {
+    /// Allocate a block from a source
+    pub fn from_source(source: &dyn FoodSource) -> Result<Self> {
+        Ok(FoodBlock {
+            region: source.allocate_block()?,
+            kind: source.kind(),
+            locked: false,
+            touched: false,
+            fill: None,
+        })
+    }
+
+    /// Size of this block
+    pub fn size(&self) -> MiB {
+        MiB(self.region.as_slice().len() / MiB(1).to_bytes().0 as usize)
+    }
+
+    /// Which food source the block came from
+    pub fn kind(&self) -> FoodKind {
+        self.kind
+    }
+
+    /// The block's contents
+    pub fn as_slice(&self) -> &[u8] {
+        self.region.as_slice()
+    }
+
+    /// The block's contents, writable
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        self.region.as_mut_slice()
+    }
+
+    /// Fault every page into RAM without changing the contents
+    ///
+    /// Each page gets one byte read and written back. The write is what
+    /// forces a private copy instead of the shared zero page.
+    pub fn touch(&mut self) {
+        let page = page_size();
+        let region = self.region.as_mut_slice();
+        for offset in (0..region.len()).step_by(page) {
+            let byte: *mut u8 = &mut region[offset];
+            // SAFETY: `byte` points into the slice we hold mutably; volatile
+            // keeps the no-op store from being optimized away
+            unsafe { std::ptr::write_volatile(byte, std::ptr::read_volatile(byte)) };
+        }
+        self.touched = true;
+    }
+
+    /// Fill the block with `content`, generated from `seed`
+    ///
+    /// The checksum is kept so [`FoodBlock::verify`] can tell if the
+    /// contents change later. Writing every byte also touches every page.
+    pub fn fill(&mut self, content: FoodContent, seed: u64) {
+        let region = self.region.as_mut_slice();
+        let mut checksum = CHECKSUM_BASIS;
+        for (index, chunk) in region.chunks_mut(8).enumerate() {
+            let word = content_word(content, seed, index).to_le_bytes();
+            chunk.copy_from_slice(&word[..chunk.len()]);
+            checksum = fold_checksum(checksum, chunk);
+        }
+        self.fill = Some(Fill { content, seed, checksum });
+        self.touched = true;
+    }
+
+    /// What the block was filled with, if it was
+    pub fn content(&self) -> Option<FoodContent> {
+        self.fill.map(|fill| fill.content)
+    }
+
+    /// Whether the block holds a fill pattern that can be verified
+    pub fn is_filled(&self) -> bool {
+        self.fill.is_some()
+    }
+
+    /// Check the contents against the fill pattern
+    ///
+    /// Returns the first corrupted byte, or `None` if the block is intact
+    /// (or was never filled). Only a checksum mismatch pays for finding
+    /// the offset.
+    pub fn verify(&self) -> Option<Corruption> {
+        let fill = self.fill?;
+        let region = self.region.as_slice();
+        let checksum = region.chunks(8).fold(CHECKSUM_BASIS, fold_checksum);
+        if checksum == fill.checksum {
+            return None;
+        }
+
+        region.chunks(8).enumerate().find_map(|(index, chunk)| {
+            let expected = content_word(fill.content, fill.seed, index).to_le_bytes();
+            let at = chunk.iter().zip(expected).position(|(found, expected)| *found != expected)?;
+            Some(Corruption {
+                offset: index * 8 + at,
+                expected: expected[at],
+                found: chunk[at],
+            })
+        })
+    }
+
+    /// Address of the block's first byte, for reporting bad memory
+    pub fn address(&self) -> usize {
+        self.region.as_slice().as_ptr() as usize
+    }
+
+    /// Whether the block has been touched, so it should be resident
+    /// unless the kernel swapped it out
+    pub fn is_touched(&self) -> bool {
+        self.touched
+    }
+
+    /// How much of the block is actually in RAM, if the platform can tell
+    pub fn resident(&self) -> Option<Bytes> {
+        #[cfg(target_os = "linux")]
+        {
+            linux::resident(self.region.as_slice())
+                .map_err(|e| log::debug!("mincore failed: {}", e))
+                .ok()
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            None
+        }
+    }
+
+    /// Pin the block in RAM so it can't be swapped out
+    pub fn lock(&mut self) -> Result<()> {
+        if self.locked {
+            return Ok(());
+        }
+        #[cfg(target_os = "linux")]
+        {
+            linux::lock(self.region.as_slice())?;
+            self.locked = true;
+            Ok(())
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            Err(anyhow!("locking memory is only available on Linux"))
+        }
+    }
+
+    /// Let the block be swapped out again
+    pub fn unlock(&mut self) {
+        if self.locked {
+            #[cfg(target_os = "linux")]
+            linux::unlock(self.region.as_slice());
+            self.locked = false;
+        }
+    }
+
+    /// Whether the block is pinned in RAM
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+}
+
+impl Drop for FoodBlock {
+    fn drop(&mut self) {
+        // Heap blocks aren't necessarily unmapped when freed, so a lock
+        // would outlive them
+        self.unlock();
+    }
+}
+
+/// FNV-1a offset basis, the starting value of a block checksum
+const CHECKSUM_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// The `index`th 8-byte word of the fill pattern for `seed` (SplitMix64)
+///
+/// Each word is computed on its own so a mismatch can be located without
+/// replaying the whole block.
+fn fill_word(seed: u64, index: usize) -> u64 {
+    let mut z = seed.wrapping_add((index as u64 + 1).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Eight-byte words that text-like food is made of
+const VOCABULARY: [&[u8; 8]; 16] = [
+    b"the pet ", b"eats ram", b" nom nom", b"bytes & ",
+    b"memory, ", b"a cache ", b"of heap ", b"and page",
+    b"s, then ", b"sleeps. ", b"\nfree() ", b"hungry! ",
+    b"malloc( ", b"4096);\n ", b"yum yum ", b"more... ",
+];
+
+/// The `index`th 8-byte word of `content` generated from `seed`
+fn content_word(content: FoodContent, seed: u64, index: usize) -> u64 {
+    match content {
+        FoodContent::Zeros => 0,
+        // 64 bytes long, so every page of a block looks the same
+        FoodContent::Pattern => fill_word(seed, index % 8),
+        FoodContent::Text => {
+            let word = VOCABULARY[(fill_word(seed, index) % VOCABULARY.len() as u64) as usize];
+            u64::from_le_bytes(*word)
+        }
+        FoodContent::Random => fill_word(seed, index),
+    }
+}
+
+/// Fold up to 8 bytes into a running FNV-1a style checksum, a word at a time
+fn fold_checksum(checksum: u64, chunk: &[u8]) -> u64 {
+    let mut word = [0u8; 8];
+    word[..chunk.len()].copy_from_slice(chunk);
+    (checksum ^ u64::from_le_bytes(word)).wrapping_mul(0x0000_0100_0000_01b3)
+}
+
+/// Size of a memory page in bytes
+pub fn page_size() -> usize {
+    #[cfg(target_os = "linux")]
+    {
+        // SAFETY: sysconf has no preconditions
+        let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+        if size > 0 {
+            return size as usize;
+        }
+    }
+    4096
+}
+
+/// How much memory this process may lock (`RLIMIT_MEMLOCK`)
+///
+/// `None` means there is no limit.
+pub fn memlock_limit() -> Option<Bytes> {
+    #[cfg(target_os = "linux")]
+    {
+        linux::memlock_limit()
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        Some(Bytes(0))
+    }
+}
+
+/// Build the food source for a kind
+///
+/// `food_dir` is only used by [`FoodKind::File`].
+pub fn food_source(kind: FoodKind, food_dir: &Path) -> Result<Arc<dyn FoodSource>> {
+    match kind {
+        FoodKind::Heap => Ok(Arc::new(HeapSource)),
+        #[cfg(target_os = "linux")]
+        FoodKind::Mmap => Ok(Arc::new(linux::MmapSource)),
+        #[cfg(target_os = "linux")]
+        FoodKind::Memfd => Ok(Arc::new(linux::MemfdSource)),
+        #[cfg(target_os = "linux")]
+        FoodKind::File => Ok(Arc::new(linux::FileSource::new(food_dir)?)),
+        #[cfg(target_os = "linux")]
+        FoodKind::HugePage => Ok(Arc::new(linux::HugePageSource)),
+        #[cfg(not(target_os = "linux"))]
+        other => {
+            let _ = food_dir;
+            Err(anyhow!("the {} food source is only available on Linux", other))
+        }
+    }
+}
+
+/// Plain heap blocks from the global allocator
+pub struct HeapSource;
+
+struct HeapBlock(Box<[u8]>);
+
+impl MemoryBlock for HeapBlock {
+    fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl FoodSource for HeapSource {
+    fn kind(&self) -> FoodKind {
+        FoodKind::Heap
+    }
+
+    fn allocate_block(&self) -> Result<Box<dyn MemoryBlock>> {
+        let len = self.block_size().to_bytes().0 as usize;
+        Ok(Box::new(HeapBlock(vec![0u8; len].into_boxed_slice())))
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::*;
+    use anyhow::Context;
+    use std::fs::OpenOptions;
+    use std::io;
+    use std::os::fd::AsRawFd;
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    /// Bytes of `region` resident in RAM, according to `mincore`
+    pub fn resident(region: &[u8]) -> io::Result<Bytes> {
+        let page = page_size();
+        let start = region.as_ptr() as usize;
+        let aligned = start & !(page - 1);
+        let len = start + region.len() - aligned;
+
+        let mut pages = vec![0u8; len.div_ceil(page)];
+        // SAFETY: `aligned..aligned + len` covers the live region (mincore
+        // needs a page aligned start) and `pages` has one byte per page
+        let rc = unsafe { libc::mincore(aligned as *mut libc::c_void, len, pages.as_mut_ptr()) };
+        if rc != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let resident = pages.iter().filter(|&&page| page & 1 != 0).count();
+        Ok(Bytes((resident * page).min(region.len()) as u64))
+    }
+
+    /// `mlock` a region
+    pub fn lock(region: &[u8]) -> Result<()> {
+        // SAFETY: locking a live region we own
+        if unsafe { libc::mlock(region.as_ptr().cast(), region.len()) } != 0 {
+            return Err(io::Error::last_os_error()).context("mlock failed");
+        }
+        Ok(())
+    }
+
+    /// `munlock` a region
+    pub fn unlock(region: &[u8]) {
+        // SAFETY: unlocking a live region we own
+        unsafe {
+            libc::munlock(region.as_ptr().cast(), region.len());
+        }
+    }
+
+    /// Soft `RLIMIT_MEMLOCK`, or `None` if unlimited
+    pub fn memlock_limit() -> Option<Bytes> {
+        let mut limit = libc::rlimit { rlim_cur: 0, rlim_max: 0 };
+        // SAFETY: `limit` is a valid out pointer
+        if unsafe { libc::getrlimit(libc::RLIMIT_MEMLOCK, &mut limit) } != 0 {
+            return Some(Bytes(0));
+        }
+        if limit.rlim_cur == libc::RLIM_INFINITY {
+            None
+        } else {
+            Some(Bytes(limit.rlim_cur))
+        }
+    }
+
+    /// Huge page size used for THP-advised regions
+    const HUGE_PAGE: usize = 2 * 1024 * 1024;
+
+    /// A `mmap`ed region, unmapped on drop
+    struct MappedRegion {
+        ptr: *mut u8,
+        len: usize,
+    }
+
+    // The region is exclusively owned; nothing else holds the pointer
+    unsafe impl Send for MappedRegion {}
+
+    impl MappedRegion {
+        /// Map `len` bytes, shared over `fd` or anonymous/private if `None`
+        fn map(len: usize, fd: Option<i32>) -> io::Result<Self> {
+            let (flags, fd) = match fd {
+                Some(fd) => (libc::MAP_SHARED, fd),
+                None => (libc::MAP_PRIVATE | libc::MAP_ANONYMOUS, -1),
+            };
+            // SAFETY: a fresh mapping with no address hint; checked below
+            let ptr = unsafe {
+                libc::mmap(
+                    std::ptr::null_mut(),
+                    len,
+                    libc::PROT_READ | libc::PROT_WRITE,
+                    flags,
+                    fd,
+                    0,
+                )
+            };
+            if ptr == libc::MAP_FAILED {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(MappedRegion { ptr: ptr.cast(), len })
+        }
+    }
+
+    impl MemoryBlock for MappedRegion {
+        fn as_slice(&self) -> &[u8] {
+            // SAFETY: ptr/len describe a live readable mapping we own
+            unsafe { std::slice::from_raw_parts(self.ptr, self.len) }
+        }
+
+        fn as_mut_slice(&mut self) -> &mut [u8] {
+            // SAFETY: ptr/len describe a live writable mapping we own
+            unsafe { std::slice::from_raw_parts_mut(self.ptr, self.len) }
+        }
+    }
+
+    impl Drop for MappedRegion {
+        fn drop(&mut self) {
+            // SAFETY: unmapping exactly the region we mapped
+            unsafe {
+                libc::munmap(self.ptr.cast(), self.len);
+            }
+        }
+    }
+
+    /// Anonymous private mappings
+    pub struct MmapSource;
+
+    impl FoodSource for MmapSource {
+        fn kind(&self) -> FoodKind {
+            FoodKind::Mmap
+        }
+
+        fn allocate_block(&self) -> Result<Box<dyn MemoryBlock>> {
+            let len = self.block_size().to_bytes().0 as usize;
+            let region = MappedRegion::map(len, None).context("anonymous mmap failed")?;
+            Ok(Box::new(region))
+        }
+    }
+
+    /// Mappings of anonymous memfd files
+    pub struct MemfdSource;
+
+    impl FoodSource for MemfdSource {
+        fn kind(&self) -> FoodKind {
+            FoodKind::Memfd
+        }
+
+        fn allocate_block(&self) -> Result<Box<dyn MemoryBlock>> {
+            let len = self.block_size().to_bytes().0 as usize;
+
+            // SAFETY: name is a valid NUL-terminated string
+            let fd = unsafe { libc::memfd_create(c"ram_pet_food".as_ptr(), libc::MFD_CLOEXEC) };
+            if fd < 0 {
+                return Err(io::Error::last_os_error()).context("memfd_create failed");
+            }
+
+            // SAFETY: fd is a memfd we just created and own
+            let result = unsafe {
+                if libc::ftruncate(fd, len as libc::off_t) != 0 {
+                    Err(io::Error::last_os_error()).context("failed to size memfd")
+                } else {
+                    MappedRegion::map(len, Some(fd)).context("failed to map memfd")
+                }
+            };
+
+            // The mapping keeps the memory alive once the fd is closed
+            // SAFETY: closing the fd we own
+            unsafe {
+                libc::close(fd);
+            }
+
+            Ok(Box::new(result?))
+        }
+    }
+
+    /// Files created so far by any [`FileSource`] in this process
+    ///
+    /// Process-wide so a fresh source never reuses the name of a file an
+    /// earlier one still has mapped.
+    static FOOD_FILES: AtomicU64 = AtomicU64::new(0);
+
+    /// Shared mappings of files in a directory
+    ///
+    /// Each file is deleted as soon as it's mapped, so nothing is left
+    /// behind in the directory (often a tmpfs) however the process exits.
+    /// The mapping keeps the pages, which still count as Shmem and against
+    /// the cgroup's file memory.
+    pub struct FileSource {
+        dir: PathBuf,
+    }
+
+    impl FileSource {
+        /// Serve food from files created in `dir`
+        pub fn new(dir: &Path) -> Result<Self> {
+            if !dir.is_dir() {
+                return Err(anyhow!("food directory {} does not exist", dir.display()));
+            }
+            Ok(FileSource { dir: dir.to_path_buf() })
+        }
+    }
+
+    impl FoodSource for FileSource {
+        fn kind(&self) -> FoodKind {
+            FoodKind::File
+        }
+
+        fn allocate_block(&self) -> Result<Box<dyn MemoryBlock>> {
+            let len = self.block_size().to_bytes().0 as usize;
+            let path = self.dir.join(format!(
+                "ram_pet-{}-{}.food",
+                std::process::id(),
+                FOOD_FILES.fetch_add(1, Ordering::Relaxed)
+            ));
+
+            let file = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create_new(true)
+                .open(&path)
+                .with_context(|| format!("failed to create {}", path.display()))?;
+
+            let mapped = file.set_len(len as u64)
+                .with_context(|| format!("failed to size {}", path.display()))
+                .and_then(|_| {
+                    MappedRegion::map(len, Some(file.as_raw_fd()))
+                        .with_context(|| format!("failed to map {}", path.display()))
+                });
+
+            if let Err(e) = std::fs::remove_file(&path) {
+                log::warn!("Couldn't delete food file {}: {}", path.display(), e);
+            }
+            Ok(Box::new(mapped?))
+        }
+    }
+
+    /// 2 MB aligned anonymous regions advised for transparent huge pages
+    pub struct HugePageSource;
+
+    impl FoodSource for HugePageSource {
+        fn kind(&self) -> FoodKind {
+            FoodKind::HugePage
+        }
+
+        fn block_size(&self) -> MiB {
+            MiB(HUGE_PAGE / (1024 * 1024))
+        }
+
+        fn allocate_block(&self) -> Result<Box<dyn MemoryBlock>> {
+            // Over-map so a huge page aligned window is guaranteed to fit
+            let mut region = MappedRegion::map(HUGE_PAGE * 2, None)
+                .context("anonymous mmap for huge page failed")?;
+
+            let start = region.ptr as usize;
+            let aligned = (start + HUGE_PAGE - 1) & !(HUGE_PAGE - 1);
+            let head = aligned - start;
+            let tail = region.len - head - HUGE_PAGE;
+
+            // SAFETY: trimming the unaligned head and tail of our own mapping,
+            // then advising the aligned window that remains
+            unsafe {
+                if head > 0 {
+                    libc::munmap(region.ptr.cast(), head);
+                }
+                if tail > 0 {
+                    libc::munmap((aligned + HUGE_PAGE) as *mut libc::c_void, tail);
+                }
+                region.ptr = aligned as *mut u8;
+                region.len = HUGE_PAGE;
+
+                if libc::madvise(region.ptr.cast(), region.len, libc::MADV_HUGEPAGE) != 0 {
+                    log::debug!("MADV_HUGEPAGE refused: {}", io::Error::last_os_error());
+                }
+            }
+
+            Ok(Box::new(region))
+        }
+    }
+}
+
+/// Heap food that serves a few blocks, then stalls until let go
+#[cfg(test)]
+pub(crate) struct StallingSource {
+    served: std::sync::atomic::AtomicUsize,
+    before_stall: usize,
+    release: std::sync::Barrier,
+}
+
+#[cfg(test)]
+impl StallingSource {
+    /// Serve `before_stall` blocks, then block on the next one
+    pub fn new(before_stall: usize) -> Self {
+        StallingSource {
+            served: std::sync::atomic::AtomicUsize::new(0),
+            before_stall,
+            release: std::sync::Barrier::new(2),
+        }
+    }
+
+    /// Let the stalled allocation through (waits for it to arrive)
+    pub fn release(&self) {
+        self.release.wait();
+    }
+}
+
+#[cfg(test)]
+impl FoodSource for StallingSource {
+    fn kind(&self) -> FoodKind {
+        FoodKind::Heap
+    }
+
+    fn allocate_block(&self) -> Result<Box<dyn MemoryBlock>> {
+        if self.served.fetch_add(1, std::sync::atomic::Ordering::SeqCst) == self.before_stall {
+            self.release.wait();
+        }
+        HeapSource.allocate_block()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_food_kind_names() {
+        for kind in FoodKind::ALL {
+            assert_eq!(kind.name().parse::<FoodKind>().unwrap(), kind);
+        }
+        assert_eq!(FoodKind::HugePage.next(), FoodKind::Heap);
+        assert!("caviar".parse::<FoodKind>().is_err());
+    }
+
+    #[test]
+    fn test_heap_block() {
+        let mut block = FoodBlock::from_source(&HeapSource).unwrap();
+        assert_eq!(block.size(), MiB(1));
+        assert_eq!(block.kind(), FoodKind::Heap);
+        block.as_mut_slice()[0] = 42;
+        assert_eq!(block.as_slice()[0], 42);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_linux_sources() {
+        let dir = std::env::temp_dir();
+        for kind in FoodKind::ALL {
+            let source = food_source(kind, &dir).unwrap();
+            let mut block = FoodBlock::from_source(source.as_ref()).unwrap();
+            assert_eq!(block.size(), source.block_size());
+            block.as_mut_slice()[4096] = 7;
+            assert_eq!(block.as_slice()[4096], 7);
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_touch_makes_block_resident() {
+        let mut block = FoodBlock::from_source(&linux::MmapSource).unwrap();
+        block.as_mut_slice()[0] = 9;
+
+        // Fresh anonymous memory is only mapped in as it's touched
+        assert!(block.resident().unwrap() < block.size().to_bytes());
+        block.touch();
+        assert_eq!(block.resident().unwrap(), block.size().to_bytes());
+        assert_eq!(block.as_slice()[0], 9);
+    }
+
+    #[test]
+    fn test_fill_and_verify() {
+        let mut block = FoodBlock::from_source(&HeapSource).unwrap();
+        assert_eq!(block.verify(), None); // Nothing to check yet
+
+        block.fill(FoodContent::Random, 7);
+        assert!(block.is_filled());
+        block.touch();
+        assert_eq!(block.verify(), None);
+
+        let expected = block.as_slice()[5000];
+        block.as_mut_slice()[5000] ^= 0x10;
+        assert_eq!(block.verify(), Some(Corruption {
+            offset: 5000,
+            expected,
+            found: expected ^ 0x10,
+        }));
+    }
+
+    #[test]
+    fn test_content_profiles() {
+        let mut block = FoodBlock::from_source(&HeapSource).unwrap();
+        for content in FoodContent::ALL {
+            assert_eq!(content.name().parse::<FoodContent>().unwrap(), content);
+
+            block.fill(content, 42);
+            assert_eq!(block.content(), Some(content));
+            assert_eq!(block.verify(), None);
+
+            let bytes = block.as_slice();
+            let distinct = (0..=255u8).filter(|b| bytes.contains(b)).count();
+            match content {
+                FoodContent::Zeros => assert_eq!(distinct, 1),
+                FoodContent::Pattern => assert_eq!(bytes[..64], bytes[4096..4160]),
+                FoodContent::Text => assert!(bytes.is_ascii()),
+                FoodContent::Random => assert_eq!(distinct, 256),
+            }
+        }
+    }
+}
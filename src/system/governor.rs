@@ -0,0 +1,199 @@
+// src/system/governor.rs
+// RAM Eating Pet Simulator - Safety Governor
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use super::memory::{SharedBlocks, SharedReleases};
+use super::sampler::SnapshotHandle;
+use super::units::MiB;
+
+/// Something the governor did behind the game's back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GovernorEvent {
+    /// Blocks were released because the host was running low
+    Vomited {
+        /// How much was given back
+        released: MiB,
+        /// Free RAM the governor saw before releasing
+        available: MiB,
+    },
+}
+
+/// Watchdog thread that gives RAM back when the host (or our cgroup) runs low
+///
+/// It holds its own handle to the pet's blocks and reads the shared
+/// snapshots, so it keeps working even if the UI loop is stuck. The game
+/// learns what happened through [`SafetyGovernor::events`], and the memory
+/// manager through the shared releases.
+pub struct SafetyGovernor {
+    events: Receiver<GovernorEvent>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl SafetyGovernor {
+    /// Start watching, checking every `interval`
+    pub fn spawn(
+        blocks: SharedBlocks,
+        releases: SharedReleases,
+        snapshots: SnapshotHandle,
+        min_free: MiB,
+        interval: Duration,
+    ) -> Self {
+        let (sender, events) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread = {
+            let stop = stop.clone();
+            let mut watchdog = Watchdog::new(blocks, releases, snapshots, min_free);
+            thread::Builder::new()
+                .name("safety-governor".to_string())
+                .spawn(move || watchdog.run(interval, &stop, sender))
+                .expect("failed to spawn safety governor thread")
+        };
+
+        SafetyGovernor {
+            events,
+            stop,
+            thread: Some(thread),
+        }
+    }
+
+    /// Events since the last call
+    pub fn events(&self) -> impl Iterator<Item = GovernorEvent> + '_ {
+        self.events.try_iter()
+    }
+}
+
+impl Drop for SafetyGovernor {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            thread.thread().unpark();
+            let _ = thread.join();
+        }
+    }
+}
+
+/// The governor's state, kept apart from the thread so it can be tested
+struct Watchdog {
+    blocks: SharedBlocks,
+    /// RAM released since the snapshot taken at the given time, shared with
+    /// the memory manager
+    released_since: SharedReleases,
+    snapshots: SnapshotHandle,
+    min_free: MiB,
+}
+
+impl Watchdog {
+    fn new(blocks: SharedBlocks, released_since: SharedReleases, snapshots: SnapshotHandle, min_free: MiB) -> Self {
+        Watchdog {
+            blocks,
+            released_since,
+            snapshots,
+            min_free,
+        }
+    }
+
+    fn run(&mut self, interval: Duration, stop: &AtomicBool, events: Sender<GovernorEvent>) {
+        while !stop.load(Ordering::Relaxed) {
+            thread::park_timeout(interval);
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
+            if let Some(event) = self.check() {
+                log::warn!("Safety governor: {:?}", event);
+                // The game may already be gone; the RAM is freed either way
+                let _ = events.send(event);
+            }
+        }
+    }
+
+    /// Release enough blocks to get back above the minimum, if needed
+    fn check(&mut self) -> Option<GovernorEvent> {
+        let snapshot = self.snapshots.latest();
+        let already_released = {
+            let mut released_since = self.released_since.lock().unwrap();
+            if snapshot.taken_at > released_since.0 {
+                *released_since = (snapshot.taken_at, MiB(0));
+            }
+            released_since.1
+        };
+
+        // Don't release the same shortfall twice before the sampler catches up
+        let available = snapshot.budget().available.to_mib() + already_released;
+        if available >= self.min_free {
+            return None;
+        }
+
+        let shortfall = self.min_free - available;
+        let mut blocks = self.blocks.lock().unwrap();
+        let mut released = MiB(0);
+        while released < shortfall {
+            match blocks.pop() {
+                Some(block) => released += block.size(),
+                None => break,
+            }
+        }
+        drop(blocks);
+
+        if released.is_zero() {
+            return None;
+        }
+        self.released_since.lock().unwrap().1 += released;
+        Some(GovernorEvent::Vomited { released, available })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::food::{FoodBlock, HeapSource};
+    use crate::system::sampler::SystemSnapshot;
+    use std::sync::Mutex;
+    use std::time::Instant;
+
+    fn snapshot_with_available(available: MiB) -> SnapshotHandle {
+        SnapshotHandle::fixed(SystemSnapshot {
+            available: available.to_bytes(),
+            ..Default::default()
+        })
+    }
+
+    fn released() -> SharedReleases {
+        Arc::new(Mutex::new((Instant::now(), MiB(0))))
+    }
+
+    fn blocks(count: usize) -> SharedBlocks {
+        let blocks = (0..count).map(|_| FoodBlock::from_source(&HeapSource).unwrap()).collect();
+        Arc::new(Mutex::new(blocks))
+    }
+
+    #[test]
+    fn test_releases_shortfall_once() {
+        let blocks = blocks(10);
+        let mut watchdog = Watchdog::new(blocks.clone(), released(), snapshot_with_available(MiB(96)), MiB(100));
+
+        assert_eq!(watchdog.check(), Some(GovernorEvent::Vomited {
+            released: MiB(4),
+            available: MiB(96),
+        }));
+        assert_eq!(blocks.lock().unwrap().len(), 6);
+
+        // The stale snapshot still says 96 MB, but we know we freed 4 MB
+        assert_eq!(watchdog.check(), None);
+    }
+
+    #[test]
+    fn test_leaves_pet_alone_with_room_to_spare() {
+        let blocks = blocks(2);
+        let mut watchdog = Watchdog::new(blocks.clone(), released(), snapshot_with_available(MiB(500)), MiB(100));
+
+        assert_eq!(watchdog.check(), None);
+        assert_eq!(blocks.lock().unwrap().len(), 2);
+    }
+}
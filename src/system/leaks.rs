@@ -0,0 +1,272 @@
+// src/system/leaks.rs
+// RAM Eating Pet Simulator - Memory Leak Detector
+
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use super::monitor::RamTracker;
+use super::sampler::{FreshSnapshots, SystemSnapshot};
+use super::units::MiB;
+
+/// How far below its peak in the window a process may dip (e.g. a garbage
+/// collection) and still count as growing steadily
+const LEAK_DIP: f64 = 0.02;
+
+/// Process table samples taken per window; the full table is expensive,
+/// and a leak takes minutes to show anyway
+const SAMPLES_PER_WINDOW: u32 = 20;
+
+/// A process being watched for leaks, as of the last sample
+#[derive(Debug, Clone)]
+pub struct WatchedProcess {
+    pub pid: u32,
+    pub name: String,
+    /// Current RSS
+    pub size: MiB,
+    /// RSS change over the samples we have
+    pub growth: i64,
+    /// Time those samples cover
+    pub over: Duration,
+    /// RSS at each sample, oldest first
+    pub history: Vec<MiB>,
+    /// Whether it looks like it's leaking
+    pub suspect: bool,
+}
+
+impl WatchedProcess {
+    /// The history squeezed into `width` averaged points, for a sparkline
+    pub fn sparkline_points(&self, width: usize) -> Vec<f32> {
+        if width == 0 || self.history.len() <= width {
+            return self.history.iter().map(|size| size.0 as f32).collect();
+        }
+        (0..width)
+            .map(|i| {
+                let bucket = &self.history[i * self.history.len() / width..(i + 1) * self.history.len() / width];
+                bucket.iter().map(|size| size.0 as f32).sum::<f32>() / bucket.len() as f32
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for WatchedProcess {
+    /// E.g. "node (pid 4121) +300 MB in 10 min"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.over.as_secs();
+        let over = if secs >= 60 { format!("{} min", secs / 60) } else { format!("{} s", secs) };
+        write!(f, "{} (pid {}) {:+} MB in {}", self.name, self.pid, self.growth, over)
+    }
+}
+
+/// RSS history for one process
+struct History {
+    name: String,
+    usage: RamTracker,
+    suspect: bool,
+}
+
+/// Keeps RSS history for the biggest processes and flags the ones that
+/// grow steadily
+///
+/// A process is a suspect once its history covers the whole window, it has
+/// grown at least `slope` MB a minute over it, and it never dipped far
+/// below its running peak on the way. We never watch ourselves: the pet
+/// growing is the point.
+pub struct LeakDetector {
+    watch: usize,
+    window: Duration,
+    /// Shortest history that counts as covering the window, allowing for
+    /// samples arriving a little late
+    full: Duration,
+    /// MB per minute that counts as leaking
+    slope: f32,
+    /// How often the process table is sampled
+    interval: Duration,
+    samples: usize,
+    processes: HashMap<u32, History>,
+    fresh: FreshSnapshots,
+}
+
+impl LeakDetector {
+    /// Watch the `watch` biggest processes
+    ///
+    /// The process table is sampled every `interval` at most, and less
+    /// often if the window is long enough (every 30 s for 10 minutes).
+    pub fn new(watch: usize, window: Duration, slope: f32, interval: Duration) -> Self {
+        let interval = (window / SAMPLES_PER_WINDOW).max(interval);
+        let samples = (window.as_secs_f64() / interval.as_secs_f64().max(0.001)).ceil() as usize + 1;
+        LeakDetector {
+            watch,
+            window,
+            full: window.saturating_sub(interval / 2),
+            slope,
+            interval,
+            samples: samples.max(2),
+            processes: HashMap::new(),
+            fresh: FreshSnapshots::default(),
+        }
+    }
+
+    /// Whether the next sample is due, so the process table is needed
+    pub fn wants_table(&self, now: Instant) -> bool {
+        self.fresh.last().is_none_or(|last| now.duration_since(last) >= self.interval)
+    }
+
+    /// Record a new snapshot, returning processes that just became suspects
+    ///
+    /// Snapshots without the process table, already seen or taken before
+    /// the next sample is due are ignored.
+    pub fn observe(&mut self, snapshot: &SystemSnapshot) -> Vec<WatchedProcess> {
+        if !self.wants_table(snapshot.taken_at) {
+            return Vec::new();
+        }
+        let table = match self.fresh.processes(snapshot) {
+            Some(table) => table,
+            None => return Vec::new(),
+        };
+
+        let ours = std::process::id();
+        for process in table.iter().filter(|process| process.pid != ours).take(self.watch) {
+            let history = self.processes.entry(process.pid).or_insert_with(|| History {
+                name: process.name.clone(),
+                usage: RamTracker::new(self.samples),
+                suspect: false,
+            });
+            // A reused PID is a different process
+            if history.name != process.name {
+                history.name = process.name.clone();
+                history.usage = RamTracker::new(self.samples);
+                history.suspect = false;
+            }
+            history.usage.record_usage(snapshot.taken_at, process.memory_mb);
+        }
+
+        // Forget processes that have been out of the top for a whole window
+        let window = self.window;
+        self.processes.retain(|_, history| {
+            history.usage.samples().last()
+                .is_some_and(|(at, _)| snapshot.taken_at.duration_since(*at) <= window)
+        });
+
+        let mut found = Vec::new();
+        for (pid, history) in self.processes.iter_mut() {
+            let leaking = is_leaking(history.usage.samples(), self.full, self.slope);
+            if leaking && !history.suspect {
+                found.push(describe(*pid, history, true));
+            }
+            history.suspect = leaking;
+        }
+        found
+    }
+
+    /// Everything being watched, suspects first, then biggest first
+    pub fn watched(&self) -> Vec<WatchedProcess> {
+        let mut watched: Vec<WatchedProcess> = self.processes.iter()
+            .map(|(pid, history)| describe(*pid, history, history.suspect))
+            .collect();
+        watched.sort_by_key(|process| (!process.suspect, std::cmp::Reverse(process.size)));
+        watched
+    }
+}
+
+fn describe(pid: u32, history: &History, suspect: bool) -> WatchedProcess {
+    let samples = history.usage.samples();
+    let over = match (samples.first(), samples.last()) {
+        (Some((first, _)), Some((last, _))) => last.duration_since(*first),
+        _ => Duration::ZERO,
+    };
+    WatchedProcess {
+        pid,
+        name: history.name.clone(),
+        size: samples.last().map_or(MiB(0), |(_, size)| *size),
+        growth: history.usage.get_trend(),
+        over,
+        history: samples.iter().map(|(_, size)| *size).collect(),
+        suspect,
+    }
+}
+
+/// Whether a history at least `full` long shows sustained growth of at
+/// least `slope` MB a minute
+fn is_leaking(samples: &[(Instant, MiB)], full: Duration, slope: f32) -> bool {
+    let (first, last) = match (samples.first(), samples.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return false,
+    };
+    let span = last.0.duration_since(first.0);
+    if span.is_zero() || span < full {
+        return false;
+    }
+
+    let rate = last.1.delta(first.1) as f64 / span.as_secs_f64() * 60.0;
+    if rate < slope as f64 {
+        return false;
+    }
+
+    let mut peak = MiB(0);
+    samples.iter().all(|(_, size)| {
+        peak = peak.max(*size);
+        size.0 as f64 >= peak.0 as f64 * (1.0 - LEAK_DIP)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::monitor::ProcessInfo;
+
+    fn snapshot(start: Instant, secs: u64, table: &[(u32, &str, usize)]) -> SystemSnapshot {
+        let table = table.iter()
+            .map(|&(pid, name, mb)| ProcessInfo { pid, name: name.to_string(), memory_mb: MiB(mb), uid: None, user: None })
+            .collect();
+        SystemSnapshot::fake(start + Duration::from_secs(secs), table)
+    }
+
+    #[test]
+    fn test_flags_steady_growth_once() {
+        // Ten minute window, sampled once a minute, 10 MB/min counts as leaking
+        let mut detector = LeakDetector::new(2, Duration::from_secs(600), 10.0, Duration::from_secs(60));
+        let start = Instant::now();
+
+        let mut flagged = Vec::new();
+        for minute in 0..=10 {
+            let table = [(4121, "node", 100 + minute * 30), (7, "postgres", 500), (9, "tiny", 1)];
+            flagged.extend(detector.observe(&snapshot(start, minute as u64 * 60, &table)));
+        }
+
+        assert_eq!(flagged.len(), 1);
+        assert_eq!(flagged[0].to_string(), "node (pid 4121) +300 MB in 10 min");
+
+        // Still leaking, but already reported
+        let table = [(4121, "node", 430), (7, "postgres", 500)];
+        assert!(detector.observe(&snapshot(start, 660, &table)).is_empty());
+
+        // Samples come no faster than once a minute, whoever asked for the table
+        assert!(!detector.wants_table(start + Duration::from_secs(690)));
+        detector.observe(&snapshot(start, 690, &[(4121, "node", 999)]));
+        assert_eq!(detector.watched()[0].size, MiB(430));
+        assert!(detector.wants_table(start + Duration::from_secs(720)));
+
+        let watched = detector.watched();
+        assert_eq!(watched.len(), 2);
+        assert!(watched[0].suspect && !watched[1].suspect);
+        assert_eq!(watched[0].sparkline_points(4).len(), 4);
+    }
+
+    #[test]
+    fn test_is_leaking() {
+        let start = Instant::now();
+        let series = |sizes: &[usize]| -> Vec<(Instant, MiB)> {
+            sizes.iter().enumerate()
+                .map(|(i, &mb)| (start + Duration::from_secs(i as u64 * 60), MiB(mb)))
+                .collect()
+        };
+        let window = Duration::from_secs(240);
+
+        assert!(is_leaking(&series(&[100, 120, 140, 160, 180]), window, 10.0));
+        // Too slow, too short, or it gave memory back along the way
+        assert!(!is_leaking(&series(&[100, 105, 110, 115, 120]), window, 10.0));
+        assert!(!is_leaking(&series(&[100, 120, 140]), window, 10.0));
+        assert!(!is_leaking(&series(&[100, 160, 100, 160, 180]), window, 10.0));
+    }
+}
@@ -2,158 +2,679 @@
 // RAM Eating Pet Simulator - Memory Management
 
 use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use super::food::{food_source, memlock_limit, FoodBlock, FoodContent, FoodKind, FoodSource};
+use super::sampler::{SnapshotHandle, SystemSnapshot};
+use super::units::{Bytes, MiB};
+
+/// The pet's blocks, shared with anything that may release them
+pub type SharedBlocks = Arc<Mutex<Vec<FoodBlock>>>;
+
+/// RAM released from the shared blocks behind the memory manager's back,
+/// since the snapshot taken at the given time
+pub type SharedReleases = Arc<Mutex<(Instant, MiB)>>;
+
+/// How often the periodic re-touch policy walks the pet's memory
+pub const RETOUCH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// How hard the memory manager works to keep the pet in real RAM
+///
+/// Freshly allocated memory is often just mapped zero pages that don't
+/// count towards RSS until written, so a "lazy" pet looks smaller in `top`
+/// than it claims to be.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResidencyPolicy {
+    /// Leave pages for the kernel to map in whenever they're first used
+    Lazy,
+    /// Write to every page as soon as it's allocated
+    #[default]
+    Touch,
+    /// Touch on allocation and again every [`RETOUCH_INTERVAL`], pulling
+    /// back anything that was swapped out
+    Retouch,
+    /// Touch and `mlock` the memory, as far as `RLIMIT_MEMLOCK` allows
+    Mlock,
+}
+
+impl ResidencyPolicy {
+    /// Config name of the policy
+    pub fn name(&self) -> &'static str {
+        match self {
+            ResidencyPolicy::Lazy => "lazy",
+            ResidencyPolicy::Touch => "touch",
+            ResidencyPolicy::Retouch => "retouch",
+            ResidencyPolicy::Mlock => "mlock",
+        }
+    }
+}
+
+impl fmt::Display for ResidencyPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ResidencyPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "lazy" => Ok(ResidencyPolicy::Lazy),
+            "touch" => Ok(ResidencyPolicy::Touch),
+            "retouch" => Ok(ResidencyPolicy::Retouch),
+            "mlock" => Ok(ResidencyPolicy::Mlock),
+            _ => Err(anyhow!(
+                "unknown residency policy '{}' (expected lazy, touch, retouch or mlock)", s
+            )),
+        }
+    }
+}
+
+/// How much of the pet's memory is really in RAM
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Residency {
+    /// Memory allocated for the pet
+    pub virtual_size: Bytes,
+    /// Part of it resident in RAM, if the platform can tell
+    pub resident: Option<Bytes>,
+    /// Part of it locked with `mlock`
+    pub locked: Bytes,
+    /// Part of it that has been touched (and so ought to be resident)
+    pub touched: Bytes,
+    /// Touched memory that is no longer resident, i.e. swapped out
+    pub cold: Option<Bytes>,
+}
+
+impl Residency {
+    /// Share of the touched memory that has gone cold (0.0 - 1.0)
+    pub fn cold_fraction(&self) -> Option<f32> {
+        let cold = self.cold?;
+        if self.touched.0 == 0 {
+            Some(0.0)
+        } else {
+            Some(cold.0 as f32 / self.touched.0 as f32)
+        }
+    }
+}
 
 /// Manages actual RAM allocation for the pet
 pub struct MemoryManager {
-    /// Vector of allocated memory blocks (each element is 1MB)
-    allocated_blocks: Arc<Mutex<Vec<Box<[u8; 1_048_576]>>>>,
+    /// Allocated memory blocks (1MB each, 2MB for huge pages)
+    allocated_blocks: SharedBlocks,
     /// Minimum free RAM to maintain (MB)
-    min_free_ram: usize,
+    min_free_ram: MiB,
+    /// Shared system snapshots used for free-RAM checks
+    snapshots: SnapshotHandle,
+    /// Net MB allocated since the snapshot taken at the given time
+    unsampled: (Instant, i64),
+    /// Full targets of allocation jobs still running
+    reserved: MiB,
+    /// What the safety governor has released that the snapshots haven't seen
+    released_elsewhere: SharedReleases,
+    /// Food source used when none is given
+    food: FoodKind,
+    /// What synchronous allocations fill blocks with
+    content: FoodContent,
+    /// Directory for file-backed food
+    food_dir: PathBuf,
+    /// How allocated memory is kept resident
+    residency: ResidencyPolicy,
+    /// When the memory was last walked by the re-touch policy
+    last_touch: Instant,
+    /// Whether new blocks get a seeded fill pattern the scrubber can check
+    integrity: bool,
 }
 
 impl MemoryManager {
-    /// Create a new memory manager
-    pub fn new(min_free_ram_mb: usize) -> Self {
+    /// Create a new memory manager with its own one-off system snapshot
+    pub fn new(min_free_ram_mb: MiB) -> Self {
+        Self::with_snapshots(min_free_ram_mb, SnapshotHandle::fixed(SystemSnapshot::capture()))
+    }
+    
+    /// Create a new memory manager reading shared system snapshots
+    pub fn with_snapshots(min_free_ram_mb: MiB, snapshots: SnapshotHandle) -> Self {
+        let taken_at = snapshots.latest().taken_at;
         MemoryManager {
             allocated_blocks: Arc::new(Mutex::new(Vec::new())),
             min_free_ram: min_free_ram_mb,
+            snapshots,
+            unsampled: (taken_at, 0),
+            reserved: MiB(0),
+            released_elsewhere: Arc::new(Mutex::new((taken_at, MiB(0)))),
+            food: FoodKind::default(),
+            content: FoodContent::default(),
+            food_dir: std::env::temp_dir(),
+            residency: ResidencyPolicy::default(),
+            last_touch: Instant::now(),
+            integrity: false,
         }
     }
     
-    /// Allocate memory (in MB)
-    pub fn allocate(&mut self, amount_mb: usize) -> Result<()> {
+    /// Handle to the pet's blocks for the safety governor
+    pub fn shared_blocks(&self) -> SharedBlocks {
+        self.allocated_blocks.clone()
+    }
+    
+    /// Handle for the safety governor to report what it released
+    pub fn shared_releases(&self) -> SharedReleases {
+        self.released_elsewhere.clone()
+    }
+    
+    /// Set the food source used by [`MemoryManager::allocate`]
+    pub fn set_food(&mut self, kind: FoodKind) {
+        self.food = kind;
+    }
+    
+    /// Food source used by [`MemoryManager::allocate`]
+    pub fn food(&self) -> FoodKind {
+        self.food
+    }
+    
+    /// Fill blocks allocated from now on with a checkable pattern
+    ///
+    /// Zero food stays zeros, which can't show stuck-at-0 bits or most
+    /// addressing faults, so a checked pet's own memory should be given
+    /// [`FoodContent::Pattern`] with [`MemoryManager::set_content`]. A lazy
+    /// pet's zero blocks are left unwritten, since filling touches every page.
+    pub fn set_integrity(&mut self, enabled: bool) {
+        self.integrity = enabled;
+    }
+    
+    /// Set what [`MemoryManager::allocate`] and [`MemoryManager::allocate_from`]
+    /// fill blocks with
+    pub fn set_content(&mut self, content: FoodContent) {
+        self.content = content;
+    }
+    
+    /// What [`MemoryManager::allocate`] fills blocks with
+    pub fn content(&self) -> FoodContent {
+        self.content
+    }
+    
+    /// Set the directory file-backed food is created in
+    pub fn set_food_dir(&mut self, dir: impl Into<PathBuf>) {
+        self.food_dir = dir.into();
+    }
+    
+    /// Change the residency policy, applying it to memory already held
+    pub fn set_residency(&mut self, policy: ResidencyPolicy) {
+        self.residency = policy;
+        
+        let mut blocks = self.allocated_blocks.lock().unwrap();
+        if policy != ResidencyPolicy::Mlock {
+            blocks.iter_mut().for_each(FoodBlock::unlock);
+        }
+        
+        let mut lock_budget = Self::lock_budget(&blocks);
+        for block in blocks.iter_mut() {
+            Self::settle(block, policy, FoodContent::Zeros, self.integrity, &mut lock_budget);
+        }
+        self.last_touch = Instant::now();
+    }
+    
+    /// Current residency policy
+    pub fn residency_policy(&self) -> ResidencyPolicy {
+        self.residency
+    }
+    
+    /// Re-touch the pet's memory if the policy calls for it and it's due
+    pub fn maintain_residency(&mut self) {
+        if self.residency == ResidencyPolicy::Retouch
+            && self.last_touch.elapsed() >= RETOUCH_INTERVAL
+        {
+            let _ = self.touch_memory();
+        }
+    }
+    
+    /// Check how much of the pet's memory is really in RAM
+    pub fn residency(&self) -> Residency {
+        let blocks = self.allocated_blocks.lock().unwrap();
+        let mut report = Residency {
+            resident: Some(Bytes(0)),
+            cold: Some(Bytes(0)),
+            ..Default::default()
+        };
+        
+        for block in blocks.iter() {
+            let size = block.size().to_bytes();
+            let resident = block.resident();
+            
+            report.virtual_size += size;
+            if block.is_locked() {
+                report.locked += size;
+            }
+            report.resident = report.resident.zip(resident).map(|(a, b)| a + b);
+            
+            // Untouched blocks were never resident; only touched ones go cold
+            if block.is_touched() {
+                report.touched += size;
+                report.cold = report.cold.zip(resident).map(|(a, b)| a + size.saturating_sub(b));
+            }
+        }
+        report
+    }
+    
+    /// Touch the blocks that have been swapped out, returning how much was
+    /// brought back
+    pub fn warm_up(&mut self) -> MiB {
+        let mut blocks = self.allocated_blocks.lock().unwrap();
+        let mut warmed = MiB(0);
+        
+        for block in blocks.iter_mut() {
+            let size = block.size();
+            let cold = match block.resident() {
+                Some(resident) => resident < size.to_bytes(),
+                // Can't tell, so warm everything
+                None => true,
+            };
+            if cold {
+                block.touch();
+                warmed += size;
+            }
+        }
+        warmed
+    }
+    
+    /// Memory that can still be locked, or `None` if unlimited
+    fn lock_budget(blocks: &[FoodBlock]) -> Option<Bytes> {
+        let locked: Bytes = blocks.iter()
+            .filter(|b| b.is_locked())
+            .map(|b| b.size().to_bytes())
+            .sum();
+        memlock_limit().map(|limit| limit.saturating_sub(locked))
+    }
+    
+    /// Fill a block with `content` and make it resident according to the policy
+    ///
+    /// Zero blocks are only written when the policy or `integrity` calls
+    /// for it; anything else has to be written to be there at all, even
+    /// for a lazy pet. Whatever is written is what the player fed.
+    fn settle(
+        block: &mut FoodBlock,
+        policy: ResidencyPolicy,
+        content: FoodContent,
+        integrity: bool,
+        lock_budget: &mut Option<Bytes>,
+    ) {
+        let lazy = policy == ResidencyPolicy::Lazy;
+        if !block.is_filled() && (content != FoodContent::Zeros || (integrity && !lazy)) {
+            block.fill(content, rand::random());
+        } else if !lazy {
+            block.touch();
+        }
+        if policy != ResidencyPolicy::Mlock || block.is_locked() {
+            return;
+        }
+        
+        let size = block.size().to_bytes();
+        if lock_budget.is_some_and(|left| left < size) {
+            return; // Over RLIMIT_MEMLOCK; stays merely touched
+        }
+        match block.lock() {
+            Ok(()) => {
+                if let Some(left) = lock_budget {
+                    *left = left.saturating_sub(size);
+                }
+            }
+            Err(e) => log::debug!("Could not lock pet memory: {:#}", e),
+        }
+    }
+    
+    /// Free RAM according to the latest snapshot (host or cgroup, whichever
+    /// is tighter), minus anything we've allocated since it was taken and
+    /// anything running jobs have reserved, plus anything the governor has
+    /// released
+    pub fn estimated_free_mb(&mut self) -> MiB {
+        let snapshot = self.snapshots.latest();
+        if snapshot.taken_at > self.unsampled.0 {
+            self.unsampled = (snapshot.taken_at, 0);
+        }
+        
+        // Releases from before the latest snapshot are already in it
+        let (since, released) = *self.released_elsewhere.lock().unwrap();
+        let released = if since >= snapshot.taken_at { released.0 as i64 } else { 0 };
+        
+        let free = snapshot.budget().available.to_mib().0 as i64
+            - self.unsampled.1
+            - self.reserved.0 as i64
+            + released;
+        MiB(free.max(0) as usize)
+    }
+    
+    /// Record a change in our allocations the snapshots haven't seen yet
+    fn note_unsampled(&mut self, delta_mb: i64) {
+        self.unsampled.1 += delta_mb;
+    }
+    
+    /// Allocate memory (in MB) from the default food source
+    pub fn allocate(&mut self, amount_mb: MiB) -> Result<()> {
+        self.allocate_from(amount_mb, self.food)
+    }
+    
+    /// Allocate memory (in MB) from a specific food source
+    ///
+    /// Sources with blocks bigger than 1MB round the amount up.
+    pub fn allocate_from(&mut self, amount_mb: MiB, kind: FoodKind) -> Result<()> {
+        let source = food_source(kind, &self.food_dir)?;
+        let (block_count, amount_mb) = self.plan_allocation(source.as_ref(), amount_mb)?;
+        
+        // Allocate the memory, then hand it over in one short lock so the
+        // safety governor is never kept waiting behind a big meal
+        let blocks = self.take_blocks(source.as_ref(), block_count)
+            .map_err(|e| anyhow!("Failed to allocate {} memory: {:#}", kind, e))?;
+        self.allocated_blocks.lock().unwrap().extend(blocks);
+        
+        self.note_unsampled(amount_mb.0 as i64);
+        Ok(())
+    }
+    
+    /// Allocate and settle `block_count` blocks without holding the pet's blocks
+    ///
+    /// If one fails, the ones allocated so far are released.
+    fn take_blocks(&self, source: &dyn FoodSource, block_count: usize) -> Result<Vec<FoodBlock>> {
+        let mut lock_budget = Self::lock_budget(&self.allocated_blocks.lock().unwrap());
+        let mut blocks = Vec::with_capacity(block_count);
+        for _ in 0..block_count {
+            let mut block = FoodBlock::from_source(source)?;
+            Self::settle(&mut block, self.residency, self.content, self.integrity, &mut lock_budget);
+            blocks.push(block);
+        }
+        Ok(blocks)
+    }
+    
+    /// Check there's room for an allocation from `source`
+    ///
+    /// Returns how many blocks to take and the rounded size.
+    fn plan_allocation(&mut self, source: &dyn FoodSource, amount_mb: MiB) -> Result<(usize, MiB)> {
+        let block_size = source.block_size();
+        let block_count = amount_mb.0.div_ceil(block_size.0);
+        let amount_mb = MiB(block_count * block_size.0);
+        
         // Check if we can safely allocate
-        let monitor = super::monitor::SystemMonitor::new();
-        let free_ram = monitor.get_free_ram_mb();
+        let free_ram = self.estimated_free_mb();
         
         if free_ram < amount_mb + self.min_free_ram {
             return Err(anyhow!(
-                "Cannot allocate {} MB. Only {} MB free (minimum {} MB required)",
+                "Cannot allocate {}. Only {} free (minimum {} required)",
                 amount_mb,
                 free_ram,
                 self.min_free_ram
             ));
         }
         
-        // Allocate the memory
-        let mut blocks = self.allocated_blocks.lock().unwrap();
+        Ok((block_count, amount_mb))
+    }
+    
+    /// Start allocating memory (in MB) filled with `content` on a worker thread
+    ///
+    /// The blocks stay with the job until [`MemoryManager::finish_allocation`]
+    /// hands them over, so nothing else sees a half-eaten meal. The whole
+    /// amount counts against free RAM while the job runs.
+    pub fn start_allocation(&mut self, amount_mb: MiB, kind: FoodKind, content: FoodContent) -> Result<AllocationJob> {
+        let source = food_source(kind, &self.food_dir)?;
+        self.start_allocation_with(source, amount_mb, content)
+    }
+    
+    /// Start allocating memory (in MB) from a source the caller picked
+    pub(crate) fn start_allocation_with(&mut self, source: Arc<dyn FoodSource>, amount_mb: MiB, content: FoodContent) -> Result<AllocationJob> {
+        let (block_count, amount_mb) = self.plan_allocation(source.as_ref(), amount_mb)?;
+        let kind = source.kind();
+        let policy = self.residency;
+        let integrity = self.integrity;
+        let mut lock_budget = Self::lock_budget(&self.allocated_blocks.lock().unwrap());
+        
+        let progress = Arc::new(AtomicUsize::new(0));
+        let cancel = Arc::new(AtomicBool::new(false));
         
-        for i in 0..amount_mb {
-            // Allocate 1MB blocks
-            match Self::allocate_block() {
-                Ok(block) => blocks.push(block),
-                Err(e) => {
-                    // If allocation fails, release what we allocated so far
-                    for _ in 0..i {
-                        blocks.pop();
+        let worker = {
+            let progress = progress.clone();
+            let cancel = cancel.clone();
+            thread::Builder::new()
+                .name("allocation-worker".to_string())
+                .spawn(move || {
+                    let mut blocks = Vec::with_capacity(block_count);
+                    for _ in 0..block_count {
+                        if cancel.load(Ordering::Relaxed) {
+                            break;
+                        }
+                        match FoodBlock::from_source(source.as_ref()) {
+                            Ok(mut block) => {
+                                Self::settle(&mut block, policy, content, integrity, &mut lock_budget);
+                                progress.fetch_add(block.size().0, Ordering::Relaxed);
+                                blocks.push(block);
+                            }
+                            Err(e) => {
+                                let error = anyhow!("Failed to allocate {} memory: {:#}", kind, e);
+                                return (blocks, Some(error));
+                            }
+                        }
                     }
-                    return Err(anyhow!("Failed to allocate memory: {}", e));
-                }
-            }
-        }
+                    (blocks, None)
+                })?
+        };
         
-        Ok(())
+        // Held apart from the unsampled delta, which a new snapshot resets
+        // while the job is still running
+        self.reserved += amount_mb;
+        
+        Ok(AllocationJob {
+            target: amount_mb,
+            kind,
+            content,
+            progress,
+            cancel,
+            worker: Some(worker),
+        })
     }
     
-    /// Allocate a single 1MB block
-    fn allocate_block() -> Result<Box<[u8; 1_048_576]>> {
-        // Try to allocate 1MB
-        let block = vec![0u8; 1_048_576];
+    /// Wait for an allocation job and settle up
+    ///
+    /// With `keep_partial` false, a meal that was cancelled or failed
+    /// part-way is rolled back entirely; otherwise whatever was allocated
+    /// is kept.
+    pub fn finish_allocation(&mut self, mut job: AllocationJob, keep_partial: bool) -> AllocationOutcome {
+        let (blocks, error) = job.worker.take()
+            .and_then(|worker| worker.join().ok())
+            .unwrap_or_else(|| (Vec::new(), Some(anyhow!("allocation worker panicked"))));
+        
+        let allocated: MiB = blocks.iter().map(FoodBlock::size).sum();
+        let cancelled = job.is_cancelled() && allocated < job.target;
+        let complete = error.is_none() && !cancelled;
+        
+        let kept = if complete || keep_partial {
+            self.allocated_blocks.lock().unwrap().extend(blocks);
+            allocated
+        } else {
+            MiB(0)
+        };
         
-        // Convert to boxed array
-        let boxed_slice = block.into_boxed_slice();
-        let ptr = Box::into_raw(boxed_slice) as *mut [u8; 1_048_576];
+        // Swap the reservation for what was actually kept
+        self.reserved -= job.target;
+        self.note_unsampled(kept.0 as i64);
         
-        unsafe {
-            Ok(Box::from_raw(ptr))
+        AllocationOutcome {
+            requested: job.target,
+            kept,
+            cancelled,
+            error,
         }
     }
     
-    /// Release memory (in MB)
-    pub fn release(&mut self, amount_mb: usize) -> Result<()> {
+    /// Pop blocks off the top without releasing more than `amount_mb`
+    ///
+    /// Returns how much was actually released; a big block on top that
+    /// doesn't fit stops the release early.
+    fn pop_up_to(&mut self, amount_mb: MiB) -> MiB {
         let mut blocks = self.allocated_blocks.lock().unwrap();
+        let mut released = MiB(0);
         
-        let to_release = amount_mb.min(blocks.len());
-        for _ in 0..to_release {
+        while let Some(block) = blocks.last() {
+            if released + block.size() > amount_mb {
+                break;
+            }
+            released += block.size();
             blocks.pop();
         }
         
+        drop(blocks);
+        self.note_unsampled(-(released.0 as i64));
+        released
+    }
+    
+    /// Release memory (in MB)
+    pub fn release(&mut self, amount_mb: MiB) -> Result<()> {
+        self.pop_up_to(amount_mb);
         Ok(())
     }
     
     /// Clear all allocated memory
     pub fn clear(&mut self) {
         let mut blocks = self.allocated_blocks.lock().unwrap();
+        let released: MiB = blocks.iter().map(FoodBlock::size).sum();
         blocks.clear();
         
         // Force garbage collection (hint to the system)
         drop(blocks);
+        self.note_unsampled(-(released.0 as i64));
     }
     
     /// Get currently allocated memory in MB
-    pub fn get_allocated_mb(&self) -> usize {
-        self.allocated_blocks.lock().unwrap().len()
+    pub fn get_allocated_mb(&self) -> MiB {
+        self.allocated_blocks.lock().unwrap().iter().map(FoodBlock::size).sum()
     }
     
-    /// Fill memory with pattern (makes it "real" allocation)
+    /// Get allocated memory broken down by food source
+    pub fn get_allocated_by_food(&self) -> Vec<(FoodKind, MiB)> {
+        let blocks = self.allocated_blocks.lock().unwrap();
+        FoodKind::ALL.iter()
+            .map(|&kind| {
+                let held: MiB = blocks.iter().filter(|b| b.kind() == kind).map(FoodBlock::size).sum();
+                (kind, held)
+            })
+            .filter(|(_, held)| !held.is_zero())
+            .collect()
+    }
+    
+    /// Touch every page so the memory is actually in RAM
+    /// (not just virtually allocated)
     pub fn touch_memory(&mut self) -> Result<()> {
         let mut blocks = self.allocated_blocks.lock().unwrap();
+        blocks.iter_mut().for_each(FoodBlock::touch);
         
-        for (i, block) in blocks.iter_mut().enumerate() {
-            // Write a pattern to ensure the memory is actually allocated
-            // (not just virtually allocated)
-            let pattern = (i % 256) as u8;
-            for byte in block.iter_mut().step_by(4096) {
-                *byte = pattern;
-            }
-        }
-        
+        self.last_touch = Instant::now();
         Ok(())
     }
     
     /// Digest memory (release gradually)
-    pub fn digest(&mut self, amount_mb: usize) -> Result<usize> {
-        let mut blocks = self.allocated_blocks.lock().unwrap();
-        let current_size = blocks.len();
-        
-        if current_size == 0 {
-            return Ok(0);
-        }
-        
-        let to_digest = amount_mb.min(current_size);
-        for _ in 0..to_digest {
-            blocks.pop();
+    pub fn digest(&mut self, amount_mb: MiB) -> Result<MiB> {
+        Ok(self.pop_up_to(amount_mb))
+    }
+}
+
+/// An allocation running on a worker thread
+///
+/// Dropping the job cancels it and throws away anything it allocated.
+pub struct AllocationJob {
+    target: MiB,
+    kind: FoodKind,
+    content: FoodContent,
+    progress: Arc<AtomicUsize>,
+    cancel: Arc<AtomicBool>,
+    worker: Option<JoinHandle<(Vec<FoodBlock>, Option<anyhow::Error>)>>,
+}
+
+impl AllocationJob {
+    /// Total being allocated
+    pub fn target(&self) -> MiB {
+        self.target
+    }
+    
+    /// Amount allocated so far
+    pub fn progress(&self) -> MiB {
+        MiB(self.progress.load(Ordering::Relaxed))
+    }
+    
+    /// Food source being allocated from
+    pub fn kind(&self) -> FoodKind {
+        self.kind
+    }
+    
+    /// What the blocks are being filled with
+    pub fn content(&self) -> FoodContent {
+        self.content
+    }
+    
+    /// Ask the worker to stop after the current block
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+    
+    /// Whether the job has been asked to stop
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+    
+    /// Whether the worker is done and the job can be finished without waiting
+    pub fn is_finished(&self) -> bool {
+        self.worker.as_ref().is_none_or(|worker| worker.is_finished())
+    }
+}
+
+impl Drop for AllocationJob {
+    fn drop(&mut self) {
+        if let Some(worker) = self.worker.take() {
+            self.cancel();
+            let _ = worker.join();
         }
-        
-        Ok(to_digest)
     }
 }
 
+/// How an allocation job ended
+#[derive(Debug)]
+pub struct AllocationOutcome {
+    /// Amount the job set out to allocate
+    pub requested: MiB,
+    /// Amount handed to the memory manager
+    pub kept: MiB,
+    /// Whether the job was cancelled part-way
+    pub cancelled: bool,
+    /// Why the job stopped early, if it failed
+    pub error: Option<anyhow::Error>,
+}
+
 /// Memory statistics
 #[derive(Debug, Clone)]
 pub struct MemoryStats {
-    pub allocated_mb: usize,
-    pub system_free_mb: usize,
-    pub system_total_mb: usize,
-    pub process_usage_mb: usize,
+    pub allocated_mb: MiB,
+    pub system_free_mb: MiB,
+    pub system_total_mb: MiB,
+    pub process_usage_mb: MiB,
 }
 
 impl MemoryStats {
     /// Get current memory statistics
     pub fn current(manager: &MemoryManager) -> Result<Self> {
-        let monitor = super::monitor::SystemMonitor::new();
+        let snapshot = manager.snapshots.latest();
         
         Ok(MemoryStats {
             allocated_mb: manager.get_allocated_mb(),
-            system_free_mb: monitor.get_free_ram_mb(),
-            system_total_mb: monitor.get_total_ram_mb(),
-            process_usage_mb: monitor.get_process_ram_mb()?,
+            system_free_mb: snapshot.budget().available.to_mib(),
+            system_total_mb: snapshot.total.to_mib(),
+            process_usage_mb: snapshot.process_rss.map_or(MiB(0), |rss| rss.to_mib()),
         })
     }
     
     /// Check if it's safe to allocate more
-    pub fn can_allocate(&self, amount_mb: usize, min_free_mb: usize) -> bool {
+    pub fn can_allocate(&self, amount_mb: MiB, min_free_mb: MiB) -> bool {
         self.system_free_mb >= amount_mb + min_free_mb
     }
 }
@@ -161,28 +682,28 @@ impl MemoryStats {
 /// Safe memory allocator with limits
 pub struct SafeAllocator {
     manager: MemoryManager,
-    max_allocation_mb: usize,
-    warning_threshold_mb: usize,
+    max_allocation_mb: MiB,
+    warning_threshold_mb: MiB,
 }
 
 impl SafeAllocator {
     /// Create a new safe allocator
-    pub fn new(min_free_ram_mb: usize, max_allocation_mb: usize) -> Self {
+    pub fn new(min_free_ram_mb: MiB, max_allocation_mb: MiB) -> Self {
         SafeAllocator {
             manager: MemoryManager::new(min_free_ram_mb),
             max_allocation_mb,
-            warning_threshold_mb: max_allocation_mb * 80 / 100, // 80% threshold
+            warning_threshold_mb: MiB(max_allocation_mb.0 * 80 / 100), // 80% threshold
         }
     }
     
     /// Safely allocate memory with checks
-    pub fn allocate_safe(&mut self, amount_mb: usize) -> Result<()> {
+    pub fn allocate_safe(&mut self, amount_mb: MiB) -> Result<()> {
         let current = self.manager.get_allocated_mb();
         let new_total = current + amount_mb;
         
         if new_total > self.max_allocation_mb {
             return Err(anyhow!(
-                "Cannot allocate {} MB. Would exceed maximum of {} MB",
+                "Cannot allocate {}. Would exceed maximum of {}",
                 amount_mb,
                 self.max_allocation_mb
             ));
@@ -191,9 +712,9 @@ impl SafeAllocator {
         self.manager.allocate(amount_mb)?;
         
         if new_total >= self.warning_threshold_mb {
-            eprintln!("⚠️ Warning: Allocated {} MB ({}% of maximum)",
+            eprintln!("⚠️ Warning: Allocated {} ({}% of maximum)",
                 new_total,
-                new_total * 100 / self.max_allocation_mb
+                new_total.0 * 100 / self.max_allocation_mb.0
             );
         }
         
@@ -216,41 +737,234 @@ impl Drop for MemoryManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::system::food::StallingSource;
+    use crate::system::governor::{GovernorEvent, SafetyGovernor};
     
     #[test]
     fn test_memory_allocation() {
-        let mut manager = MemoryManager::new(100);
+        let mut manager = MemoryManager::new(MiB(100));
         
         // Should be able to allocate small amount
-        let result = manager.allocate(1);
+        let result = manager.allocate(MiB(1));
         if result.is_ok() {
-            assert_eq!(manager.get_allocated_mb(), 1);
+            assert_eq!(manager.get_allocated_mb(), MiB(1));
             manager.clear();
         }
     }
     
     #[test]
     fn test_memory_release() {
-        let mut manager = MemoryManager::new(100);
+        let mut manager = MemoryManager::new(MiB(100));
         
-        if manager.allocate(2).is_ok() {
-            assert_eq!(manager.get_allocated_mb(), 2);
-            manager.release(1).unwrap();
-            assert_eq!(manager.get_allocated_mb(), 1);
+        if manager.allocate(MiB(2)).is_ok() {
+            assert_eq!(manager.get_allocated_mb(), MiB(2));
+            manager.release(MiB(1)).unwrap();
+            assert_eq!(manager.get_allocated_mb(), MiB(1));
             manager.clear();
         }
     }
     
+    #[test]
+    fn test_free_estimate_counts_unsampled_allocations() {
+        let mut manager = MemoryManager::new(MiB(16));
+        let before = manager.estimated_free_mb();
+        
+        manager.allocate(MiB(4)).unwrap();
+        // The fixed snapshot never refreshes, so our own allocation
+        // must be accounted for by hand
+        assert_eq!(manager.estimated_free_mb(), before - MiB(4));
+        manager.release(MiB(4)).unwrap();
+        assert_eq!(manager.estimated_free_mb(), before);
+    }
+    
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_mixed_food_sources() {
+        let mut manager = MemoryManager::new(MiB(16));
+        
+        manager.allocate_from(MiB(1), FoodKind::Mmap).unwrap();
+        manager.allocate_from(MiB(1), FoodKind::HugePage).unwrap();
+        
+        // Huge pages come in 2MB blocks
+        assert_eq!(manager.get_allocated_mb(), MiB(3));
+        assert_eq!(manager.get_allocated_by_food(), vec![
+            (FoodKind::Mmap, MiB(1)),
+            (FoodKind::HugePage, MiB(2)),
+        ]);
+        
+        // Releasing 1MB can't split the 2MB block on top
+        assert_eq!(manager.digest(MiB(1)).unwrap(), MiB(0));
+        assert_eq!(manager.digest(MiB(3)).unwrap(), MiB(3));
+    }
+    
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_file_food_twice() {
+        let mut manager = MemoryManager::with_snapshots(MiB(0), SnapshotHandle::fixed(SystemSnapshot {
+            available: MiB(64).to_bytes(),
+            ..Default::default()
+        }));
+        let dir = std::env::temp_dir().join(format!("ram_pet_food_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        manager.set_food_dir(&dir);
+        
+        // Each meal builds its own source; the first meal's files are still mapped
+        manager.allocate_from(MiB(1), FoodKind::File).unwrap();
+        manager.allocate_from(MiB(1), FoodKind::File).unwrap();
+        assert_eq!(manager.get_allocated_by_food(), vec![(FoodKind::File, MiB(2))]);
+        
+        // The files are gone as soon as they're mapped, so a killed pet leaves none
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+        manager.touch_memory().unwrap();
+        std::fs::remove_dir(&dir).unwrap();
+    }
+    
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_residency_policies() {
+        let mut manager = MemoryManager::new(MiB(16));
+        manager.set_residency(ResidencyPolicy::Lazy);
+        
+        manager.allocate_from(MiB(2), FoodKind::Mmap).unwrap();
+        let lazy = manager.residency();
+        assert_eq!(lazy.virtual_size, MiB(2).to_bytes());
+        assert!(lazy.resident.unwrap() < lazy.virtual_size);
+        
+        // Lazy memory was never resident, so it isn't cold either
+        assert_eq!(lazy.cold_fraction(), Some(0.0));
+        
+        // Switching policy settles memory already held
+        manager.set_residency(ResidencyPolicy::Touch);
+        let touched = manager.residency();
+        assert_eq!(touched.resident, Some(MiB(2).to_bytes()));
+        assert_eq!(touched.touched, MiB(2).to_bytes());
+        assert_eq!(touched.cold_fraction(), Some(0.0));
+        assert_eq!(manager.warm_up(), MiB(0));
+    }
+    
+    #[test]
+    fn test_residency_policy_names() {
+        assert_eq!("MLOCK".parse::<ResidencyPolicy>().unwrap(), ResidencyPolicy::Mlock);
+        assert_eq!(ResidencyPolicy::Retouch.to_string(), "retouch");
+        assert!("pinned".parse::<ResidencyPolicy>().is_err());
+    }
+    
+    #[test]
+    fn test_allocation_job() {
+        let mut manager = MemoryManager::new(MiB(16));
+        
+        let job = manager.start_allocation(MiB(3), FoodKind::Heap, FoodContent::Text).unwrap();
+        assert_eq!(job.target(), MiB(3));
+        let outcome = manager.finish_allocation(job, true);
+        assert!(outcome.error.is_none());
+        assert_eq!(outcome.kept, MiB(3));
+        assert_eq!(manager.get_allocated_mb(), MiB(3));
+        
+        // Non-zero food is written in even without integrity checks
+        let blocks = manager.shared_blocks();
+        assert!(blocks.lock().unwrap().iter().all(|b| b.content() == Some(FoodContent::Text)));
+        
+        // Checked zero food is still zeros, so it still tastes and
+        // compresses like zeros
+        manager.set_integrity(true);
+        manager.allocate_from(MiB(1), FoodKind::Heap).unwrap();
+        let blocks = blocks.lock().unwrap();
+        let block = blocks.last().unwrap();
+        assert_eq!(block.content(), Some(FoodContent::Zeros));
+        assert!(block.as_slice().iter().all(|&b| b == 0));
+    }
+    
+    #[test]
+    fn test_cancelled_allocation_rolls_back() {
+        let mut manager = MemoryManager::new(MiB(16));
+        let before = manager.estimated_free_mb();
+        
+        let job = manager.start_allocation(MiB(64), FoodKind::Heap, FoodContent::Zeros).unwrap();
+        job.cancel();
+        let outcome = manager.finish_allocation(job, false);
+        assert!(outcome.cancelled);
+        assert_eq!(outcome.kept, MiB(0));
+        assert_eq!(manager.get_allocated_mb(), MiB(0));
+        assert_eq!(manager.estimated_free_mb(), before);
+    }
+    
+    #[test]
+    fn test_reservation_survives_new_snapshot() {
+        let snapshots = SnapshotHandle::fixed(SystemSnapshot {
+            available: MiB(200).to_bytes(),
+            ..Default::default()
+        });
+        let mut manager = MemoryManager::with_snapshots(MiB(0), snapshots.clone());
+        
+        let job = manager.start_allocation(MiB(64), FoodKind::Heap, FoodContent::Zeros).unwrap();
+        assert_eq!(manager.estimated_free_mb(), MiB(136));
+        
+        // A snapshot taken mid-job must not hand the reservation back
+        snapshots.publish(SystemSnapshot {
+            available: MiB(200).to_bytes(),
+            ..Default::default()
+        });
+        assert_eq!(manager.estimated_free_mb(), MiB(136));
+        
+        // Only what was kept still counts once the job is settled
+        let outcome = manager.finish_allocation(job, true);
+        assert_eq!(manager.estimated_free_mb(), MiB(200) - outcome.kept);
+        manager.clear();
+        assert_eq!(manager.estimated_free_mb(), MiB(200));
+    }
+    
+    #[test]
+    fn test_governor_frees_during_allocation() {
+        // 2 MB short of the governor's minimum
+        let snapshots = SnapshotHandle::fixed(SystemSnapshot {
+            available: MiB(98).to_bytes(),
+            ..Default::default()
+        });
+        let mut manager = MemoryManager::with_snapshots(MiB(0), snapshots.clone());
+        manager.set_food(FoodKind::Heap);
+        manager.allocate(MiB(4)).unwrap();
+        
+        let governor = SafetyGovernor::spawn(
+            manager.shared_blocks(),
+            manager.shared_releases(),
+            snapshots,
+            MiB(100),
+            Duration::from_millis(5),
+        );
+        let source = StallingSource::new(0);
+        
+        let (event, eaten) = thread::scope(|scope| {
+            let meal = scope.spawn(|| manager.take_blocks(&source, 3));
+            
+            // The meal is stuck mid-allocation; the governor must still get through
+            let deadline = Instant::now() + Duration::from_secs(5);
+            let mut event = None;
+            while event.is_none() && Instant::now() < deadline {
+                event = governor.events().next();
+                thread::sleep(Duration::from_millis(5));
+            }
+            source.release();
+            (event, meal.join().unwrap().unwrap())
+        });
+        
+        assert_eq!(event, Some(GovernorEvent::Vomited { released: MiB(2), available: MiB(98) }));
+        assert_eq!(eaten.len(), 3);
+        assert_eq!(manager.get_allocated_mb(), MiB(2));
+        
+        // What the governor gave back counts as free again
+        assert_eq!(manager.estimated_free_mb(), MiB(98) - MiB(4) + MiB(2));
+    }
+    
     #[test]
     fn test_safe_allocator() {
-        let mut allocator = SafeAllocator::new(100, 10);
+        let mut allocator = SafeAllocator::new(MiB(100), MiB(10));
         
         // Should succeed for small allocation
-        let result = allocator.allocate_safe(1);
+        let result = allocator.allocate_safe(MiB(1));
         assert!(result.is_ok() || result.is_err()); // Depends on system RAM
         
         // Should fail for allocation over max
-        let result = allocator.allocate_safe(11);
+        let result = allocator.allocate_safe(MiB(11));
         assert!(result.is_err());
     }
 }
\ No newline at end of file
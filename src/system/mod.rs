@@ -1,11 +1,24 @@
 // src/system/mod.rs
 // RAM Eating Pet Simulator - System Module
 
+pub mod balloon;
+pub mod food;
+pub mod governor;
+pub mod leaks;
 pub mod memory;
 pub mod monitor;
+pub mod pressure;
+pub mod rivals;
+pub mod sampler;
+pub mod scrubber;
+pub mod units;
+pub mod vitals;
 
 use anyhow::Result;
 
+use sampler::SystemSnapshot;
+use units::{Bytes, MiB};
+
 /// System utilities and helpers
 pub struct SystemUtils;
 
@@ -42,41 +55,20 @@ impl SystemUtils {
     }
     
     /// Check if system has enough RAM for safe operation
-    pub fn check_ram_safety(required_mb: usize, min_free_mb: usize) -> Result<bool> {
-        let monitor = monitor::SystemMonitor::new();
-        let free_ram = monitor.get_free_ram_mb();
+    pub fn check_ram_safety(required_mb: MiB, min_free_mb: MiB) -> Result<bool> {
+        let free_ram = SystemSnapshot::capture().budget().available.to_mib();
         
         Ok(free_ram >= required_mb + min_free_mb)
     }
     
     /// Format bytes to human readable string
     pub fn format_bytes(bytes: u64) -> String {
-        const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
-        
-        if bytes == 0 {
-            return "0 B".to_string();
-        }
-        
-        let base = 1024_f64;
-        let bytes_f64 = bytes as f64;
-        let exponent = (bytes_f64.ln() / base.ln()).floor() as i32;
-        let unit_index = exponent.min(UNITS.len() as i32 - 1) as usize;
-        let size = bytes_f64 / base.powi(exponent);
-        
-        if size >= 100.0 {
-            format!("{:.0} {}", size, UNITS[unit_index])
-        } else if size >= 10.0 {
-            format!("{:.1} {}", size, UNITS[unit_index])
-        } else {
-            format!("{:.2} {}", size, UNITS[unit_index])
-        }
+        Bytes(bytes).to_string()
     }
     
     /// Get system uptime
     pub fn get_uptime() -> Result<std::time::Duration> {
-        use sysinfo::System;
-        let _sys = System::new_all();
-        Ok(std::time::Duration::from_secs(System::uptime()))
+        Ok(std::time::Duration::from_secs(sysinfo::System::uptime()))
     }
 }
 
@@ -84,38 +76,43 @@ impl SystemUtils {
 #[derive(Debug, Clone)]
 pub struct SystemHealth {
     pub ram_usage_percent: f32,
-    pub free_ram_mb: usize,
-    pub total_ram_mb: usize,
-    pub process_ram_mb: usize,
+    pub free_ram_mb: MiB,
+    pub total_ram_mb: MiB,
+    pub process_ram_mb: MiB,
     pub is_healthy: bool,
 }
 
 impl SystemHealth {
     /// Check current system health
     pub fn check() -> Result<Self> {
-        let monitor = monitor::SystemMonitor::new();
-        let total = monitor.get_total_ram_mb();
-        let free = monitor.get_free_ram_mb();
-        let used = monitor.get_used_ram_mb();
-        let process = monitor.get_process_ram_mb()?;
+        Ok(Self::from_snapshot(&SystemSnapshot::capture()))
+    }
+    
+    /// Judge system health from an existing snapshot
+    pub fn from_snapshot(snapshot: &SystemSnapshot) -> Self {
+        let budget = snapshot.budget();
+        let total = budget.total.to_mib();
+        let free = budget.available.to_mib();
+        let used = budget.used.to_mib();
+        let process = snapshot.process_rss.map_or(MiB(0), Bytes::to_mib);
         
-        let usage_percent = (used as f32 / total as f32) * 100.0;
-        let is_healthy = free > 512 && usage_percent < 90.0;
+        let usage_percent = (used.0 as f32 / total.0 as f32) * 100.0;
+        let is_healthy = free > MiB(512) && usage_percent < 90.0;
         
-        Ok(SystemHealth {
+        SystemHealth {
             ram_usage_percent: usage_percent,
             free_ram_mb: free,
             total_ram_mb: total,
             process_ram_mb: process,
             is_healthy,
-        })
+        }
     }
     
     /// Get a warning message if system is unhealthy
     pub fn get_warning(&self) -> Option<String> {
         if !self.is_healthy {
-            if self.free_ram_mb < 512 {
-                Some(format!("⚠️ WARNING: Only {} MB RAM free!", self.free_ram_mb))
+            if self.free_ram_mb < MiB(512) {
+                Some(format!("⚠️ WARNING: Only {} RAM free!", self.free_ram_mb))
             } else if self.ram_usage_percent > 90.0 {
                 Some(format!("⚠️ WARNING: RAM usage at {:.1}%!", self.ram_usage_percent))
             } else {
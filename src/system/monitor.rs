@@ -1,57 +1,88 @@
 // src/system/monitor.rs
 // RAM Eating Pet Simulator - System Monitoring
 
-use anyhow::Result;
-use sysinfo::{System, Pid};
-use std::sync::{Arc, Mutex};
+use anyhow::{anyhow, Result};
+use sysinfo::{System, Uid};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::pressure::MemoryPressure;
+use super::sampler::{MemoryBudget, SnapshotHandle, SystemSampler, SystemSnapshot};
+use super::units::{Bytes, MiB};
+use super::vitals::ProcessVitals;
+
+/// PSI "some" percentage at which the system counts as under memory pressure
+pub const STALL_PRESSURE: f32 = 10.0;
 
 /// System monitor for tracking RAM usage
+///
+/// Reads come from the snapshot fetched by the last [`SystemMonitor::update`],
+/// so everything drawn in one frame agrees and no call blocks on sysinfo.
 pub struct SystemMonitor {
-    system: Arc<Mutex<System>>,
+    sampler: SystemSampler,
+    current: Arc<SystemSnapshot>,
 }
 
 impl SystemMonitor {
-    /// Create a new system monitor
+    /// Create a new system monitor sampling once per second
     pub fn new() -> Self {
-        let mut system = System::new_all();
-        system.refresh_all();
+        Self::with_interval(Duration::from_secs(1))
+    }
+    
+    /// Create a new system monitor sampling at the given interval
+    pub fn with_interval(interval: Duration) -> Self {
+        let sampler = SystemSampler::spawn(interval);
+        let current = sampler.handle().latest();
         
-        SystemMonitor {
-            system: Arc::new(Mutex::new(system)),
-        }
+        SystemMonitor { sampler, current }
     }
     
     /// Update system information
-    pub fn update(&self) -> Result<()> {
-        let mut sys = self.system.lock().unwrap();
-        sys.refresh_memory();
-        sys.refresh_processes();
+    pub fn update(&mut self) -> Result<()> {
+        self.current = self.sampler.handle().latest();
         Ok(())
     }
     
+    /// Handle for other subsystems to read the same snapshots
+    pub fn snapshots(&self) -> SnapshotHandle {
+        self.sampler.handle()
+    }
+    
+    /// The snapshot currently being displayed
+    pub fn snapshot(&self) -> &SystemSnapshot {
+        &self.current
+    }
+    
     /// Get total system RAM in MB
-    pub fn get_total_ram_mb(&self) -> usize {
-        let sys = self.system.lock().unwrap();
-        (sys.total_memory() / 1024) as usize
+    pub fn get_total_ram_mb(&self) -> MiB {
+        self.current.total.to_mib()
     }
     
     /// Get used system RAM in MB
-    pub fn get_used_ram_mb(&self) -> usize {
-        let sys = self.system.lock().unwrap();
-        (sys.used_memory() / 1024) as usize
+    pub fn get_used_ram_mb(&self) -> MiB {
+        self.current.used.to_mib()
+    }
+    
+    /// Get free RAM in MB under the tighter of the host and cgroup limits
+    pub fn get_free_ram_mb(&self) -> MiB {
+        self.current.budget().available.to_mib()
+    }
+    
+    /// Memory budget we live under (host or cgroup, whichever is tighter)
+    pub fn get_budget(&self) -> MemoryBudget {
+        self.current.budget()
     }
     
-    /// Get free system RAM in MB
-    pub fn get_free_ram_mb(&self) -> usize {
-        let sys = self.system.lock().unwrap();
-        (sys.available_memory() / 1024) as usize
+    /// Memory pressure stall information, where the kernel provides it
+    pub fn get_pressure(&self) -> Option<MemoryPressure> {
+        self.current.pressure
     }
     
-    /// Get RAM usage percentage
+    /// Get RAM usage percentage of the memory budget
     pub fn get_ram_usage_percent(&self) -> f32 {
-        let sys = self.system.lock().unwrap();
-        let total = sys.total_memory() as f32;
-        let used = sys.used_memory() as f32;
+        let budget = self.current.budget();
+        let total = budget.total.0 as f32;
+        let used = budget.used.0 as f32;
         
         if total > 0.0 {
             (used / total) * 100.0
@@ -61,29 +92,25 @@ impl SystemMonitor {
     }
     
     /// Get current process RAM usage in MB
-    pub fn get_process_ram_mb(&self) -> Result<usize> {
-        let mut sys = self.system.lock().unwrap();
-        sys.refresh_processes();
-        
-        let pid = Pid::from(std::process::id() as usize);
-        
-        if let Some(process) = sys.process(pid) {
-            Ok((process.memory() / 1024) as usize)
-        } else {
-            // Fallback: estimate based on our allocations
-            Ok(50) // Base overhead estimate
-        }
+    pub fn get_process_ram_mb(&self) -> Result<MiB> {
+        self.current.vitals.map(|vitals| vitals.rss)
+            .or(self.current.process_rss)
+            .map(Bytes::to_mib)
+            .ok_or_else(|| anyhow!("process memory usage is unavailable"))
+    }
+    
+    /// Detailed memory figures for our own process
+    pub fn get_vitals(&self) -> Option<ProcessVitals> {
+        self.current.vitals
     }
     
     /// Get system information summary
     pub fn get_system_info(&self) -> SystemInfo {
-        let sys = self.system.lock().unwrap();
-        
         SystemInfo {
-            total_ram_mb: (sys.total_memory() / 1024) as usize,
-            used_ram_mb: (sys.used_memory() / 1024) as usize,
-            free_ram_mb: (sys.available_memory() / 1024) as usize,
-            cpu_count: sys.cpus().len(),
+            total_ram_mb: self.get_total_ram_mb(),
+            used_ram_mb: self.get_used_ram_mb(),
+            free_ram_mb: self.get_free_ram_mb(),
+            cpu_count: std::thread::available_parallelism().map_or(1, |n| n.get()),
             system_name: System::name().unwrap_or_else(|| "Unknown".to_string()),
             kernel_version: System::kernel_version().unwrap_or_else(|| "Unknown".to_string()),
             os_version: System::os_version().unwrap_or_else(|| "Unknown".to_string()),
@@ -93,39 +120,50 @@ impl SystemMonitor {
     
     /// Check if system is under memory pressure
     pub fn is_memory_pressure(&self) -> bool {
-        self.get_free_ram_mb() < 500 || self.get_ram_usage_percent() > 90.0
+        self.get_free_ram_mb() < MiB(500)
+            || self.get_ram_usage_percent() > 90.0
+            || self.get_pressure().is_some_and(|psi| psi.some_avg10 >= STALL_PRESSURE)
     }
     
     /// Get top memory consuming processes
+    ///
+    /// The process table is only sampled once someone asks for it, so the
+    /// first call may return an empty list until the next sample lands.
     pub fn get_top_processes(&self, count: usize) -> Vec<ProcessInfo> {
-        let sys = self.system.lock().unwrap();
-        let mut processes: Vec<ProcessInfo> = sys.processes()
-            .iter()
-            .map(|(pid, process)| ProcessInfo {
-                pid: pid.as_u32(),
-                name: process.name().to_string(),
-                memory_mb: (process.memory() / 1024) as usize,
-            })
-            .collect();
+        self.sampler.set_process_table(true);
         
-        processes.sort_by(|a, b| b.memory_mb.cmp(&a.memory_mb));
-        processes.truncate(count);
-        processes
+        match &self.current.processes {
+            Some(processes) => processes.iter().take(count).cloned().collect(),
+            None => Vec::new(),
+        }
+    }
+    
+    /// The whole process table, biggest first
+    ///
+    /// Like [`SystemMonitor::get_top_processes`] this starts sampling the
+    /// table, and returns `None` until the first sample with it lands.
+    pub fn get_process_table(&self) -> Option<Arc<Vec<ProcessInfo>>> {
+        self.sampler.set_process_table(true);
+        self.current.processes.clone()
+    }
+    
+    /// Stop sampling the full process table
+    pub fn release_process_table(&self) {
+        self.sampler.set_process_table(false);
     }
     
     /// Monitor RAM changes over time
-    pub fn get_ram_delta(&self, previous_free: usize) -> i32 {
-        let current_free = self.get_free_ram_mb();
-        current_free as i32 - previous_free as i32
+    pub fn get_ram_delta(&self, previous_free: MiB) -> i64 {
+        self.get_free_ram_mb().delta(previous_free)
     }
 }
 
 /// System information summary
 #[derive(Debug, Clone)]
 pub struct SystemInfo {
-    pub total_ram_mb: usize,
-    pub used_ram_mb: usize,
-    pub free_ram_mb: usize,
+    pub total_ram_mb: MiB,
+    pub used_ram_mb: MiB,
+    pub free_ram_mb: MiB,
     pub cpu_count: usize,
     pub system_name: String,
     pub kernel_version: String,
@@ -141,8 +179,8 @@ impl SystemInfo {
             self.system_name,
             self.os_version,
             self.cpu_count,
-            self.used_ram_mb,
-            self.total_ram_mb
+            self.used_ram_mb.0,
+            self.total_ram_mb.0
         )
     }
 }
@@ -152,12 +190,16 @@ impl SystemInfo {
 pub struct ProcessInfo {
     pub pid: u32,
     pub name: String,
-    pub memory_mb: usize,
+    pub memory_mb: MiB,
+    /// User ID of the owner, if known
+    pub uid: Option<Uid>,
+    /// Login name of the owner (the bare UID if it has no account), if known
+    pub user: Option<String>,
 }
 
 /// RAM usage tracker for historical data
 pub struct RamTracker {
-    history: Vec<(std::time::Instant, usize)>,
+    history: Vec<(std::time::Instant, MiB)>,
     max_history: usize,
 }
 
@@ -172,40 +214,48 @@ impl RamTracker {
     
     /// Record current RAM usage
     pub fn record(&mut self, monitor: &SystemMonitor) {
-        let now = std::time::Instant::now();
-        let used = monitor.get_used_ram_mb();
-        
-        self.history.push((now, used));
+        let snapshot = monitor.snapshot();
+        self.record_usage(snapshot.taken_at, snapshot.used.to_mib());
+    }
+    
+    /// Record any usage figure taken at a given time
+    pub fn record_usage(&mut self, at: std::time::Instant, usage: MiB) {
+        self.history.push((at, usage));
         
         if self.history.len() > self.max_history {
             self.history.remove(0);
         }
     }
     
+    /// Every recorded sample, oldest first
+    pub fn samples(&self) -> &[(std::time::Instant, MiB)] {
+        &self.history
+    }
+    
     /// Get average RAM usage over the history
-    pub fn get_average(&self) -> usize {
+    pub fn get_average(&self) -> MiB {
         if self.history.is_empty() {
-            return 0;
+            return MiB(0);
         }
         
-        let sum: usize = self.history.iter().map(|(_, usage)| usage).sum();
-        sum / self.history.len()
+        let sum: MiB = self.history.iter().map(|(_, usage)| *usage).sum();
+        MiB(sum.0 / self.history.len())
     }
     
     /// Get RAM usage trend (positive = increasing, negative = decreasing)
-    pub fn get_trend(&self) -> i32 {
+    pub fn get_trend(&self) -> i64 {
         if self.history.len() < 2 {
             return 0;
         }
         
-        let first = self.history.first().unwrap().1 as i32;
-        let last = self.history.last().unwrap().1 as i32;
-        last - first
+        let first = self.history.first().unwrap().1;
+        let last = self.history.last().unwrap().1;
+        last.delta(first)
     }
     
     /// Get peak RAM usage
-    pub fn get_peak(&self) -> usize {
-        self.history.iter().map(|(_, usage)| *usage).max().unwrap_or(0)
+    pub fn get_peak(&self) -> MiB {
+        self.history.iter().map(|(_, usage)| *usage).max().unwrap_or_default()
     }
 }
 
@@ -225,11 +275,15 @@ mod tests {
         
         // Should be able to get RAM info
         let total = monitor.get_total_ram_mb();
-        assert!(total > 0);
+        assert!(total > MiB(0));
         
         let free = monitor.get_free_ram_mb();
-        assert!(free > 0);
+        assert!(free > MiB(0));
         assert!(free <= total);
+        
+        // sysinfo reports bytes; a machine with more "MB" than 16 TB means
+        // we've slipped back into KiB
+        assert!(total < MiB(16 * 1024 * 1024));
     }
     
     #[test]
@@ -237,7 +291,7 @@ mod tests {
         let monitor = SystemMonitor::new();
         let info = monitor.get_system_info();
         
-        assert!(info.total_ram_mb > 0);
+        assert!(info.total_ram_mb > MiB(0));
         assert!(!info.system_name.is_empty());
         assert!(info.cpu_count > 0);
     }
@@ -248,6 +302,6 @@ mod tests {
         let monitor = SystemMonitor::new();
         
         tracker.record(&monitor);
-        assert!(tracker.get_average() > 0);
+        assert!(tracker.get_average() > MiB(0));
     }
 }
\ No newline at end of file
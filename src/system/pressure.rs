@@ -0,0 +1,183 @@
+// src/system/pressure.rs
+// RAM Eating Pet Simulator - Cgroup Limits and Memory Pressure (Linux)
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::units::Bytes;
+
+/// Where cgroup v2 is normally mounted
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// System-wide pressure stall information for memory
+const PSI_MEMORY: &str = "/proc/pressure/memory";
+
+/// Memory limit and usage of the tightest cgroup we live in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CgroupMemory {
+    /// `memory.max` of the cgroup
+    pub limit: Bytes,
+    /// `memory.current` minus inactive page cache the kernel can reclaim
+    pub usage: Bytes,
+}
+
+impl CgroupMemory {
+    /// Room left before the cgroup starts reclaiming or OOM-killing
+    pub fn headroom(&self) -> Bytes {
+        self.limit.saturating_sub(self.usage)
+    }
+
+    /// Read the limit and usage of one cgroup directory
+    ///
+    /// `None` if the cgroup has no memory limit.
+    pub fn read(dir: &Path) -> Option<Self> {
+        let limit = fs::read_to_string(dir.join("memory.max")).ok()?;
+        let limit = Bytes(limit.trim().parse().ok()?); // "max" means unlimited
+
+        let current: u64 = fs::read_to_string(dir.join("memory.current")).ok()?
+            .trim()
+            .parse()
+            .ok()?;
+        let inactive_file = fs::read_to_string(dir.join("memory.stat"))
+            .ok()
+            .and_then(|stat| stat_value(&stat, "inactive_file"))
+            .unwrap_or(0);
+
+        Some(CgroupMemory {
+            limit,
+            usage: Bytes(current.saturating_sub(inactive_file)),
+        })
+    }
+}
+
+/// The cgroup v2 directories limiting this process, innermost first
+///
+/// Empty when not running under cgroup v2.
+pub fn cgroup_dirs() -> Vec<PathBuf> {
+    let Some(path) = fs::read_to_string("/proc/self/cgroup")
+        .ok()
+        .and_then(|text| unified_cgroup_path(&text))
+    else {
+        return Vec::new();
+    };
+
+    // Limits on any ancestor apply to us too
+    let mut dir = Path::new(CGROUP_ROOT).join(path.trim_start_matches('/'));
+    let mut dirs = Vec::new();
+    while dir.starts_with(CGROUP_ROOT) {
+        dirs.push(dir.clone());
+        if !dir.pop() {
+            break;
+        }
+    }
+    dirs
+}
+
+/// The tightest limit among the given cgroups, by remaining headroom
+pub fn tightest_cgroup(dirs: &[PathBuf]) -> Option<CgroupMemory> {
+    dirs.iter()
+        .filter_map(|dir| CgroupMemory::read(dir))
+        .min_by_key(CgroupMemory::headroom)
+}
+
+/// Path of our cgroup v2 group, from the `0::` line of `/proc/self/cgroup`
+fn unified_cgroup_path(proc_cgroup: &str) -> Option<String> {
+    proc_cgroup.lines()
+        .find_map(|line| line.strip_prefix("0::"))
+        .map(str::to_string)
+}
+
+/// Value of a `key value` line in a cgroup stat file
+fn stat_value(stat: &str, key: &str) -> Option<u64> {
+    stat.lines().find_map(|line| {
+        let (name, value) = line.split_once(' ')?;
+        if name == key { value.trim().parse().ok() } else { None }
+    })
+}
+
+/// Memory pressure stall percentages from PSI
+///
+/// "some" is the share of time at least one task was stalled waiting for
+/// memory; "full" is the share of time every task was.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MemoryPressure {
+    /// `some` over the last 10 seconds
+    pub some_avg10: f32,
+    /// `some` over the last 60 seconds
+    pub some_avg60: f32,
+    /// `full` over the last 10 seconds
+    pub full_avg10: f32,
+}
+
+impl MemoryPressure {
+    /// Read system-wide memory pressure, if the kernel exposes it
+    pub fn read() -> Option<Self> {
+        fs::read_to_string(PSI_MEMORY).ok().and_then(|text| Self::parse(&text))
+    }
+
+    /// Parse the contents of a PSI file
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut pressure = MemoryPressure::default();
+        let mut found = false;
+
+        for line in text.lines() {
+            let mut fields = line.split_whitespace();
+            let kind = fields.next()?;
+            for field in fields {
+                let Some((name, value)) = field.split_once('=') else { continue };
+                let Ok(value) = value.parse::<f32>() else { continue };
+                match (kind, name) {
+                    ("some", "avg10") => pressure.some_avg10 = value,
+                    ("some", "avg60") => pressure.some_avg60 = value,
+                    ("full", "avg10") => pressure.full_avg10 = value,
+                    _ => continue,
+                }
+                found = true;
+            }
+        }
+
+        found.then_some(pressure)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_psi() {
+        let text = "some avg10=12.50 avg60=3.00 avg300=1.00 total=123\n\
+                    full avg10=4.25 avg60=1.00 avg300=0.50 total=45\n";
+        let pressure = MemoryPressure::parse(text).unwrap();
+        assert_eq!(pressure.some_avg10, 12.5);
+        assert_eq!(pressure.some_avg60, 3.0);
+        assert_eq!(pressure.full_avg10, 4.25);
+        assert!(MemoryPressure::parse("").is_none());
+    }
+
+    #[test]
+    fn test_unified_cgroup_path() {
+        let text = "4:memory:/docker/abc\n0::/user.slice/session-1.scope\n";
+        assert_eq!(unified_cgroup_path(text).unwrap(), "/user.slice/session-1.scope");
+        assert!(unified_cgroup_path("4:memory:/docker/abc\n").is_none());
+    }
+
+    #[test]
+    fn test_read_cgroup_dir() {
+        let dir = std::env::temp_dir().join(format!("ram_pet-cgroup-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("memory.max"), "1073741824\n").unwrap();
+        fs::write(dir.join("memory.current"), "805306368\n").unwrap();
+        fs::write(dir.join("memory.stat"), "anon 1\ninactive_file 268435456\n").unwrap();
+
+        let cgroup = CgroupMemory::read(&dir).unwrap();
+        assert_eq!(cgroup.usage, Bytes(536_870_912));
+        assert_eq!(cgroup.headroom(), Bytes(536_870_912));
+
+        // No limit, no budget
+        fs::write(dir.join("memory.max"), "max\n").unwrap();
+        assert!(CgroupMemory::read(&dir).is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,199 @@
+// src/system/rivals.rs
+// RAM Eating Pet Simulator - Food Competition
+
+use std::collections::HashSet;
+
+use super::monitor::ProcessInfo;
+use super::sampler::{FreshSnapshots, SystemSnapshot};
+use super::units::MiB;
+
+/// How many of the biggest processes the pet competes against
+pub const RIVALS: usize = 8;
+
+/// One entry in the food competition
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rival {
+    /// Process ID, or `None` for the pet itself
+    pub pid: Option<u32>,
+    pub name: String,
+    pub size: MiB,
+}
+
+impl Rival {
+    /// Whether this entry is the pet
+    pub fn is_pet(&self) -> bool {
+        self.pid.is_none()
+    }
+}
+
+/// How the pet's place in the competition changed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RankChange {
+    /// The pet moved up to `rank` (1 = biggest), overtaking `passed`
+    Climbed { rank: usize, passed: Rival },
+    /// The pet fell to `rank`; `leader` is now just above it
+    Dropped { rank: usize, leader: Rival },
+}
+
+/// Ranks the pet against every other memory eater on the system
+///
+/// Our own process is left out (the pet stands in for it), as is the
+/// process an adopted pet mirrors.
+pub struct Competition {
+    stand_in: Option<u32>,
+    standings: Vec<Rival>,
+    rank: Option<usize>,
+    /// Processes that were bigger than the pet last time
+    ahead: HashSet<u32>,
+    fresh: FreshSnapshots,
+}
+
+impl Competition {
+    /// Start a competition; `stand_in` is a process the pet already is
+    pub fn new(stand_in: Option<u32>) -> Self {
+        Competition {
+            stand_in,
+            standings: Vec::new(),
+            rank: None,
+            ahead: HashSet::new(),
+            fresh: FreshSnapshots::default(),
+        }
+    }
+
+    /// The top rivals and the pet, biggest first
+    ///
+    /// The pet is always included, even if it's below the top.
+    pub fn standings(&self) -> &[Rival] {
+        &self.standings
+    }
+
+    /// The pet's rank (1 = biggest), once the process table has come in
+    pub fn rank(&self) -> Option<usize> {
+        self.rank
+    }
+
+    /// Re-rank the pet against a new snapshot
+    ///
+    /// Returns how its rank changed since the last snapshot, if it did.
+    pub fn update(&mut self, snapshot: &SystemSnapshot, pet_name: &str, pet_size: MiB) -> Option<RankChange> {
+        let table = self.fresh.processes(snapshot)?;
+
+        let everyone = line_up(table, self.skipped(), pet_name, pet_size);
+        let rank = everyone.iter().position(Rival::is_pet).map_or(1, |index| index + 1);
+        let change = match self.rank {
+            // Rising because bigger processes exited or shrank doesn't
+            // overtake anyone; only passing one that was ahead does
+            Some(old) if rank < old => everyone[rank..].iter()
+                .find(|rival| rival.pid.is_some_and(|pid| self.ahead.contains(&pid)))
+                .map(|passed| RankChange::Climbed { rank, passed: passed.clone() }),
+            Some(old) if rank > old => Some(RankChange::Dropped {
+                rank,
+                leader: everyone[rank - 2].clone(),
+            }),
+            _ => None,
+        };
+
+        // The top rivals, with the pet tacked on the end if it's further down
+        let mut standings: Vec<Rival> = everyone.iter().take(RIVALS + 1).cloned().collect();
+        if rank > RIVALS {
+            standings[RIVALS] = everyone[rank - 1].clone();
+        }
+        self.standings = standings;
+        self.rank = Some(rank);
+        self.ahead = everyone[..rank - 1].iter().filter_map(|rival| rival.pid).collect();
+        change
+    }
+
+    fn skipped(&self) -> [Option<u32>; 2] {
+        [Some(std::process::id()), self.stand_in]
+    }
+}
+
+/// Every process in a table sorted biggest first, with the pet slotted in
+fn line_up(table: &[ProcessInfo], skipped: [Option<u32>; 2], pet_name: &str, pet_size: MiB) -> Vec<Rival> {
+    let mut everyone: Vec<Rival> = table.iter()
+        .filter(|process| !skipped.contains(&Some(process.pid)))
+        .map(|process| Rival { pid: Some(process.pid), name: process.name.clone(), size: process.memory_mb })
+        .collect();
+    // Ties go to the pet; it's hungrier
+    let place = everyone.iter().take_while(|rival| rival.size > pet_size).count();
+    everyone.insert(place, Rival { pid: None, name: pet_name.to_string(), size: pet_size });
+    everyone
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    fn snapshot(secs: u64, sizes: &[usize]) -> SystemSnapshot {
+        let table = sizes.iter().enumerate()
+            .map(|(i, &mb)| ProcessInfo { pid: 1000 + i as u32, name: format!("proc{}", i), memory_mb: MiB(mb), uid: None, user: None })
+            .collect();
+        SystemSnapshot::fake(Instant::now() + Duration::from_secs(secs), table)
+    }
+
+    #[test]
+    fn test_rank_changes() {
+        let mut competition = Competition::new(None);
+        let sizes = [2048, 1024, 512, 256];
+
+        assert_eq!(competition.update(&snapshot(1, &sizes), "Chomper", MiB(300)), None);
+        assert_eq!(competition.rank(), Some(4));
+        assert!(competition.standings()[3].is_pet());
+
+        match competition.update(&snapshot(2, &sizes), "Chomper", MiB(600)) {
+            Some(RankChange::Climbed { rank, passed }) => {
+                assert_eq!(rank, 3);
+                assert_eq!(passed.name, "proc2");
+            }
+            other => panic!("unexpected change {:?}", other),
+        }
+
+        match competition.update(&snapshot(3, &sizes), "Chomper", MiB(100)) {
+            Some(RankChange::Dropped { rank, leader }) => {
+                assert_eq!(rank, 5);
+                assert_eq!(leader.name, "proc3");
+            }
+            other => panic!("unexpected change {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_still_last_in_a_shorter_table() {
+        let mut competition = Competition::new(None);
+        competition.update(&snapshot(1, &[2048, 1024, 512, 256]), "Chomper", MiB(1));
+        assert_eq!(competition.rank(), Some(5));
+
+        // Processes exited; the pet is higher up but still passed nobody
+        assert_eq!(competition.update(&snapshot(2, &[2048, 1024]), "Chomper", MiB(1)), None);
+        assert_eq!(competition.rank(), Some(3));
+    }
+
+    #[test]
+    fn test_rival_above_exits() {
+        let mut competition = Competition::new(None);
+        competition.update(&snapshot(1, &[2048, 1024, 512, 256]), "Chomper", MiB(600));
+        assert_eq!(competition.rank(), Some(3));
+
+        // proc0 quits; the pet rises but everyone below it was already there
+        let mut later = snapshot(2, &[2048, 1024, 512, 256]);
+        later.processes = later.processes.map(|table| Arc::new(table[1..].to_vec()));
+        assert_eq!(competition.update(&later, "Chomper", MiB(600)), None);
+        assert_eq!(competition.rank(), Some(2));
+    }
+
+    #[test]
+    fn test_pet_kept_below_the_top() {
+        let sizes: Vec<usize> = (0..20).map(|i| 2000 - i * 50).collect();
+        let mut competition = Competition::new(Some(1000));
+        competition.update(&snapshot(1, &sizes), "Chomper", MiB(1));
+
+        assert_eq!(competition.rank(), Some(20));
+        let standings = competition.standings();
+        assert_eq!(standings.len(), RIVALS + 1);
+        assert_eq!(standings[0].name, "proc1");
+        assert!(standings[RIVALS].is_pet());
+    }
+}
@@ -0,0 +1,388 @@
+// src/system/sampler.rs
+// RAM Eating Pet Simulator - Background System Sampler
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, ProcessRefreshKind, System, Uid, UpdateKind, Users};
+
+use super::monitor::ProcessInfo;
+use super::pressure::{cgroup_dirs, tightest_cgroup, CgroupMemory, MemoryPressure};
+use super::units::Bytes;
+use super::vitals::{ProcessVitals, VitalsReader};
+
+/// Immutable view of system memory at one point in time
+#[derive(Debug, Clone)]
+pub struct SystemSnapshot {
+    /// When the sample was taken
+    pub taken_at: Instant,
+    /// Total physical RAM
+    pub total: Bytes,
+    /// RAM in use
+    pub used: Bytes,
+    /// RAM available to new allocations
+    pub available: Bytes,
+    /// Resident size of our own process, if it could be read
+    pub process_rss: Option<Bytes>,
+    /// Every process on the system, biggest first (only when requested)
+    pub processes: Option<Arc<Vec<ProcessInfo>>>,
+    /// Tightest cgroup v2 memory limit we live under, if any
+    pub cgroup: Option<CgroupMemory>,
+    /// Memory pressure stall information, if the kernel provides it
+    pub pressure: Option<MemoryPressure>,
+    /// Detailed memory figures for our own process, where `/proc` has them
+    pub vitals: Option<ProcessVitals>,
+}
+
+impl SystemSnapshot {
+    /// Take a single cheap sample (memory totals and our own process only)
+    pub fn capture() -> Self {
+        Probe::new().sample(false)
+    }
+    
+    /// Take a single sample including the full process table
+    pub fn capture_with_processes() -> Self {
+        Probe::new().sample(true)
+    }
+    
+    /// A made-up snapshot taken `at`, holding `processes` and nothing else
+    #[cfg(test)]
+    pub(crate) fn fake(at: Instant, processes: Vec<ProcessInfo>) -> Self {
+        SystemSnapshot {
+            taken_at: at,
+            processes: Some(Arc::new(processes)),
+            ..Default::default()
+        }
+    }
+    
+    /// The memory budget we really live under
+    ///
+    /// Inside a container the host's available memory means little; the
+    /// cgroup limit is what gets us OOM-killed. Whichever leaves less room
+    /// wins.
+    pub fn budget(&self) -> MemoryBudget {
+        match self.cgroup {
+            Some(cgroup) if cgroup.headroom() < self.available => MemoryBudget {
+                scope: BudgetScope::Cgroup,
+                total: cgroup.limit,
+                used: cgroup.usage,
+                available: cgroup.headroom(),
+            },
+            _ => MemoryBudget {
+                scope: BudgetScope::Host,
+                total: self.total,
+                used: self.used,
+                available: self.available,
+            },
+        }
+    }
+}
+
+impl Default for SystemSnapshot {
+    /// A sample taken now that knows nothing
+    fn default() -> Self {
+        SystemSnapshot {
+            taken_at: Instant::now(),
+            total: Bytes(0),
+            used: Bytes(0),
+            available: Bytes(0),
+            process_rss: None,
+            processes: None,
+            cgroup: None,
+            pressure: None,
+            vitals: None,
+        }
+    }
+}
+
+/// Which limit a [`MemoryBudget`] comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetScope {
+    /// The machine's physical RAM
+    Host,
+    /// Our cgroup's `memory.max`
+    Cgroup,
+}
+
+/// Total, used and available memory under the tightest limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryBudget {
+    pub scope: BudgetScope,
+    pub total: Bytes,
+    pub used: Bytes,
+    pub available: Bytes,
+}
+
+/// Cheap, clonable handle to the most recent snapshot
+#[derive(Clone)]
+pub struct SnapshotHandle {
+    latest: Arc<RwLock<Arc<SystemSnapshot>>>,
+}
+
+impl SnapshotHandle {
+    /// Handle to a single snapshot that is never refreshed
+    pub fn fixed(snapshot: SystemSnapshot) -> Self {
+        SnapshotHandle {
+            latest: Arc::new(RwLock::new(Arc::new(snapshot))),
+        }
+    }
+
+    /// Get the latest published snapshot
+    pub fn latest(&self) -> Arc<SystemSnapshot> {
+        self.latest.read().unwrap().clone()
+    }
+
+    /// Publish a new snapshot
+    pub(crate) fn publish(&self, snapshot: SystemSnapshot) {
+        *self.latest.write().unwrap() = Arc::new(snapshot);
+    }
+}
+
+/// Remembers the last snapshot a reader took in
+///
+/// Readers poll the latest snapshot far more often than new ones are
+/// published, so each keeps one of these to never count a sample twice.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FreshSnapshots {
+    last: Option<Instant>,
+}
+
+impl FreshSnapshots {
+    /// When the last snapshot taken in was taken
+    pub fn last(&self) -> Option<Instant> {
+        self.last
+    }
+
+    /// Take in a snapshot, returning whether it's one we haven't seen
+    pub fn accept(&mut self, snapshot: &SystemSnapshot) -> bool {
+        if self.last == Some(snapshot.taken_at) {
+            return false;
+        }
+        self.last = Some(snapshot.taken_at);
+        true
+    }
+
+    /// The process table of a snapshot we haven't seen
+    ///
+    /// A snapshot without the table isn't taken in, so the next one that
+    /// has it still counts.
+    pub fn processes<'a>(&mut self, snapshot: &'a SystemSnapshot) -> Option<&'a Arc<Vec<ProcessInfo>>> {
+        let table = snapshot.processes.as_ref()?;
+        self.accept(snapshot).then_some(table)
+    }
+}
+
+/// Background thread that samples the system on a fixed interval
+///
+/// Readers never touch sysinfo themselves: they grab the latest
+/// [`SystemSnapshot`] through a [`SnapshotHandle`]. Only memory totals and
+/// our own process are refreshed unless the full process table has been
+/// asked for with [`SystemSampler::set_process_table`].
+pub struct SystemSampler {
+    handle: SnapshotHandle,
+    want_processes: Arc<AtomicBool>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl SystemSampler {
+    /// Start sampling every `interval`
+    ///
+    /// The first snapshot is taken before this returns, so readers always
+    /// have data.
+    pub fn spawn(interval: Duration) -> Self {
+        let mut probe = Probe::new();
+        let handle = SnapshotHandle::fixed(probe.sample(false));
+        let want_processes = Arc::new(AtomicBool::new(false));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread = {
+            let handle = handle.clone();
+            let want_processes = want_processes.clone();
+            let stop = stop.clone();
+            thread::Builder::new()
+                .name("system-sampler".to_string())
+                .spawn(move || {
+                    while !stop.load(Ordering::Relaxed) {
+                        thread::park_timeout(interval);
+                        if stop.load(Ordering::Relaxed) {
+                            break;
+                        }
+                        handle.publish(probe.sample(want_processes.load(Ordering::Relaxed)));
+                    }
+                })
+                .expect("failed to spawn system sampler thread")
+        };
+
+        SystemSampler {
+            handle,
+            want_processes,
+            stop,
+            thread: Some(thread),
+        }
+    }
+
+    /// Get a handle to the published snapshots
+    pub fn handle(&self) -> SnapshotHandle {
+        self.handle.clone()
+    }
+
+    /// Ask for (or stop asking for) the full process table in snapshots
+    pub fn set_process_table(&self, enabled: bool) {
+        if !self.want_processes.swap(enabled, Ordering::Relaxed) && enabled {
+            // Wake the sampler so the table shows up promptly
+            if let Some(thread) = &self.thread {
+                thread.thread().unpark();
+            }
+        }
+    }
+}
+
+impl Drop for SystemSampler {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            thread.thread().unpark();
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Owns the sysinfo state and refreshes only what's needed
+struct Probe {
+    system: System,
+    pid: Pid,
+    /// Cgroup v2 directories whose limits apply to us
+    cgroups: Vec<PathBuf>,
+    vitals: VitalsReader,
+    /// User accounts, loaded the first time the process table is sampled
+    users: Option<Users>,
+    /// UIDs still without an account after the accounts were last reloaded
+    unknown_uids: HashSet<Uid>,
+}
+
+impl Probe {
+    fn new() -> Self {
+        Probe {
+            system: System::new(),
+            pid: Pid::from_u32(std::process::id()),
+            cgroups: cgroup_dirs(),
+            vitals: VitalsReader::default(),
+            users: None,
+            unknown_uids: HashSet::new(),
+        }
+    }
+
+    fn sample(&mut self, processes: bool) -> SystemSnapshot {
+        self.system.refresh_memory();
+
+        let refresh = ProcessRefreshKind::new().with_memory();
+        let table = if processes {
+            self.system.refresh_processes_specifics(refresh.with_user(UpdateKind::OnlyIfNotSet));
+            let users = self.users.get_or_insert_with(Users::new_with_refreshed_list);
+
+            // Accounts created since the list was loaded need it reloaded,
+            // but a UID that has no account at all mustn't reload it every time
+            let missing: HashSet<Uid> = self.system.processes().values()
+                .filter_map(|process| process.user_id())
+                .filter(|uid| users.get_user_by_id(uid).is_none())
+                .cloned()
+                .collect();
+            if !missing.is_subset(&self.unknown_uids) {
+                users.refresh_list();
+                self.unknown_uids = missing.into_iter()
+                    .filter(|uid| users.get_user_by_id(uid).is_none())
+                    .collect();
+            }
+
+            let mut table: Vec<ProcessInfo> = self.system.processes()
+                .iter()
+                .map(|(pid, process)| ProcessInfo {
+                    pid: pid.as_u32(),
+                    name: process.name().to_string(),
+                    memory_mb: Bytes(process.memory()).to_mib(),
+                    uid: process.user_id().cloned(),
+                    user: process.user_id().map(|uid| users.get_user_by_id(uid)
+                        .map_or_else(|| (**uid).to_string(), |user| user.name().to_string())),
+                })
+                .collect();
+            table.sort_by_key(|p| std::cmp::Reverse(p.memory_mb));
+            Some(Arc::new(table))
+        } else {
+            self.system.refresh_process_specifics(self.pid, refresh);
+            None
+        };
+
+        SystemSnapshot {
+            taken_at: Instant::now(),
+            total: Bytes(self.system.total_memory()),
+            used: Bytes(self.system.used_memory()),
+            available: Bytes(self.system.available_memory()),
+            process_rss: self.system.process(self.pid).map(|p| Bytes(p.memory())),
+            processes: table,
+            cgroup: tightest_cgroup(&self.cgroups),
+            pressure: MemoryPressure::read(),
+            vitals: self.vitals.read(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture() {
+        let snapshot = SystemSnapshot::capture();
+        assert!(snapshot.total > Bytes(0));
+        assert!(snapshot.available <= snapshot.total);
+        assert!(snapshot.processes.is_none());
+    }
+
+    #[test]
+    fn test_budget_prefers_tighter_cgroup() {
+        let mut snapshot = SystemSnapshot {
+            available: Bytes(8 << 30),
+            ..Default::default()
+        };
+        assert_eq!(snapshot.budget().scope, BudgetScope::Host);
+
+        snapshot.cgroup = Some(CgroupMemory { limit: Bytes(2 << 30), usage: Bytes(1 << 30) });
+        let budget = snapshot.budget();
+        assert_eq!(budget.scope, BudgetScope::Cgroup);
+        assert_eq!(budget.available, Bytes(1 << 30));
+        assert_eq!(budget.total, Bytes(2 << 30));
+    }
+
+    #[test]
+    fn test_fresh_snapshots() {
+        let mut fresh = FreshSnapshots::default();
+        let now = Instant::now();
+        let bare = SystemSnapshot { taken_at: now, ..Default::default() };
+        assert!(fresh.processes(&bare).is_none());
+        assert!(fresh.last().is_none());
+
+        let full = SystemSnapshot::fake(now, Vec::new());
+        assert!(fresh.processes(&full).is_some());
+        assert!(fresh.processes(&full).is_none());
+        assert!(!fresh.accept(&full));
+        assert_eq!(fresh.last(), Some(now));
+    }
+
+    #[test]
+    fn test_sampler_refreshes() {
+        let sampler = SystemSampler::spawn(Duration::from_millis(10));
+        let handle = sampler.handle();
+        let first = handle.latest().taken_at;
+
+        sampler.set_process_table(true);
+        thread::sleep(Duration::from_millis(100));
+
+        let latest = handle.latest();
+        assert!(latest.taken_at > first);
+        assert!(latest.processes.as_ref().is_some_and(|p| !p.is_empty()));
+    }
+}
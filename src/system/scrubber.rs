@@ -0,0 +1,257 @@
+// src/system/scrubber.rs
+// RAM Eating Pet Simulator - Memory Integrity Scrubber
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use super::food::{Corruption, FoodKind};
+use super::memory::SharedBlocks;
+use super::units::{Bytes, MiB};
+
+/// How often the scrubber wakes up to check its next batch of blocks
+const SCRUB_TICK: Duration = Duration::from_millis(100);
+
+/// A block whose contents changed behind our back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mismatch {
+    /// Index of the block in the pet's memory when it was checked
+    pub block: usize,
+    /// Food source the block came from
+    pub kind: FoodKind,
+    /// Address of the block's first byte
+    pub address: usize,
+    /// The first bad byte
+    pub corruption: Corruption,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "block #{} ({}) at {:#x}, offset {:#x}: expected {:#04x}, found {:#04x}",
+            self.block,
+            self.kind,
+            self.address + self.corruption.offset,
+            self.corruption.offset,
+            self.corruption.expected,
+            self.corruption.found,
+        )
+    }
+}
+
+/// Running totals of everything the scrubber has checked
+#[derive(Debug, Clone, Default)]
+pub struct ScrubReport {
+    /// Complete walks over the pet's memory
+    pub passes: u64,
+    /// Blocks verified
+    pub blocks_checked: u64,
+    /// Memory verified
+    pub checked: Bytes,
+    /// Every mismatch found, in order
+    pub mismatches: Vec<Mismatch>,
+}
+
+/// Background thread that reads the pet's memory back, like a tiny memtest
+///
+/// Blocks are filled with a seeded pattern when allocated (see
+/// [`FoodBlock::fill`](super::food::FoodBlock::fill)). The scrubber walks
+/// them at a fixed rate, taking the lock for one block at a time, and
+/// reports any that no longer match. A bad block is refilled so a
+/// stuck bit shows up again on the next pass.
+pub struct Scrubber {
+    events: Receiver<Mismatch>,
+    report: Arc<Mutex<ScrubReport>>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Scrubber {
+    /// Start scrubbing about `rate` of memory per second
+    pub fn spawn(blocks: SharedBlocks, rate: MiB) -> Self {
+        let (sender, events) = mpsc::channel();
+        let report = Arc::new(Mutex::new(ScrubReport::default()));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread = {
+            let stop = stop.clone();
+            let mut walker = Walker::new(blocks, report.clone());
+            // Spread the rate over the ticks in a second, at least a block each
+            let per_tick = MiB((rate.0 * SCRUB_TICK.as_millis() as usize / 1000).max(1));
+            thread::Builder::new()
+                .name("memory-scrubber".to_string())
+                .spawn(move || walker.run(per_tick, &stop, sender))
+                .expect("failed to spawn memory scrubber thread")
+        };
+
+        Scrubber {
+            events,
+            report,
+            stop,
+            thread: Some(thread),
+        }
+    }
+
+    /// Mismatches found since the last call
+    pub fn events(&self) -> impl Iterator<Item = Mismatch> + '_ {
+        self.events.try_iter()
+    }
+
+    /// Totals so far
+    pub fn report(&self) -> ScrubReport {
+        self.report.lock().unwrap().clone()
+    }
+}
+
+impl Drop for Scrubber {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            thread.thread().unpark();
+            let _ = thread.join();
+        }
+    }
+}
+
+/// The scrubber's position and totals, kept apart from the thread so it can
+/// be tested
+struct Walker {
+    blocks: SharedBlocks,
+    report: Arc<Mutex<ScrubReport>>,
+    /// Next block to check
+    cursor: usize,
+    /// Whether anything was checked since the cursor last wrapped
+    pass_started: bool,
+}
+
+impl Walker {
+    fn new(blocks: SharedBlocks, report: Arc<Mutex<ScrubReport>>) -> Self {
+        Walker {
+            blocks,
+            report,
+            cursor: 0,
+            pass_started: false,
+        }
+    }
+
+    fn run(&mut self, per_tick: MiB, stop: &AtomicBool, events: Sender<Mismatch>) {
+        while !stop.load(Ordering::Relaxed) {
+            thread::park_timeout(SCRUB_TICK);
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
+            for mismatch in self.scrub(per_tick) {
+                log::error!("Memory integrity: {}", mismatch);
+                // The game may already be gone; the report still has it
+                let _ = events.send(mismatch);
+            }
+        }
+    }
+
+    /// Verify the next `budget` worth of blocks, wrapping at the end
+    fn scrub(&mut self, budget: MiB) -> Vec<Mismatch> {
+        let mut found = Vec::new();
+        let mut checked = MiB(0);
+        let mut visited = 0;
+
+        while checked < budget {
+            // One block per lock so feeding and digesting aren't held up
+            let mut blocks = self.blocks.lock().unwrap();
+            if self.cursor >= blocks.len() {
+                self.cursor = 0;
+                if self.pass_started {
+                    self.pass_started = false;
+                    self.report.lock().unwrap().passes += 1;
+                }
+            }
+            // Nothing (left) worth checking, e.g. a lazy pet that's never filled
+            if visited >= blocks.len() {
+                break;
+            }
+
+            let index = self.cursor;
+            let block = &mut blocks[index];
+            self.cursor += 1;
+            visited += 1;
+            if !block.is_filled() {
+                continue;
+            }
+
+            let size = block.size();
+            let mismatch = block.verify().map(|corruption| Mismatch {
+                block: index,
+                kind: block.kind(),
+                address: block.address(),
+                corruption,
+            });
+            if let Some(content) = mismatch.and(block.content()) {
+                block.fill(content, rand::random());
+            }
+            drop(blocks);
+
+            checked += size;
+            self.pass_started = true;
+            let mut report = self.report.lock().unwrap();
+            report.blocks_checked += 1;
+            report.checked += size.to_bytes();
+            if let Some(mismatch) = mismatch {
+                report.mismatches.push(mismatch);
+                found.push(mismatch);
+            }
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::food::{FoodBlock, FoodContent, HeapSource};
+
+    fn filled_blocks(count: usize) -> SharedBlocks {
+        let blocks = (0..count)
+            .map(|seed| {
+                let mut block = FoodBlock::from_source(&HeapSource).unwrap();
+                block.fill(FoodContent::Random, seed as u64);
+                block
+            })
+            .collect();
+        Arc::new(Mutex::new(blocks))
+    }
+
+    #[test]
+    fn test_clean_memory_passes() {
+        let blocks = filled_blocks(4);
+        let report = Arc::new(Mutex::new(ScrubReport::default()));
+        let mut walker = Walker::new(blocks, report.clone());
+
+        assert!(walker.scrub(MiB(4)).is_empty());
+        assert!(walker.scrub(MiB(1)).is_empty()); // Wraps around
+
+        let report = report.lock().unwrap();
+        assert_eq!(report.passes, 1);
+        assert_eq!(report.blocks_checked, 5);
+        assert_eq!(report.checked, MiB(5).to_bytes());
+    }
+
+    #[test]
+    fn test_finds_corrupted_block() {
+        let blocks = filled_blocks(3);
+        blocks.lock().unwrap()[2].as_mut_slice()[12345] ^= 0x01;
+        let report = Arc::new(Mutex::new(ScrubReport::default()));
+        let mut walker = Walker::new(blocks.clone(), report.clone());
+
+        let found = walker.scrub(MiB(3));
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].block, 2);
+        assert_eq!(found[0].corruption.offset, 12345);
+        assert_eq!(report.lock().unwrap().mismatches, found);
+
+        // The block was rewritten, so it's clean on the next pass
+        assert!(walker.scrub(MiB(3)).is_empty());
+    }
+}
@@ -0,0 +1,205 @@
+// src/system/units.rs
+// RAM Eating Pet Simulator - Memory Size Units
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::str::FromStr;
+
+/// Bytes in one KiB
+const KIB: u64 = 1024;
+/// Bytes in one MiB
+const MIB: u64 = 1024 * 1024;
+
+/// A memory size in bytes
+///
+/// This is what sysinfo and the kernel report. Convert to [`MiB`] with
+/// [`Bytes::to_mib`] rather than dividing by hand.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Bytes(pub u64);
+
+/// A memory size in mebibytes
+///
+/// The game calls these "MB" everywhere it talks to the player; one pet
+/// "MB" is one 1,048,576-byte block.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct MiB(pub usize);
+
+impl Bytes {
+    /// Size from a count of KiB (as found in `/proc`)
+    pub const fn from_kib(kib: u64) -> Self {
+        Bytes(kib * KIB)
+    }
+
+    /// Whole MiB contained in this size (rounded down)
+    pub const fn to_mib(self) -> MiB {
+        MiB((self.0 / MIB) as usize)
+    }
+
+    /// Size in MiB including the fractional part
+    pub fn as_mib_f64(self) -> f64 {
+        self.0 as f64 / MIB as f64
+    }
+
+    /// Subtract without going below zero
+    pub const fn saturating_sub(self, other: Bytes) -> Bytes {
+        Bytes(self.0.saturating_sub(other.0))
+    }
+}
+
+impl MiB {
+    /// Size in bytes
+    pub const fn to_bytes(self) -> Bytes {
+        Bytes(self.0 as u64 * MIB)
+    }
+
+    /// Subtract without going below zero
+    pub const fn saturating_sub(self, other: MiB) -> MiB {
+        MiB(self.0.saturating_sub(other.0))
+    }
+
+    /// Signed difference `self - other` in MiB
+    pub const fn delta(self, other: MiB) -> i64 {
+        self.0 as i64 - other.0 as i64
+    }
+
+    /// Whether this is zero
+    pub const fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl From<MiB> for Bytes {
+    fn from(mib: MiB) -> Self {
+        mib.to_bytes()
+    }
+}
+
+impl Add for Bytes {
+    type Output = Bytes;
+    fn add(self, rhs: Bytes) -> Bytes {
+        Bytes(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for Bytes {
+    fn add_assign(&mut self, rhs: Bytes) {
+        self.0 += rhs.0;
+    }
+}
+
+impl Sub for Bytes {
+    type Output = Bytes;
+    fn sub(self, rhs: Bytes) -> Bytes {
+        Bytes(self.0 - rhs.0)
+    }
+}
+
+impl Sum for Bytes {
+    fn sum<I: Iterator<Item = Bytes>>(iter: I) -> Bytes {
+        Bytes(iter.map(|b| b.0).sum())
+    }
+}
+
+impl Add for MiB {
+    type Output = MiB;
+    fn add(self, rhs: MiB) -> MiB {
+        MiB(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for MiB {
+    fn add_assign(&mut self, rhs: MiB) {
+        self.0 += rhs.0;
+    }
+}
+
+impl Sub for MiB {
+    type Output = MiB;
+    fn sub(self, rhs: MiB) -> MiB {
+        MiB(self.0 - rhs.0)
+    }
+}
+
+impl SubAssign for MiB {
+    fn sub_assign(&mut self, rhs: MiB) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Sum for MiB {
+    fn sum<I: Iterator<Item = MiB>>(iter: I) -> MiB {
+        MiB(iter.map(|m| m.0).sum())
+    }
+}
+
+impl FromStr for MiB {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(MiB)
+    }
+}
+
+impl fmt::Display for MiB {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} MB", self.0)
+    }
+}
+
+impl fmt::Display for Bytes {
+    /// Human readable size (e.g. "1.50 GB")
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
+
+        if self.0 == 0 {
+            return write!(f, "0 B");
+        }
+
+        let base = KIB as f64;
+        let bytes = self.0 as f64;
+        let exponent = ((bytes.ln() / base.ln()).floor() as i32).min(UNITS.len() as i32 - 1);
+        let size = bytes / base.powi(exponent);
+        let unit = UNITS[exponent as usize];
+
+        if size >= 100.0 {
+            write!(f, "{:.0} {}", size, unit)
+        } else if size >= 10.0 {
+            write!(f, "{:.1} {}", size, unit)
+        } else {
+            write!(f, "{:.2} {}", size, unit)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(Bytes(1_048_576).to_mib(), MiB(1));
+        assert_eq!(Bytes(8 * 1024 * 1024 * 1024).to_mib(), MiB(8192));
+        assert_eq!(MiB(2).to_bytes(), Bytes(2_097_152));
+        assert_eq!(Bytes::from_kib(2048).to_mib(), MiB(2));
+        // Rounds down, never up
+        assert_eq!(Bytes(1_048_575).to_mib(), MiB(0));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(MiB(10) + MiB(5), MiB(15));
+        assert_eq!(MiB(5).saturating_sub(MiB(10)), MiB(0));
+        assert_eq!(MiB(5).delta(MiB(10)), -5);
+        assert_eq!([MiB(1), MiB(2)].into_iter().sum::<MiB>(), MiB(3));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(MiB(50).to_string(), "50 MB");
+        assert_eq!(Bytes(1536 * 1024 * 1024).to_string(), "1.50 GB");
+    }
+}
@@ -0,0 +1,157 @@
+// src/system/vitals.rs
+// RAM Eating Pet Simulator - Process Vital Signs (Linux)
+
+use std::fs;
+use std::time::Instant;
+
+use super::units::Bytes;
+
+/// Our own process's memory, as the kernel sees it
+///
+/// Read from `/proc/self/status`, `/proc/self/smaps_rollup` and
+/// `/proc/self/stat`. Fields the kernel doesn't provide stay zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ProcessVitals {
+    /// Resident set size (what `top` shows as RES)
+    pub rss: Bytes,
+    /// Proportional set size: shared pages split between their users
+    pub pss: Bytes,
+    /// Resident anonymous memory (heap, anonymous mappings)
+    pub anonymous: Bytes,
+    /// Resident file-backed memory
+    pub file: Bytes,
+    /// Resident shared memory (memfd, tmpfs)
+    pub shmem: Bytes,
+    /// Memory swapped out
+    pub swap: Bytes,
+    /// Page faults served without I/O
+    pub minor_faults: u64,
+    /// Page faults that had to read from disk or swap
+    pub major_faults: u64,
+    /// Minor faults per second since the previous reading
+    pub minor_fault_rate: f32,
+    /// Major faults per second since the previous reading
+    pub major_fault_rate: f32,
+}
+
+impl ProcessVitals {
+    /// Share of our memory that's swapped out (0.0 - 1.0)
+    pub fn swapped_fraction(&self) -> f32 {
+        let total = self.rss.0 + self.swap.0;
+        if total == 0 {
+            0.0
+        } else {
+            self.swap.0 as f32 / total as f32
+        }
+    }
+
+    /// Parse `/proc/self/status` (VmRSS, RssAnon, RssFile, RssShmem, VmSwap)
+    pub fn parse_status(&mut self, text: &str) {
+        for (key, value) in kib_fields(text) {
+            match key {
+                "VmRSS" => self.rss = value,
+                "RssAnon" => self.anonymous = value,
+                "RssFile" => self.file = value,
+                "RssShmem" => self.shmem = value,
+                "VmSwap" => self.swap = value,
+                _ => {}
+            }
+        }
+    }
+
+    /// Parse `/proc/self/smaps_rollup` (Pss, and Swap which is more exact)
+    pub fn parse_smaps_rollup(&mut self, text: &str) {
+        for (key, value) in kib_fields(text) {
+            match key {
+                "Pss" => self.pss = value,
+                "Swap" => self.swap = value,
+                _ => {}
+            }
+        }
+    }
+
+    /// Parse `/proc/self/stat` (minflt and majflt)
+    pub fn parse_stat(&mut self, text: &str) -> Option<()> {
+        // The command name may contain spaces; fields resume after its ')'
+        let (_, rest) = text.rsplit_once(')')?;
+        let fields: Vec<&str> = rest.split_whitespace().collect();
+        // Field 3 (state) is index 0, so minflt (10) is 7 and majflt (12) is 9
+        self.minor_faults = fields.get(7)?.parse().ok()?;
+        self.major_faults = fields.get(9)?.parse().ok()?;
+        Some(())
+    }
+}
+
+/// `Key:   123 kB` lines as byte sizes
+fn kib_fields(text: &str) -> impl Iterator<Item = (&str, Bytes)> {
+    text.lines().filter_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        let kib = value.trim().strip_suffix("kB")?.trim().parse().ok()?;
+        Some((key.trim(), Bytes::from_kib(kib)))
+    })
+}
+
+/// Reads vitals and turns fault counters into rates
+#[derive(Default)]
+pub struct VitalsReader {
+    previous: Option<(Instant, u64, u64)>,
+}
+
+impl VitalsReader {
+    /// Take a reading, if `/proc/self` is available
+    pub fn read(&mut self) -> Option<ProcessVitals> {
+        let mut vitals = ProcessVitals::default();
+        vitals.parse_status(&fs::read_to_string("/proc/self/status").ok()?);
+        if let Ok(rollup) = fs::read_to_string("/proc/self/smaps_rollup") {
+            vitals.parse_smaps_rollup(&rollup);
+        }
+        if let Ok(stat) = fs::read_to_string("/proc/self/stat") {
+            vitals.parse_stat(&stat);
+        }
+
+        let now = Instant::now();
+        if let Some((then, minor, major)) = self.previous {
+            let secs = now.duration_since(then).as_secs_f32();
+            if secs > 0.0 {
+                vitals.minor_fault_rate = vitals.minor_faults.saturating_sub(minor) as f32 / secs;
+                vitals.major_fault_rate = vitals.major_faults.saturating_sub(major) as f32 / secs;
+            }
+        }
+        self.previous = Some((now, vitals.minor_faults, vitals.major_faults));
+
+        Some(vitals)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_files() {
+        let mut vitals = ProcessVitals::default();
+        vitals.parse_status("Name:\tram_pet\nVmRSS:\t  3072 kB\nRssAnon:\t  2048 kB\n\
+                             RssFile:\t  1024 kB\nRssShmem:\t     0 kB\nVmSwap:\t  1024 kB\n");
+        vitals.parse_smaps_rollup("55d0-7ffc ---p 00000000 00:00 0  [rollup]\n\
+                                   Rss:  3072 kB\nPss:  2560 kB\nSwap:  1024 kB\n");
+        vitals.parse_stat("42 (ram pet) S 1 42 42 0 -1 4194304 1500 0 7 0 3 1").unwrap();
+
+        assert_eq!(vitals.rss, Bytes::from_kib(3072));
+        assert_eq!(vitals.pss, Bytes::from_kib(2560));
+        assert_eq!(vitals.anonymous, Bytes::from_kib(2048));
+        assert_eq!(vitals.file, Bytes::from_kib(1024));
+        assert_eq!(vitals.swap, Bytes::from_kib(1024));
+        assert_eq!(vitals.minor_faults, 1500);
+        assert_eq!(vitals.major_faults, 7);
+        assert_eq!(vitals.swapped_fraction(), 0.25);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_read_own_vitals() {
+        let mut reader = VitalsReader::default();
+        let vitals = reader.read().unwrap();
+        assert!(vitals.rss > Bytes(0));
+        assert!(vitals.minor_faults > 0);
+    }
+}